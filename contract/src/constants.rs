@@ -5,22 +5,69 @@ pub const PREFIX_ACCESS_KEY_NAME: &str = "swap_contract_package_access";
 pub const PREFIX_CONTRACT_NAME: &str = "swap_contract_hash";
 pub const PREFIX_CONTRACT_VERSION: &str = "swap_contract_version";
 pub const PREFIX_CONTRACT_PACKAGE_NAME: &str = "swap_contract_package";
+pub const INSTALLED_SWAPS: &str = "swap_contracts_installed";
+pub const PREFIX_FACTORY_ACCESS_KEY_NAME: &str = "swap_factory_package_access";
+pub const PREFIX_FACTORY_CONTRACT_NAME: &str = "swap_factory_contract_hash";
+pub const PREFIX_FACTORY_PACKAGE_NAME: &str = "swap_factory_package";
 
+pub const ENTRY_POINT_ADD_LIQUIDITY: &str = "add_liquidity";
+pub const ENTRY_POINT_ADD_POOL: &str = "add_pool";
 pub const ENTRY_POINT_ALLOWANCE: &str = "allowance";
 pub const ENTRY_POINT_APPROVE: &str = "approve";
 pub const ENTRY_POINT_BALANCE_COWL: &str = "balance_cowl";
 pub const ENTRY_POINT_BALANCE_CSPR: &str = "balance_cspr";
 pub const ENTRY_POINT_BALANCE_OF: &str = "balance_of";
+pub const ENTRY_POINT_BONDING_CURVE: &str = "bonding_curve";
 pub const ENTRY_POINT_CHANGE_SECURITY: &str = "change_security";
+pub const ENTRY_POINT_CLAIM: &str = "claim";
+pub const ENTRY_POINT_CLAIMABLE_OF: &str = "claimable_of";
+pub const ENTRY_POINT_CREATE_POOL: &str = "create_pool";
 pub const ENTRY_POINT_COWL_TO_CSPR: &str = "cowl_to_cspr";
 pub const ENTRY_POINT_CSPR_TO_COWL: &str = "cspr_to_cowl";
+pub const ENTRY_POINT_CSPR_TO_TOKEN: &str = "cspr_to_token";
+pub const ENTRY_POINT_DEPOSIT_COWL: &str = "deposit_cowl";
 pub const ENTRY_POINT_DEPOSIT_CSPR: &str = "deposit_cspr";
 pub const ENTRY_POINT_INSTALL: &str = "install";
+pub const ENTRY_POINT_INSTALL_FACTORY: &str = "install_factory";
+pub const ENTRY_POINT_LIQUIDITY_POSITION: &str = "liquidity_position";
+pub const ENTRY_POINT_ORACLE_FEED: &str = "oracle_feed";
+pub const ENTRY_POINT_POOL: &str = "pool";
+pub const ENTRY_POINT_POOLS: &str = "pools";
+pub const ENTRY_POINT_POOL_COUNT: &str = "pool_count";
+pub const ENTRY_POINT_POOL_TOKENS: &str = "pool_tokens";
+pub const ENTRY_POINT_PRICING_MODE: &str = "pricing_mode";
+pub const ENTRY_POINT_QUOTE_CSPR_TO_TOKEN: &str = "quote_cspr_to_token";
+pub const ENTRY_POINT_QUOTE_TOKEN_TO_CSPR: &str = "quote_token_to_cspr";
+pub const ENTRY_POINT_RECONCILE: &str = "reconcile";
+pub const ENTRY_POINT_REFERRAL_REWARDS: &str = "referral_rewards";
+pub const ENTRY_POINT_REFUND: &str = "refund";
+pub const ENTRY_POINT_REMOVE_LIQUIDITY: &str = "remove_liquidity";
+pub const ENTRY_POINT_RESERVES: &str = "reserves";
+pub const ENTRY_POINT_ROLLBACK: &str = "rollback";
+pub const ENTRY_POINT_SALE: &str = "sale";
+pub const ENTRY_POINT_SET_BONDING_CURVE: &str = "set_bonding_curve";
+pub const ENTRY_POINT_SET_CLAIM_START_TIME: &str = "set_claim_start_time";
 pub const ENTRY_POINT_SET_MODALITIES: &str = "set_modalities";
+pub const ENTRY_POINT_SET_ORACLE_CONFIG: &str = "set_oracle_config";
+pub const ENTRY_POINT_SET_PRICING_MODE: &str = "set_pricing_mode";
+pub const ENTRY_POINT_SET_REFERRAL_BONUS: &str = "set_referral_bonus";
+pub const ENTRY_POINT_SET_RESERVE_LIMITS: &str = "set_reserve_limits";
+pub const ENTRY_POINT_SET_SOFT_CAP: &str = "set_soft_cap";
+pub const ENTRY_POINT_SET_TOKEN_PAIR: &str = "set_token_pair";
+pub const ENTRY_POINT_SET_VESTING_SCHEDULE: &str = "set_vesting_schedule";
+pub const ENTRY_POINT_SWAP_COUNT: &str = "swap_count";
+pub const ENTRY_POINT_SWAP_STATS: &str = "swap_stats";
+pub const ENTRY_POINT_TOKEN_PAIR: &str = "token_pair";
+pub const ENTRY_POINT_TOKEN_SYMBOL: &str = "token_symbol";
+pub const ENTRY_POINT_TOKEN_TO_CSPR: &str = "token_to_cspr";
+pub const ENTRY_POINT_TOKEN_TO_TOKEN: &str = "token_to_token";
 pub const ENTRY_POINT_TRANSFER: &str = "transfer";
 pub const ENTRY_POINT_TRANSFER_FROM: &str = "transfer_from";
+pub const ENTRY_POINT_UPDATE_POOL: &str = "update_pool";
+pub const ENTRY_POINT_UPDATE_PRICE: &str = "update_price";
 pub const ENTRY_POINT_UPDATE_TIMES: &str = "update_times";
 pub const ENTRY_POINT_UPGRADE: &str = "upgrade";
+pub const ENTRY_POINT_VESTING_ALLOCATION: &str = "vesting_allocation";
 pub const ENTRY_POINT_WITHDRAW_COWL: &str = "withdraw_cowl";
 pub const ENTRY_POINT_WITHDRAW_CSPR: &str = "withdraw_cspr";
 
@@ -28,27 +75,113 @@ pub const ARG_ADDRESS: &str = "address";
 pub const ARG_AMOUNT: &str = "amount";
 pub const ARG_BALANCE_COWL: &str = "balance_cowl";
 pub const ARG_BALANCE_CSPR: &str = "balance_cspr";
+pub const ARG_CLAIM_START_TIME: &str = "claim_start_time";
+pub const ARG_CLIFF_DURATION: &str = "cliff_duration";
 pub const ARG_CONTRACT_HASH: &str = "contract_hash";
+pub const ARG_CONTRACT_VERSION: &str = "contract_version";
 pub const ARG_COWL_CEP18_CONTRACT_PACKAGE: &str = "cowl_cep18_contract_package";
 pub const ARG_COWL_SWAP_CONTRACT_PACKAGE: &str = "cowl_swap_contract_package";
+pub const ARG_CURVE_STEPS: &str = "curve_steps";
 pub const ARG_DURATION: &str = "duration";
+pub const ARG_ENABLED: &str = "enabled";
 pub const ARG_END_TIME: &str = "end_time";
 pub const ARG_EVENTS_MODE: &str = "events_mode";
+pub const ARG_EVENT_SEQUENCE: &str = "event_sequence";
+pub const ARG_FACTORY: &str = "factory";
+pub const ARG_FACTORY_PACKAGE: &str = "factory_package";
+pub const ARG_INPUT_DECIMALS: &str = "input_decimals";
+pub const ARG_INPUT_TOKEN_PACKAGE: &str = "input_token_package";
 pub const ARG_INSTALLER: &str = "installer";
+pub const ARG_LIMIT: &str = "limit";
+pub const ARG_MAX_AGE: &str = "max_age";
+pub const ARG_MAX_DEVIATION: &str = "max_deviation";
+pub const ARG_MIN_INPUT_AMOUNT: &str = "min_input_amount";
+pub const ARG_MIN_RESERVE_COWL: &str = "min_reserve_cowl";
+pub const ARG_MIN_RESERVE_CSPR: &str = "min_reserve_cspr";
 pub const ARG_NAME: &str = "name";
+pub const ARG_NATIVE_EVENTS_COUNT: &str = "native_events_count";
+pub const ARG_OFFSET: &str = "offset";
+pub const ARG_OUTPUT_DECIMALS: &str = "output_decimals";
+pub const ARG_OUTPUT_TOKEN_PACKAGE: &str = "output_token_package";
 pub const ARG_OWNER: &str = "owner";
+pub const ARG_PAIR_RATE: &str = "pair_rate";
 pub const ARG_PACKAGE_HASH: &str = "package_hash";
+pub const ARG_PAUSED: &str = "paused";
+pub const ARG_PHASE: &str = "phase";
+pub const ARG_PHASE_END_TIME: &str = "phase_end_time";
+pub const ARG_POOL: &str = "pool";
+pub const ARG_POOL_COUNT: &str = "pool_count";
+pub const ARG_POOL_TOKENS: &str = "pool_tokens";
+pub const ARG_PRICE: &str = "price";
+pub const ARG_PRICING_MODE: &str = "pricing_mode";
+pub const ARG_PREVIOUS_CONTRACT_HASH: &str = "previous_contract_hash";
+pub const ARG_PREVIOUS_CONTRACT_VERSION: &str = "previous_contract_version";
 pub const ARG_PURSE: &str = "purse";
+pub const ARG_RATE_TIERS: &str = "rate_tiers";
 pub const ARG_RECIPIENT: &str = "recipient";
+pub const ARG_REFERRAL_BONUS: &str = "referral_bonus";
+pub const ARG_REFERRER: &str = "referrer";
+pub const ARG_ROLLBACK_VERSION: &str = "rollback_version";
+pub const ARG_SHARES: &str = "shares";
+pub const ARG_SOFT_CAP: &str = "soft_cap";
 pub const ARG_SPENDER: &str = "spender";
+pub const ARG_SPREAD: &str = "spread";
 pub const ARG_START_TIME: &str = "start_time";
+pub const ARG_STORAGE_VERSION: &str = "storage_version";
+pub const ARG_SWAP_COUNT: &str = "swap_count";
+pub const ARG_SWEEP: &str = "sweep";
+pub const ARG_TAX_RATE: &str = "tax_rate";
+pub const ARG_TIMESTAMP: &str = "timestamp";
+pub const ARG_TOKEN_AMOUNT: &str = "token_amount";
+pub const ARG_TOKEN_CONTRACT_PACKAGE: &str = "token_contract_package";
+pub const ARG_TOKEN_DECIMALS: &str = "token_decimals";
+pub const ARG_TOKEN_SYMBOL: &str = "token_symbol";
 pub const ARG_UPGRADE_FLAG: &str = "upgrade";
+pub const ARG_VESTING_DURATION: &str = "vesting_duration";
+pub const ARG_VESTING_START_TIME: &str = "vesting_start_time";
+pub const ARG_WARNING_RESERVE_COWL: &str = "warning_reserve_cowl";
+pub const ARG_WARNING_RESERVE_CSPR: &str = "warning_reserve_cspr";
 
+pub const DICT_BONDING_CURVES: &str = "bonding_curves";
+pub const DICT_CLAIM_START_TIMES: &str = "claim_start_times";
+pub const DICT_CONTRACT_NAMED_KEYS: &str = "contract_named_keys";
+pub const DICT_CONTRACT_VERSIONS: &str = "contract_versions";
+pub const DICT_CONTRIBUTIONS: &str = "contributions";
+pub const DICT_COWL_DEPOSITS: &str = "cowl_deposits";
+pub const DICT_LIQUIDITY: &str = "liquidity";
+pub const DICT_LIQUIDITY_POSITIONS: &str = "liquidity_positions";
+pub const DICT_NATIVE_EVENTS: &str = "native_events";
+pub const DICT_ORACLE_FEEDS: &str = "oracle_feeds";
+pub const DICT_POOLS: &str = "pools";
+pub const DICT_POOL_PRICING_MODES: &str = "pool_pricing_modes";
+pub const DICT_REFERRALS: &str = "referrals";
+pub const DICT_RESERVES: &str = "reserves";
+pub const DICT_SALES: &str = "sales";
 pub const DICT_SECURITY_BADGES: &str = "security_badges";
+pub const DICT_POOL_SWAP_STATS: &str = "pool_swap_stats";
+pub const DICT_SWAP_POOLS: &str = "swap_pools";
+pub const DICT_SWAP_STATS: &str = "swap_stats";
+pub const DICT_TOKEN_PAIRS: &str = "token_pairs";
+pub const DICT_VESTING_ALLOCATIONS: &str = "vesting_allocations";
+pub const DICT_VESTING_PHASES: &str = "vesting_phases";
+pub const DICT_VESTING_SCHEDULES: &str = "vesting_schedules";
 
 pub const ADMIN_LIST: &str = "admin_list";
 pub const MINTER_LIST: &str = "minter_list";
 pub const NONE_LIST: &str = "none_list";
+pub const ORACLE_LIST: &str = "oracle_list";
+
+pub const CSPR_DECIMALS: u8 = 9;
+/// Token metadata of swaps installed before the token was configurable.
+pub const DEFAULT_TOKEN_SYMBOL: &str = "COWL";
+pub const DEFAULT_TOKEN_DECIMALS: u8 = 9;
+pub const MAX_TOKEN_DECIMALS: u8 = 18;
+
+/// Maximum number of pools returned by one `pools` call.
+pub const MAX_POOLS_PAGE: u64 = 50;
+
+/// Fixed-point scale of token pair rates, so a 1:1 pair has a rate of `10^9`.
+pub const PAIR_RATE_PRECISION: U512 = U512([1_000_000_000u64, 0, 0, 0, 0, 0, 0, 0]);
 
 pub const MIN_SWAP_AMOUNT: U512 = U512([10_000_000_000u64, 0, 0, 0, 0, 0, 0, 0]);
 
@@ -72,3 +205,8 @@ pub const RATE_TIERS: [RateTier; 4] = [
 ];
 
 pub const TAX_RATE: U512 = U512([10, 0, 0, 0, 0, 0, 0, 0]);
+/// Denominator of rates given in basis points.
+pub const MAX_BASIS_POINTS: u32 = 10_000;
+
+/// Highest tax a pool can be configured with, in percent.
+pub const MAX_TAX_RATE: U512 = U512([100, 0, 0, 0, 0, 0, 0, 0]);
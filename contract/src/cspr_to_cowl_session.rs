@@ -15,8 +15,12 @@ use casper_contract::{
 };
 use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U512};
 use cowl_swap::{
-    constants::{ARG_AMOUNT, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_PURSE, ENTRY_POINT_CSPR_TO_COWL},
+    constants::{
+        ARG_AMOUNT, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_POOL, ARG_PURSE, ARG_REFERRER,
+        ENTRY_POINT_CSPR_TO_COWL,
+    },
     error::SwapError,
+    utils::get_optional_named_arg_with_user_errors,
 };
 
 #[no_mangle]
@@ -36,13 +40,28 @@ pub extern "C" fn call() {
 
     transfer_from_purse_to_purse(source_purse, local_purse, amount, None).unwrap_or_revert();
 
+    let mut runtime_args = runtime_args! {
+        ARG_AMOUNT => amount,
+        ARG_PURSE => local_purse
+    };
+    // Without a pool the contract swaps in its default pool
+    if let Some(pool) =
+        get_optional_named_arg_with_user_errors::<Key>(ARG_POOL, SwapError::InvalidPool)
+    {
+        runtime_args.insert(ARG_POOL, pool).unwrap_or_revert();
+    }
+    if let Some(referrer) =
+        get_optional_named_arg_with_user_errors::<Key>(ARG_REFERRER, SwapError::InvalidReferrer)
+    {
+        runtime_args
+            .insert(ARG_REFERRER, referrer)
+            .unwrap_or_revert();
+    }
+
     call_versioned_contract::<()>(
         cowl_swap_contract_package_key_hash,
         None,
         ENTRY_POINT_CSPR_TO_COWL,
-        runtime_args! {
-            ARG_AMOUNT => amount,
-            ARG_PURSE => local_purse
-        },
+        runtime_args,
     );
 }
@@ -0,0 +1,192 @@
+//! Bonding curve sale of a pool, swaps being integrated across every step they cross.
+use alloc::vec::Vec;
+use casper_types::U512;
+
+use crate::{
+    constants::CSPR_DECIMALS, error::SwapError, rate::token_to_cspr_amount, utils::tuple_typed,
+};
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::DICT_BONDING_CURVES,
+    pools::{pool_item_key, Pool},
+    utils::{get_dictionary_value_from_key, set_dictionary_value_for_key},
+};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+
+/// Fixed-point scale of curve rates, so a rate of one whole token per CSPR is `10^9`.
+pub const CURVE_RATE_PRECISION: U512 = U512([1_000_000_000u64, 0, 0, 0, 0, 0, 0, 0]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CurveStep {
+    /// Cumulative tokens sold, in the token's smallest unit, up to which the rate applies.
+    pub sold_until: U512,
+    /// Whole tokens per CSPR scaled by [`CURVE_RATE_PRECISION`].
+    pub rate: U512,
+}
+
+tuple_typed! {
+    CurveStep as (U512, U512) {
+        sold_until, rate
+    }
+}
+
+/// Checks a curve's steps: at least one, strictly increasing thresholds and rates that never rise.
+pub fn validate_curve_steps(steps: &[CurveStep]) -> Result<(), SwapError> {
+    let first = steps.first().ok_or(SwapError::InvalidBondingCurve)?;
+    if first.sold_until.is_zero() || steps.iter().any(|step| step.rate.is_zero()) {
+        return Err(SwapError::InvalidBondingCurve);
+    }
+    if steps
+        .windows(2)
+        .any(|steps| steps[0].sold_until >= steps[1].sold_until || steps[0].rate < steps[1].rate)
+    {
+        return Err(SwapError::InvalidBondingCurve);
+    }
+    Ok(())
+}
+
+/// Motes paying for `token_amount` at `rate`, rounded up so the curve never undercharges.
+fn step_cost(token_amount: U512, rate: U512, token_decimals: u8) -> Result<U512, SwapError> {
+    let numerator = token_amount
+        .checked_mul(U512::exp10(CSPR_DECIMALS.into()))
+        .and_then(|value| value.checked_mul(CURVE_RATE_PRECISION))
+        .ok_or(SwapError::Overflow)?;
+    let denominator = rate
+        .checked_mul(U512::exp10(token_decimals.into()))
+        .ok_or(SwapError::Overflow)?;
+    let cost = numerator / denominator;
+    if (numerator % denominator).is_zero() {
+        Ok(cost)
+    } else {
+        cost.checked_add(U512::one()).ok_or(SwapError::Overflow)
+    }
+}
+
+/// Tokens bought with `cspr_amount` at `rate`, rounded down.
+fn step_tokens(cspr_amount: U512, rate: U512, token_decimals: u8) -> Result<U512, SwapError> {
+    cspr_amount
+        .checked_mul(rate)
+        .and_then(|value| value.checked_mul(U512::exp10(token_decimals.into())))
+        .map(|value| value / U512::exp10(CSPR_DECIMALS.into()) / CURVE_RATE_PRECISION)
+        .ok_or(SwapError::Overflow)
+}
+
+/// Curve of a pool and the tokens sold on it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BondingCurve {
+    pub steps: Vec<CurveStep>,
+    pub sold: U512,
+}
+
+impl BondingCurve {
+    /// Rate of the next token sold, `None` once the curve is sold out.
+    pub fn current_rate(&self) -> Option<U512> {
+        self.steps
+            .iter()
+            .find(|step| self.sold < step.sold_until)
+            .map(|step| step.rate)
+    }
+
+    /// Tokens bought with `cspr_amount` motes from the current point of the curve.
+    pub fn buy(&self, cspr_amount: U512, token_decimals: u8) -> Result<U512, SwapError> {
+        let mut remaining = cspr_amount;
+        let mut sold = self.sold;
+        for step in self.steps.iter().filter(|step| self.sold < step.sold_until) {
+            let capacity = step
+                .sold_until
+                .checked_sub(sold)
+                .ok_or(SwapError::Overflow)?;
+            let cost = step_cost(capacity, step.rate, token_decimals)?;
+            if remaining < cost {
+                let tokens = step_tokens(remaining, step.rate, token_decimals)?;
+                return sold
+                    .checked_add(tokens)
+                    .and_then(|sold| sold.checked_sub(self.sold))
+                    .ok_or(SwapError::Overflow);
+            }
+            remaining = remaining.checked_sub(cost).ok_or(SwapError::Overflow)?;
+            sold = step.sold_until;
+            if remaining.is_zero() {
+                return sold.checked_sub(self.sold).ok_or(SwapError::Overflow);
+            }
+        }
+        Err(SwapError::BondingCurveSoldOut)
+    }
+
+    /// Motes paid back for `token_amount` tokens returned down the curve, before the pool tax.
+    pub fn sell(&self, token_amount: U512, token_decimals: u8) -> Result<U512, SwapError> {
+        if token_amount > self.sold {
+            return Err(SwapError::InsufficientLiquidity);
+        }
+        let mut remaining = token_amount;
+        let mut sold = self.sold;
+        let mut cspr_amount = U512::zero();
+        for (index, step) in self.steps.iter().enumerate().rev() {
+            let step_start = if index == 0 {
+                U512::zero()
+            } else {
+                self.steps[index - 1].sold_until
+            };
+            if sold <= step_start {
+                continue;
+            }
+            let tokens = remaining.min(sold.checked_sub(step_start).ok_or(SwapError::Overflow)?);
+            let step_amount = token_to_cspr_amount(
+                tokens
+                    .checked_mul(CURVE_RATE_PRECISION)
+                    .ok_or(SwapError::Overflow)?,
+                step.rate,
+                token_decimals,
+            )
+            .ok_or(SwapError::Overflow)?;
+            cspr_amount = cspr_amount
+                .checked_add(step_amount)
+                .ok_or(SwapError::Overflow)?;
+            remaining = remaining.checked_sub(tokens).ok_or(SwapError::Overflow)?;
+            sold = sold.checked_sub(tokens).ok_or(SwapError::Overflow)?;
+            if remaining.is_zero() {
+                break;
+            }
+        }
+        Ok(cspr_amount)
+    }
+}
+
+tuple_typed! {
+    BondingCurve as (Vec<(U512, U512)>, U512) {
+        steps, sold
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_bonding_curve(pool: &Pool) -> Option<BondingCurve> {
+    get_dictionary_value_from_key(DICT_BONDING_CURVES, &pool_item_key(pool))
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_bonding_curve(pool: &Pool, curve: &BondingCurve) {
+    set_dictionary_value_for_key(DICT_BONDING_CURVES, &pool_item_key(pool), curve)
+}
+
+/// Moves the curve of the pool up by `token_amount` tokens bought.
+#[cfg(feature = "contract-support")]
+pub fn record_curve_purchase(pool: &Pool, token_amount: U512) {
+    let mut curve = get_bonding_curve(pool).unwrap_or_revert_with(SwapError::MissingBondingCurve);
+    curve.sold = curve
+        .sold
+        .checked_add(token_amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    save_bonding_curve(pool, &curve);
+}
+
+/// Moves the curve of the pool down by `token_amount` tokens sold back.
+#[cfg(feature = "contract-support")]
+pub fn record_curve_sale(pool: &Pool, token_amount: U512) {
+    let mut curve = get_bonding_curve(pool).unwrap_or_revert_with(SwapError::MissingBondingCurve);
+    curve.sold = curve
+        .sold
+        .checked_sub(token_amount)
+        .unwrap_or_revert_with(SwapError::InsufficientLiquidity);
+    save_bonding_curve(pool, &curve);
+}
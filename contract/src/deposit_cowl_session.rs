@@ -16,8 +16,8 @@ use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U256};
 use cowl_swap::{
     constants::{
         ARG_AMOUNT, ARG_BALANCE_COWL, ARG_COWL_CEP18_CONTRACT_PACKAGE,
-        ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_RECIPIENT, ENTRY_POINT_BALANCE_COWL,
-        ENTRY_POINT_TRANSFER,
+        ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_POOL, ARG_SPENDER, ENTRY_POINT_APPROVE,
+        ENTRY_POINT_DEPOSIT_COWL,
     },
     error::SwapError,
 };
@@ -48,9 +48,9 @@ pub extern "C" fn call() {
     call_versioned_contract::<()>(
         cowl_cep18_contract_package,
         None,
-        ENTRY_POINT_TRANSFER,
+        ENTRY_POINT_APPROVE,
         runtime_args! {
-            ARG_RECIPIENT => cowl_swap_contract_package_key,
+            ARG_SPENDER => cowl_swap_contract_package_key,
             ARG_AMOUNT => amount
         },
     );
@@ -58,8 +58,11 @@ pub extern "C" fn call() {
     let balance = call_versioned_contract::<U256>(
         cowl_swap_contract_package_key_hash,
         None,
-        ENTRY_POINT_BALANCE_COWL,
-        runtime_args! {},
+        ENTRY_POINT_DEPOSIT_COWL,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_POOL => cowl_cep18_contract_package_key
+        },
     );
     let new_uref = new_uref(balance);
     put_key(ARG_BALANCE_COWL, new_uref.into());
@@ -14,13 +14,14 @@ use casper_contract::{
     },
     unwrap_or_revert::UnwrapOrRevert,
 };
-use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U256, U512};
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U512};
 use cowl_swap::{
     constants::{
-        ARG_AMOUNT, ARG_BALANCE_CSPR, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_PURSE,
+        ARG_AMOUNT, ARG_BALANCE_CSPR, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_POOL, ARG_PURSE,
         ENTRY_POINT_DEPOSIT_CSPR,
     },
     error::SwapError,
+    utils::get_optional_named_arg_with_user_errors,
 };
 
 #[no_mangle]
@@ -40,14 +41,22 @@ pub extern "C" fn call() {
 
     transfer_from_purse_to_purse(source_purse, local_purse, amount, None).unwrap_or_revert();
 
-    let balance = call_versioned_contract::<U256>(
+    let mut runtime_args = runtime_args! {
+        ARG_AMOUNT => amount,
+        ARG_PURSE => local_purse
+    };
+    // Without a pool the deposit is accounted to the default pool
+    if let Some(pool) =
+        get_optional_named_arg_with_user_errors::<Key>(ARG_POOL, SwapError::InvalidPool)
+    {
+        runtime_args.insert(ARG_POOL, pool).unwrap_or_revert();
+    }
+
+    let balance = call_versioned_contract::<U512>(
         cowl_swap_contract_package_key_hash,
         None,
         ENTRY_POINT_DEPOSIT_CSPR,
-        runtime_args! {
-            ARG_AMOUNT => amount,
-            ARG_PURSE => local_purse
-        },
+        runtime_args,
     );
 
     let new_uref = new_uref(balance);
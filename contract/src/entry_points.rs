@@ -1,14 +1,42 @@
 //! Contains definition of the entry points.
 use crate::constants::{
-    ADMIN_LIST, ARG_AMOUNT, ARG_CONTRACT_HASH, ARG_END_TIME, ARG_EVENTS_MODE, ARG_PURSE,
-    ARG_RECIPIENT, ARG_START_TIME, ENTRY_POINT_BALANCE_COWL, ENTRY_POINT_BALANCE_CSPR,
-    ENTRY_POINT_CHANGE_SECURITY, ENTRY_POINT_COWL_TO_CSPR, ENTRY_POINT_CSPR_TO_COWL,
-    ENTRY_POINT_DEPOSIT_CSPR, ENTRY_POINT_INSTALL, ENTRY_POINT_SET_MODALITIES,
-    ENTRY_POINT_UPDATE_TIMES, ENTRY_POINT_UPGRADE, ENTRY_POINT_WITHDRAW_COWL,
-    ENTRY_POINT_WITHDRAW_CSPR, NONE_LIST,
+    ADMIN_LIST, ARG_ADDRESS, ARG_AMOUNT, ARG_CLAIM_START_TIME, ARG_CLIFF_DURATION,
+    ARG_CONTRACT_HASH, ARG_CONTRACT_VERSION, ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_CURVE_STEPS,
+    ARG_DURATION, ARG_ENABLED, ARG_END_TIME, ARG_EVENTS_MODE, ARG_INPUT_DECIMALS,
+    ARG_INPUT_TOKEN_PACKAGE, ARG_LIMIT, ARG_MAX_AGE, ARG_MAX_DEVIATION, ARG_MIN_INPUT_AMOUNT,
+    ARG_MIN_RESERVE_COWL, ARG_MIN_RESERVE_CSPR, ARG_NAME, ARG_OFFSET, ARG_OUTPUT_DECIMALS,
+    ARG_OUTPUT_TOKEN_PACKAGE, ARG_PACKAGE_HASH, ARG_PAIR_RATE, ARG_PAUSED, ARG_PHASE,
+    ARG_PHASE_END_TIME, ARG_POOL, ARG_PREVIOUS_CONTRACT_HASH, ARG_PREVIOUS_CONTRACT_VERSION,
+    ARG_PRICE, ARG_PRICING_MODE, ARG_PURSE, ARG_RATE_TIERS, ARG_RECIPIENT, ARG_REFERRAL_BONUS,
+    ARG_REFERRER, ARG_ROLLBACK_VERSION, ARG_SHARES, ARG_SOFT_CAP, ARG_SPREAD, ARG_START_TIME,
+    ARG_SWEEP, ARG_TAX_RATE, ARG_TIMESTAMP, ARG_TOKEN_AMOUNT, ARG_TOKEN_CONTRACT_PACKAGE,
+    ARG_TOKEN_DECIMALS, ARG_TOKEN_SYMBOL, ARG_VESTING_DURATION, ARG_VESTING_START_TIME,
+    ARG_WARNING_RESERVE_COWL, ARG_WARNING_RESERVE_CSPR, ENTRY_POINT_ADD_LIQUIDITY,
+    ENTRY_POINT_ADD_POOL, ENTRY_POINT_BALANCE_COWL, ENTRY_POINT_BALANCE_CSPR,
+    ENTRY_POINT_BONDING_CURVE, ENTRY_POINT_CHANGE_SECURITY, ENTRY_POINT_CLAIM,
+    ENTRY_POINT_CLAIMABLE_OF, ENTRY_POINT_COWL_TO_CSPR, ENTRY_POINT_CREATE_POOL,
+    ENTRY_POINT_CSPR_TO_COWL, ENTRY_POINT_CSPR_TO_TOKEN, ENTRY_POINT_DEPOSIT_COWL,
+    ENTRY_POINT_DEPOSIT_CSPR, ENTRY_POINT_INSTALL, ENTRY_POINT_INSTALL_FACTORY,
+    ENTRY_POINT_LIQUIDITY_POSITION, ENTRY_POINT_ORACLE_FEED, ENTRY_POINT_POOL, ENTRY_POINT_POOLS,
+    ENTRY_POINT_POOL_COUNT, ENTRY_POINT_POOL_TOKENS, ENTRY_POINT_PRICING_MODE,
+    ENTRY_POINT_QUOTE_CSPR_TO_TOKEN, ENTRY_POINT_QUOTE_TOKEN_TO_CSPR, ENTRY_POINT_RECONCILE,
+    ENTRY_POINT_REFERRAL_REWARDS, ENTRY_POINT_REFUND, ENTRY_POINT_REMOVE_LIQUIDITY,
+    ENTRY_POINT_RESERVES, ENTRY_POINT_ROLLBACK, ENTRY_POINT_SALE, ENTRY_POINT_SET_BONDING_CURVE,
+    ENTRY_POINT_SET_CLAIM_START_TIME, ENTRY_POINT_SET_MODALITIES, ENTRY_POINT_SET_ORACLE_CONFIG,
+    ENTRY_POINT_SET_PRICING_MODE, ENTRY_POINT_SET_REFERRAL_BONUS, ENTRY_POINT_SET_RESERVE_LIMITS,
+    ENTRY_POINT_SET_SOFT_CAP, ENTRY_POINT_SET_TOKEN_PAIR, ENTRY_POINT_SET_VESTING_SCHEDULE,
+    ENTRY_POINT_SWAP_COUNT, ENTRY_POINT_SWAP_STATS, ENTRY_POINT_TOKEN_PAIR,
+    ENTRY_POINT_TOKEN_SYMBOL, ENTRY_POINT_TOKEN_TO_CSPR, ENTRY_POINT_TOKEN_TO_TOKEN,
+    ENTRY_POINT_UPDATE_POOL, ENTRY_POINT_UPDATE_PRICE, ENTRY_POINT_UPDATE_TIMES,
+    ENTRY_POINT_UPGRADE, ENTRY_POINT_VESTING_ALLOCATION, ENTRY_POINT_WITHDRAW_COWL,
+    ENTRY_POINT_WITHDRAW_CSPR, NONE_LIST, ORACLE_LIST,
 };
+use crate::{factory::PoolInfo, history::SwapStats, pairs::TokenPair, pools::Pool};
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
-use casper_types::{CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter};
+use casper_types::{
+    CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter,
+    URef,
+};
 
 /// Returns the `init` entry point.
 pub fn install() -> EntryPoint {
@@ -28,6 +56,8 @@ pub fn cspr_to_cowl() -> EntryPoint {
             Parameter::new(ARG_AMOUNT, CLType::U512),
             Parameter::new(ARG_RECIPIENT, CLType::Key),
             Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_REFERRER, CLType::Key),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -38,9 +68,40 @@ pub fn cspr_to_cowl() -> EntryPoint {
 pub fn cowl_to_cspr() -> EntryPoint {
     EntryPoint::new(
         ENTRY_POINT_COWL_TO_CSPR,
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+            Parameter::new(ARG_RECIPIENT, CLType::Key),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn cspr_to_token() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_CSPR_TO_TOKEN,
         vec![
             Parameter::new(ARG_AMOUNT, CLType::U512),
             Parameter::new(ARG_RECIPIENT, CLType::Key),
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_REFERRER, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn token_to_cspr() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_TOKEN_TO_CSPR,
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+            Parameter::new(ARG_RECIPIENT, CLType::Key),
+            Parameter::new(ARG_POOL, CLType::Key),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -54,6 +115,7 @@ pub fn update_times() -> EntryPoint {
         vec![
             Parameter::new(ARG_START_TIME, CLType::U64),
             Parameter::new(ARG_END_TIME, CLType::U64),
+            Parameter::new(ARG_POOL, CLType::Key),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -64,7 +126,10 @@ pub fn update_times() -> EntryPoint {
 pub fn withdraw_cspr() -> EntryPoint {
     EntryPoint::new(
         ENTRY_POINT_WITHDRAW_CSPR,
-        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
         CLType::U512,
         EntryPointAccess::Public,
         EntryPointType::Contract,
@@ -74,17 +139,36 @@ pub fn withdraw_cspr() -> EntryPoint {
 pub fn withdraw_cowl() -> EntryPoint {
     EntryPoint::new(
         ENTRY_POINT_WITHDRAW_COWL,
-        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
         CLType::U512,
         EntryPointAccess::Public,
         EntryPointType::Contract,
     )
 }
 
+pub fn deposit_cowl() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_DEPOSIT_COWL,
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
 pub fn deposit_cspr() -> EntryPoint {
     EntryPoint::new(
         ENTRY_POINT_DEPOSIT_CSPR,
-        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+        vec![
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
         CLType::U512,
         EntryPointAccess::Public,
         EntryPointType::Contract,
@@ -94,7 +178,7 @@ pub fn deposit_cspr() -> EntryPoint {
 pub fn balance_cowl() -> EntryPoint {
     EntryPoint::new(
         ENTRY_POINT_BALANCE_COWL,
-        vec![],
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
         CLType::U512,
         EntryPointAccess::Public,
         EntryPointType::Contract,
@@ -111,10 +195,524 @@ pub fn balance_cspr() -> EntryPoint {
     )
 }
 
+pub fn reserves() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_RESERVES,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::Tuple2([
+            Box::new(CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::U512),
+            }),
+            Box::new(CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::U256),
+            }),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn reconcile() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_RECONCILE,
+        vec![
+            Parameter::new(ARG_SWEEP, CLType::Bool),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U256)]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn rollback() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_ROLLBACK,
+        vec![Parameter::new(ARG_ROLLBACK_VERSION, CLType::U32)],
+        CLType::Key,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_reserve_limits() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_RESERVE_LIMITS,
+        vec![
+            Parameter::new(ARG_MIN_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_MIN_RESERVE_COWL, CLType::U256),
+            Parameter::new(ARG_WARNING_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_WARNING_RESERVE_COWL, CLType::U256),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_stats() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SWAP_STATS,
+        vec![
+            Parameter::new(ARG_ADDRESS, CLType::Key),
+            Parameter::new(ARG_POOL, CLType::Key),
+        ],
+        SwapStats::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn swap_count() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SWAP_COUNT,
+        vec![],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn token_symbol() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_TOKEN_SYMBOL,
+        vec![],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_token_pair() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_TOKEN_PAIR,
+        vec![
+            Parameter::new(ARG_INPUT_TOKEN_PACKAGE, CLType::Key),
+            Parameter::new(ARG_OUTPUT_TOKEN_PACKAGE, CLType::Key),
+            Parameter::new(ARG_INPUT_DECIMALS, CLType::U8),
+            Parameter::new(ARG_OUTPUT_DECIMALS, CLType::U8),
+            Parameter::new(ARG_PAIR_RATE, CLType::U512),
+            Parameter::new(ARG_MIN_INPUT_AMOUNT, CLType::U512),
+            Parameter::new(ARG_ENABLED, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn token_pair() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_TOKEN_PAIR,
+        vec![
+            Parameter::new(ARG_INPUT_TOKEN_PACKAGE, CLType::Key),
+            Parameter::new(ARG_OUTPUT_TOKEN_PACKAGE, CLType::Key),
+        ],
+        CLType::Option(Box::new(TokenPair::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn token_to_token() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_TOKEN_TO_TOKEN,
+        vec![
+            Parameter::new(ARG_INPUT_TOKEN_PACKAGE, CLType::Key),
+            Parameter::new(ARG_OUTPUT_TOKEN_PACKAGE, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Rate tiers argument, `(cspr_amount, rate)` tuples in increasing CSPR thresholds.
+fn rate_tiers_type() -> CLType {
+    CLType::List(Box::new(CLType::Tuple2([
+        Box::new(CLType::U512),
+        Box::new(CLType::U512),
+    ])))
+}
+
+pub fn add_pool() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_ADD_POOL,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_TOKEN_DECIMALS, CLType::U8),
+            Parameter::new(ARG_RATE_TIERS, rate_tiers_type()),
+            Parameter::new(ARG_TAX_RATE, CLType::U512),
+            Parameter::new(ARG_START_TIME, CLType::U64),
+            Parameter::new(ARG_DURATION, CLType::U64),
+            Parameter::new(ARG_MIN_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_MIN_RESERVE_COWL, CLType::U256),
+            Parameter::new(ARG_WARNING_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_WARNING_RESERVE_COWL, CLType::U256),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn update_pool() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_UPDATE_POOL,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_RATE_TIERS, rate_tiers_type()),
+            Parameter::new(ARG_TAX_RATE, CLType::U512),
+            Parameter::new(ARG_PAUSED, CLType::Bool),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pool() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_POOL,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::Option(Box::new(Pool::cl_type())),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pool_tokens() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_POOL_TOKENS,
+        vec![],
+        CLType::List(Box::new(CLType::Key)),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_pricing_mode() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_PRICING_MODE,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_PRICING_MODE, CLType::U8),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pricing_mode() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_PRICING_MODE,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::U8,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn quote_cspr_to_token() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_QUOTE_CSPR_TO_TOKEN,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U512)]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn quote_token_to_cspr() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_QUOTE_TOKEN_TO_CSPR,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+        ],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U512)]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn add_liquidity() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_ADD_LIQUIDITY,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_TOKEN_AMOUNT, CLType::U256),
+        ],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn remove_liquidity() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_REMOVE_LIQUIDITY,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_SHARES, CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn liquidity_position() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_LIQUIDITY_POSITION,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_ADDRESS, CLType::Key),
+        ],
+        CLType::Tuple3([
+            Box::new(CLType::U512),
+            Box::new(CLType::U512),
+            Box::new(CLType::U512),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_oracle_config() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_ORACLE_CONFIG,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_MAX_AGE, CLType::U64),
+            Parameter::new(ARG_MAX_DEVIATION, CLType::U32),
+            Parameter::new(ARG_SPREAD, CLType::U32),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn update_price() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_UPDATE_PRICE,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_PRICE, CLType::U512),
+            Parameter::new(ARG_TIMESTAMP, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn oracle_feed() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_ORACLE_FEED,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::Tuple3([
+            Box::new(CLType::U512),
+            Box::new(CLType::U64),
+            Box::new(CLType::Bool),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_referral_bonus() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_REFERRAL_BONUS,
+        vec![Parameter::new(ARG_REFERRAL_BONUS, CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn referral_rewards() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_REFERRAL_REWARDS,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_ADDRESS, CLType::Key),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_bonding_curve() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_BONDING_CURVE,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_CURVE_STEPS, rate_tiers_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn bonding_curve() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_BONDING_CURVE,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::Tuple2([Box::new(CLType::U512), Box::new(CLType::U512)]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_vesting_schedule() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_VESTING_SCHEDULE,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_ENABLED, CLType::Bool),
+            Parameter::new(ARG_VESTING_START_TIME, CLType::U64),
+            Parameter::new(ARG_CLIFF_DURATION, CLType::U64),
+            Parameter::new(ARG_VESTING_DURATION, CLType::U64),
+            Parameter::new(ARG_PHASE, CLType::U64),
+            Parameter::new(ARG_PHASE_END_TIME, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn claim() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_CLAIM,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U256),
+            Parameter::new(ARG_PHASE, CLType::U64),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_claim_start_time() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_CLAIM_START_TIME,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_CLAIM_START_TIME, CLType::U64),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn claimable_of() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_CLAIMABLE_OF,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_ADDRESS, CLType::Key),
+            Parameter::new(ARG_PHASE, CLType::U64),
+        ],
+        CLType::U256,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn vesting_allocation() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_VESTING_ALLOCATION,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_ADDRESS, CLType::Key),
+            Parameter::new(ARG_PHASE, CLType::U64),
+        ],
+        CLType::Tuple3([
+            Box::new(CLType::U256),
+            Box::new(CLType::U256),
+            Box::new(CLType::U256),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn set_soft_cap() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SET_SOFT_CAP,
+        vec![
+            Parameter::new(ARG_POOL, CLType::Key),
+            Parameter::new(ARG_SOFT_CAP, CLType::U512),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn refund() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_REFUND,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::U512,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn sale() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_SALE,
+        vec![Parameter::new(ARG_POOL, CLType::Key)],
+        CLType::Tuple3([
+            Box::new(CLType::U8),
+            Box::new(CLType::U512),
+            Box::new(CLType::U512),
+        ]),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
 pub fn upgrade() -> EntryPoint {
     EntryPoint::new(
         ENTRY_POINT_UPGRADE,
-        vec![Parameter::new(ARG_CONTRACT_HASH, CLType::Key)],
+        vec![
+            Parameter::new(ARG_CONTRACT_HASH, CLType::Key),
+            Parameter::new(ARG_CONTRACT_VERSION, CLType::U32),
+            Parameter::new(ARG_PREVIOUS_CONTRACT_HASH, CLType::Key),
+            Parameter::new(ARG_PREVIOUS_CONTRACT_VERSION, CLType::U32),
+            Parameter::new(ARG_EVENTS_MODE, CLType::U8),
+            Parameter::new(ARG_START_TIME, CLType::U64),
+            Parameter::new(ARG_DURATION, CLType::U64),
+            Parameter::new(ARG_COWL_CEP18_CONTRACT_PACKAGE, CLType::Key),
+            Parameter::new(ARG_TOKEN_CONTRACT_PACKAGE, CLType::Key),
+            Parameter::new(ARG_MIN_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_MIN_RESERVE_COWL, CLType::U256),
+            Parameter::new(ARG_WARNING_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_WARNING_RESERVE_COWL, CLType::U256),
+            Parameter::new(ADMIN_LIST, CLType::List(Box::new(CLType::Key))),
+            Parameter::new(NONE_LIST, CLType::List(Box::new(CLType::Key))),
+            Parameter::new(ORACLE_LIST, CLType::List(Box::new(CLType::Key))),
+        ],
         CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
@@ -137,6 +735,7 @@ pub fn change_security() -> EntryPoint {
         vec![
             Parameter::new(ADMIN_LIST, CLType::List(Box::new(CLType::Key))),
             Parameter::new(NONE_LIST, CLType::List(Box::new(CLType::Key))),
+            Parameter::new(ORACLE_LIST, CLType::List(Box::new(CLType::Key))),
         ],
         CLType::Unit,
         EntryPointAccess::Public,
@@ -154,13 +753,138 @@ pub fn generate_entry_points() -> EntryPoints {
     entry_points.add_entry_point(update_times());
 
     entry_points.add_entry_point(withdraw_cowl());
+    entry_points.add_entry_point(deposit_cowl());
     entry_points.add_entry_point(balance_cowl());
     entry_points.add_entry_point(cspr_to_cowl());
+    entry_points.add_entry_point(cspr_to_token());
 
     entry_points.add_entry_point(withdraw_cspr());
     entry_points.add_entry_point(deposit_cspr());
     entry_points.add_entry_point(balance_cspr());
     entry_points.add_entry_point(cowl_to_cspr());
+    entry_points.add_entry_point(token_to_cspr());
+
+    entry_points.add_entry_point(add_pool());
+    entry_points.add_entry_point(update_pool());
+    entry_points.add_entry_point(pool());
+    entry_points.add_entry_point(pool_tokens());
+    entry_points.add_entry_point(set_pricing_mode());
+    entry_points.add_entry_point(pricing_mode());
+    entry_points.add_entry_point(quote_cspr_to_token());
+    entry_points.add_entry_point(quote_token_to_cspr());
+
+    entry_points.add_entry_point(set_oracle_config());
+    entry_points.add_entry_point(update_price());
+    entry_points.add_entry_point(oracle_feed());
+
+    entry_points.add_entry_point(set_bonding_curve());
+    entry_points.add_entry_point(bonding_curve());
+
+    entry_points.add_entry_point(set_referral_bonus());
+    entry_points.add_entry_point(referral_rewards());
+
+    entry_points.add_entry_point(set_vesting_schedule());
+    entry_points.add_entry_point(claim());
+    entry_points.add_entry_point(vesting_allocation());
+    entry_points.add_entry_point(set_claim_start_time());
+    entry_points.add_entry_point(claimable_of());
+
+    entry_points.add_entry_point(set_soft_cap());
+    entry_points.add_entry_point(refund());
+    entry_points.add_entry_point(sale());
+
+    entry_points.add_entry_point(add_liquidity());
+    entry_points.add_entry_point(remove_liquidity());
+    entry_points.add_entry_point(liquidity_position());
+
+    entry_points.add_entry_point(set_token_pair());
+    entry_points.add_entry_point(token_pair());
+    entry_points.add_entry_point(token_to_token());
+
+    entry_points.add_entry_point(reserves());
+    entry_points.add_entry_point(reconcile());
+    entry_points.add_entry_point(set_reserve_limits());
+
+    entry_points.add_entry_point(swap_stats());
+    entry_points.add_entry_point(swap_count());
+    entry_points.add_entry_point(token_symbol());
+    entry_points.add_entry_point(rollback());
+
+    entry_points
+}
+
+/// Returns the `install_factory` entry point, the factory counterpart of `install`.
+pub fn install_factory() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_INSTALL_FACTORY,
+        vec![
+            Parameter::new(ARG_CONTRACT_HASH, CLType::Key),
+            Parameter::new(ARG_PACKAGE_HASH, CLType::Key),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn create_pool() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_CREATE_POOL,
+        vec![
+            Parameter::new(ARG_NAME, CLType::String),
+            Parameter::new(ARG_COWL_CEP18_CONTRACT_PACKAGE, CLType::Key),
+            Parameter::new(ARG_TOKEN_CONTRACT_PACKAGE, CLType::Key),
+            Parameter::new(ARG_TOKEN_SYMBOL, CLType::String),
+            Parameter::new(ARG_TOKEN_DECIMALS, CLType::U8),
+            Parameter::new(ARG_START_TIME, CLType::U64),
+            Parameter::new(ARG_DURATION, CLType::U64),
+            Parameter::new(ARG_EVENTS_MODE, CLType::U8),
+            Parameter::new(ARG_MIN_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_MIN_RESERVE_COWL, CLType::U256),
+            Parameter::new(ARG_WARNING_RESERVE_CSPR, CLType::U512),
+            Parameter::new(ARG_WARNING_RESERVE_COWL, CLType::U256),
+            Parameter::new(ADMIN_LIST, CLType::List(Box::new(CLType::Key))),
+            Parameter::new(NONE_LIST, CLType::List(Box::new(CLType::Key))),
+        ],
+        <(Key, (Key, u32), URef)>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pool_count() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_POOL_COUNT,
+        vec![],
+        CLType::U64,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+pub fn pools() -> EntryPoint {
+    EntryPoint::new(
+        ENTRY_POINT_POOLS,
+        vec![
+            Parameter::new(ARG_OFFSET, CLType::U64),
+            Parameter::new(ARG_LIMIT, CLType::U64),
+        ],
+        Vec::<PoolInfo>::cl_type(),
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+/// Entry points of the factory. Pools it creates reuse [`generate_entry_points`], so they run the
+/// same wasm as the factory without any code being uploaded.
+pub fn generate_factory_entry_points() -> EntryPoints {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(install_factory());
+    entry_points.add_entry_point(change_security());
+
+    entry_points.add_entry_point(create_pool());
+    entry_points.add_entry_point(pool_count());
+    entry_points.add_entry_point(pools());
 
     entry_points
 }
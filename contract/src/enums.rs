@@ -1,3 +1,9 @@
+use alloc::vec::Vec;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
 use crate::error::SwapError;
 
 #[repr(u8)]
@@ -6,6 +12,8 @@ use crate::error::SwapError;
 pub enum EventsMode {
     NoEvents = 0,
     CES = 1,
+    Native = 2,
+    NativeAndCES = 3,
 }
 
 impl TryFrom<u8> for EventsMode {
@@ -15,7 +23,91 @@ impl TryFrom<u8> for EventsMode {
         match value {
             0 => Ok(EventsMode::NoEvents),
             1 => Ok(EventsMode::CES),
+            2 => Ok(EventsMode::Native),
+            3 => Ok(EventsMode::NativeAndCES),
             _ => Err(SwapError::InvalidEventsMode),
         }
     }
 }
+
+/// How a pool prices swaps against CSPR.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PricingMode {
+    /// Fixed rate tiers of the pool, the tax being kept from token → CSPR swaps.
+    Tiers = 0,
+    /// x*y=k curve over the pool reserves, the tax being kept in the pool as a liquidity fee.
+    ConstantProduct = 1,
+    /// Reference price pushed by oracle updaters less a spread, the rate tiers pricing swaps
+    /// while the price is stale.
+    Oracle = 2,
+    /// Rate dropping with the tokens sold on the bonding curve of the pool.
+    BondingCurve = 3,
+}
+
+impl TryFrom<u8> for PricingMode {
+    type Error = SwapError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PricingMode::Tiers),
+            1 => Ok(PricingMode::ConstantProduct),
+            2 => Ok(PricingMode::Oracle),
+            3 => Ok(PricingMode::BondingCurve),
+            _ => Err(SwapError::InvalidPricingMode),
+        }
+    }
+}
+
+/// Stage of the fundraising sale of a pool with a soft cap.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SaleState {
+    /// No soft cap, the pool sells as usual.
+    #[default]
+    Open = 0,
+    /// Collecting CSPR toward the soft cap, which is held for refunds.
+    Raising = 1,
+    /// Soft cap met, the raised CSPR can be withdrawn and the tokens claimed.
+    Succeeded = 2,
+    /// Sale ended below the soft cap, buyers can get their CSPR back.
+    Failed = 3,
+}
+
+impl TryFrom<u8> for SaleState {
+    type Error = SwapError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SaleState::Open),
+            1 => Ok(SaleState::Raising),
+            2 => Ok(SaleState::Succeeded),
+            3 => Ok(SaleState::Failed),
+            _ => Err(SwapError::InvalidSaleState),
+        }
+    }
+}
+
+impl CLTyped for SaleState {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for SaleState {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        (*self as u8).to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        1
+    }
+}
+
+impl FromBytes for SaleState {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (state, remainder) = u8::from_bytes(bytes)?;
+        let state = SaleState::try_from(state).map_err(|_| bytesrepr::Error::Formatting)?;
+        Ok((state, remainder))
+    }
+}
@@ -49,6 +49,76 @@ pub enum SwapError {
     InvalidStartTime = 3036,
     MissingEndTime = 3037,
     InvalidEndTime = 3038,
+    InvalidSweepFlag = 3039,
+    InsufficientLiquidity = 3040,
+    MissingReserveLimit = 3041,
+    InvalidReserveLimit = 3042,
+    MissingSwapCount = 3043,
+    InvalidSwapCount = 3044,
+    MissingNativeEventsCount = 3045,
+    InvalidNativeEventsCount = 3046,
+    MissingEventSequence = 3047,
+    InvalidEventSequence = 3048,
+    InvalidStorageVersion = 3049,
+    InvalidRollbackVersion = 3050,
+    UnknownContractVersion = 3051,
+    IncompatibleStorageVersion = 3052,
+    InvalidUpgradeCaller = 3053,
+    SwapAlreadyInstalled = 3054,
+    InvalidInstalledSwaps = 3055,
+    MissingPoolCount = 3056,
+    InvalidPoolCount = 3057,
+    InvalidPoolRange = 3058,
+    InvalidFactoryFlag = 3059,
+    MissingTokenDecimals = 3060,
+    InvalidTokenDecimals = 3061,
+    InvalidTokenSymbol = 3062,
+    MissingTokenPair = 3063,
+    TokenPairDisabled = 3064,
+    InvalidTokenPair = 3065,
+    MissingPool = 3066,
+    InvalidPool = 3067,
+    PoolAlreadyExists = 3068,
+    PoolPaused = 3069,
+    InvalidRateTiers = 3070,
+    InvalidTaxRate = 3071,
+    MissingPoolTokens = 3072,
+    InvalidPoolTokens = 3073,
+    MissingPricingMode = 3074,
+    InvalidPricingMode = 3075,
+    InsufficientShares = 3076,
+    MissingOracleConfig = 3077,
+    InvalidOracleConfig = 3078,
+    MissingOraclePrice = 3079,
+    InvalidOraclePrice = 3080,
+    InvalidOracleTimestamp = 3081,
+    StaleOraclePrice = 3082,
+    OracleDeviationExceeded = 3083,
+    InvalidOracleList = 3084,
+    MissingBondingCurve = 3085,
+    InvalidBondingCurve = 3086,
+    BondingCurveSoldOut = 3087,
+    SelfReferral = 3088,
+    InvalidReferrer = 3089,
+    MissingReferralBonus = 3090,
+    InvalidReferralBonus = 3091,
+    MissingVestingSchedule = 3092,
+    InvalidVestingSchedule = 3093,
+    NothingToClaim = 3094,
+    InsufficientClaimable = 3095,
+    MissingSoftCap = 3096,
+    InvalidSoftCap = 3097,
+    InvalidSaleState = 3098,
+    SoftCapNotReached = 3099,
+    RefundsNotOpen = 3100,
+    NothingToRefund = 3101,
+    InvalidReserveWarning = 3102,
+    InvalidFactoryPackage = 3103,
+    InvalidLiquidityProvider = 3104,
+    InvalidClaimStartTime = 3105,
+    IncompatibleNamedKeys = 3106,
+    ReferralWhileRaising = 3107,
+    InvalidPaused = 3108,
 }
 
 impl From<SwapError> for ApiError {
@@ -1,16 +1,80 @@
-use crate::security::SecurityBadge;
 #[cfg(feature = "contract-support")]
-use crate::{constants::ARG_EVENTS_MODE, enums::EventsMode, utils::get_stored_value};
+use crate::{
+    constants::{ARG_EVENTS_MODE, ARG_EVENT_SEQUENCE, ARG_NATIVE_EVENTS_COUNT, DICT_NATIVE_EVENTS},
+    enums::EventsMode,
+    error::SwapError,
+    utils::{
+        get_stored_value, get_stored_value_with_user_errors, get_verified_caller,
+        set_dictionary_value_for_key, set_stored_value,
+    },
+};
+use crate::{security::SecurityBadge, utils::tuple_typed};
 use alloc::collections::btree_map::BTreeMap;
 #[cfg(feature = "contract-support")]
+use alloc::string::ToString;
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "contract-support")]
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
-use casper_event_standard::Event;
 #[cfg(feature = "contract-support")]
 use casper_event_standard::{emit, Schemas};
-use casper_types::{Key, URef, U256, U512};
+use casper_event_standard::{EventInstance, Schema};
+#[cfg(feature = "contract-support")]
+use casper_types::bytesrepr::Bytes;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLTyped, Key, URef, U256, U512,
+};
 #[cfg(feature = "contract-support")]
 use core::convert::TryFrom;
 
+/// Defines an event whose CES name carries a layout version, e.g. `CowlToCspr_v2`. Bump the
+/// version whenever its fields change.
+macro_rules! versioned_event {
+    ($version:literal, pub struct $name:ident { $(pub $field:ident: $ty:ty),* $(,)? }) => {
+        #[derive(Debug, PartialEq, Eq)]
+        pub struct $name {
+            $(pub $field: $ty),*
+        }
+
+        impl EventInstance for $name {
+            fn name() -> String {
+                format!("{}_v{}", stringify!($name), $version)
+            }
+
+            fn schema() -> Schema {
+                let mut schema = Schema::new();
+                $(schema.with_elem(stringify!($field), <$ty as CLTyped>::cl_type());)*
+                schema
+            }
+        }
+
+        impl ToBytes for $name {
+            fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+                let mut result = bytesrepr::allocate_buffer(self)?;
+                result.extend(format!("event_{}", Self::name()).to_bytes()?);
+                $(result.extend(self.$field.to_bytes()?);)*
+                Ok(result)
+            }
+
+            fn serialized_length(&self) -> usize {
+                format!("event_{}", Self::name()).serialized_length()
+                    $(+ self.$field.serialized_length())*
+            }
+        }
+
+        impl FromBytes for $name {
+            fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+                let (event_name, remainder) = String::from_bytes(bytes)?;
+                if event_name != format!("event_{}", Self::name()) {
+                    return Err(bytesrepr::Error::Formatting);
+                }
+                $(let ($field, remainder) = <$ty>::from_bytes(remainder)?;)*
+                Ok((Self { $($field),* }, remainder))
+            }
+        }
+    };
+}
+
 #[derive(Debug)]
 pub enum Event {
     ChangeSecurity(ChangeSecurity),
@@ -18,138 +82,324 @@ pub enum Event {
     Upgrade(Upgrade),
     CowlCep18ContractPackageUpdate(CowlCep18ContractPackageUpdate),
     UpdateTimes(UpdateTimes),
+    DepositCowl(DepositCowl),
     DepositCspr(DepositCspr),
     WithdrawCowl(WithdrawCowl),
     WithdrawCspr(WithdrawCspr),
     CowlToCspr(CowlToCspr),
     CsprToCowl(CsprToCowl),
+    Reconcile(Reconcile),
+    ReserveLimitsUpdate(ReserveLimitsUpdate),
+    CsprReserveLow(CsprReserveLow),
+    CowlReserveLow(CowlReserveLow),
+    Migrated(Migrated),
+    Rollback(Rollback),
+    PoolCreated(PoolCreated),
+    TokenPairUpdate(TokenPairUpdate),
+    TokenToToken(TokenToToken),
+    PoolUpdate(PoolUpdate),
+    PricingModeUpdate(PricingModeUpdate),
+    AddLiquidity(AddLiquidity),
+    RemoveLiquidity(RemoveLiquidity),
+    OracleConfigUpdate(OracleConfigUpdate),
+    OraclePriceUpdate(OraclePriceUpdate),
+    BondingCurveUpdate(BondingCurveUpdate),
+    ReferralBonusUpdate(ReferralBonusUpdate),
+    ReferralReward(ReferralReward),
+    VestingScheduleUpdate(VestingScheduleUpdate),
+    TokensVested(TokensVested),
+    TokensClaimed(TokensClaimed),
+    ClaimStartTimeUpdate(ClaimStartTimeUpdate),
+    SaleStateUpdate(SaleStateUpdate),
+    Refund(Refund),
+}
+
+/// Fields shared by every event, the block time being in milliseconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventHeader {
+    pub sequence_id: u64,
+    pub block_time: u64,
+    pub caller: Key,
+    pub caller_package: Option<Key>,
+}
+
+impl EventHeader {
+    pub fn new(
+        sequence_id: u64,
+        block_time: u64,
+        caller: Key,
+        caller_package: Option<Key>,
+    ) -> Self {
+        Self {
+            sequence_id,
+            block_time,
+            caller,
+            caller_package,
+        }
+    }
+
+    /// Header of the next event, whose sequence id is only consumed when events are recorded.
+    #[cfg(feature = "contract-support")]
+    pub fn next() -> Self {
+        use casper_contract::contract_api::runtime::get_blocktime;
+
+        let sequence_id: u64 = get_stored_value_with_user_errors(
+            ARG_EVENT_SEQUENCE,
+            SwapError::MissingEventSequence,
+            SwapError::InvalidEventSequence,
+        );
+        if get_events_mode() != EventsMode::NoEvents {
+            set_stored_value(
+                ARG_EVENT_SEQUENCE,
+                sequence_id
+                    .checked_add(1)
+                    .unwrap_or_revert_with(SwapError::Overflow),
+            );
+        }
+
+        let (caller, caller_package) = get_verified_caller();
+        Self::new(sequence_id, get_blocktime().into(), caller, caller_package)
+    }
+}
+
+tuple_typed! {
+    EventHeader as ((u64, u64), Key, Option<Key>) {
+        sequence_id, block_time, caller, caller_package
+    }
 }
 
 #[cfg(feature = "contract-support")]
-pub fn record_event_dictionary(event: Event) {
-    let events_mode: EventsMode =
-        EventsMode::try_from(get_stored_value::<u8>(ARG_EVENTS_MODE)).unwrap_or_revert();
+fn get_events_mode() -> EventsMode {
+    EventsMode::try_from(get_stored_value::<u8>(ARG_EVENTS_MODE)).unwrap_or_revert()
+}
 
-    match events_mode {
+#[cfg(feature = "contract-support")]
+pub fn record_event_dictionary(event: Event) {
+    match get_events_mode() {
         EventsMode::NoEvents => {}
         EventsMode::CES => ces(event),
+        EventsMode::Native => native(&event),
+        EventsMode::NativeAndCES => {
+            native(&event);
+            ces(event)
+        }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq, Default)]
-pub struct SetModalities {}
+versioned_event! {
+    2,
+    pub struct SetModalities {
+        pub header: EventHeader,
+        pub old_events_mode: u8,
+        pub new_events_mode: u8,
+    }
+}
 
 impl SetModalities {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(header: EventHeader, old_events_mode: u8, new_events_mode: u8) -> Self {
+        Self {
+            header,
+            old_events_mode,
+            new_events_mode,
+        }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq, Default)]
-pub struct Upgrade {}
+versioned_event! {
+    2,
+    pub struct Upgrade {
+        pub header: EventHeader,
+        pub old_contract_hash: Key,
+        pub old_contract_version: u32,
+        pub new_contract_hash: Key,
+        pub new_contract_version: u32,
+    }
+}
 
 impl Upgrade {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(
+        header: EventHeader,
+        old_contract_hash: Key,
+        old_contract_version: u32,
+        new_contract_hash: Key,
+        new_contract_version: u32,
+    ) -> Self {
+        Self {
+            header,
+            old_contract_hash,
+            old_contract_version,
+            new_contract_hash,
+            new_contract_version,
+        }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq)]
-pub struct ChangeSecurity {
-    pub admin: Key,
-    pub sec_change_map: BTreeMap<Key, SecurityBadge>,
+versioned_event! {
+    2,
+    pub struct ChangeSecurity {
+        pub header: EventHeader,
+        pub admin: Key,
+        pub sec_change_map: BTreeMap<Key, SecurityBadge>,
+    }
 }
 
 impl ChangeSecurity {
-    pub fn new(admin: Key, sec_change_map: BTreeMap<Key, SecurityBadge>) -> Self {
+    pub fn new(
+        header: EventHeader,
+        admin: Key,
+        sec_change_map: BTreeMap<Key, SecurityBadge>,
+    ) -> Self {
         Self {
+            header,
             admin,
             sec_change_map,
         }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq)]
-pub struct CowlCep18ContractPackageUpdate {
-    pub key: Key,
-    pub cowl_cep18_contract_package_key: Key,
+versioned_event! {
+    2,
+    pub struct CowlCep18ContractPackageUpdate {
+        pub header: EventHeader,
+        pub key: Key,
+        pub cowl_cep18_contract_package_key: Key,
+    }
 }
 
 impl CowlCep18ContractPackageUpdate {
-    pub fn new(key: Key, cowl_cep18_contract_package_key: Key) -> Self {
+    pub fn new(header: EventHeader, key: Key, cowl_cep18_contract_package_key: Key) -> Self {
         Self {
+            header,
             key,
             cowl_cep18_contract_package_key,
         }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq, Default)]
-pub struct UpdateTimes {
-    pub new_start_time: u64,
-    pub new_end_time: u64,
+versioned_event! {
+    3,
+    pub struct UpdateTimes {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub new_start_time: u64,
+        pub new_end_time: u64,
+    }
 }
 
 impl UpdateTimes {
-    pub fn new(new_start_time: u64, new_end_time: u64) -> Self {
+    pub fn new(header: EventHeader, pool: Key, new_start_time: u64, new_end_time: u64) -> Self {
         Self {
+            header,
+            pool,
             new_start_time,
             new_end_time,
         }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq, Default)]
-pub struct DepositCspr {
-    pub source_purse: URef,
-    pub amount: U512,
+versioned_event! {
+    3,
+    pub struct DepositCowl {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub owner: Key,
+        pub amount: U256,
+    }
+}
+
+impl DepositCowl {
+    pub fn new(header: EventHeader, pool: Key, owner: Key, amount: U256) -> Self {
+        Self {
+            header,
+            pool,
+            owner,
+            amount,
+        }
+    }
+}
+
+versioned_event! {
+    3,
+    pub struct DepositCspr {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub source_purse: URef,
+        pub amount: U512,
+    }
 }
 
 impl DepositCspr {
-    pub fn new(source_purse: URef, amount: U512) -> Self {
+    pub fn new(header: EventHeader, pool: Key, source_purse: URef, amount: U512) -> Self {
         Self {
+            header,
+            pool,
             source_purse,
             amount,
         }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq)]
-pub struct WithdrawCowl {
-    pub recipient: Key,
-    pub amount: U256,
+versioned_event! {
+    3,
+    pub struct WithdrawCowl {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub recipient: Key,
+        pub amount: U256,
+    }
 }
 
 impl WithdrawCowl {
-    pub fn new(recipient: Key, amount: U256) -> Self {
-        Self { recipient, amount }
+    pub fn new(header: EventHeader, pool: Key, recipient: Key, amount: U256) -> Self {
+        Self {
+            header,
+            pool,
+            recipient,
+            amount,
+        }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq)]
-pub struct WithdrawCspr {
-    pub recipient: Key,
-    pub amount: U512,
+versioned_event! {
+    3,
+    pub struct WithdrawCspr {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub recipient: Key,
+        pub amount: U512,
+    }
 }
 
 impl WithdrawCspr {
-    pub fn new(recipient: Key, amount: U512) -> Self {
-        Self { recipient, amount }
+    pub fn new(header: EventHeader, pool: Key, recipient: Key, amount: U512) -> Self {
+        Self {
+            header,
+            pool,
+            recipient,
+            amount,
+        }
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq)]
-pub struct CowlToCspr {
-    pub owner: Key,
-    pub recipient: Key,
-    pub cowl_amount: U256,
-    pub cspr_amount: U512,
-    pub base_rate: U512,
-    pub tax_amount: U512,
+versioned_event! {
+    3,
+    pub struct CowlToCspr {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub owner: Key,
+        pub recipient: Key,
+        pub cowl_amount: U256,
+        pub cspr_amount: U512,
+        pub base_rate: U512,
+        pub tax_amount: U512,
+    }
 }
 
 impl CowlToCspr {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        header: EventHeader,
+        pool: Key,
         owner: Key,
         recipient: Key,
         cowl_amount: U256,
@@ -158,6 +408,8 @@ impl CowlToCspr {
         tax_amount: U512,
     ) -> Self {
         Self {
+            header,
+            pool,
             owner,
             recipient,
             cowl_amount,
@@ -168,17 +420,23 @@ impl CowlToCspr {
     }
 }
 
-#[derive(Event, Debug, PartialEq, Eq)]
-pub struct CsprToCowl {
-    pub source_purse: URef,
-    pub recipient: Key,
-    pub cowl_amount: U256,
-    pub cspr_amount: U512,
-    pub base_rate: U512,
+versioned_event! {
+    3,
+    pub struct CsprToCowl {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub source_purse: URef,
+        pub recipient: Key,
+        pub cowl_amount: U256,
+        pub cspr_amount: U512,
+        pub base_rate: U512,
+    }
 }
 
 impl CsprToCowl {
     pub fn new(
+        header: EventHeader,
+        pool: Key,
         source_purse: URef,
         recipient: Key,
         cowl_amount: U256,
@@ -186,6 +444,8 @@ impl CsprToCowl {
         base_rate: U512,
     ) -> Self {
         Self {
+            header,
+            pool,
             source_purse,
             recipient,
             cowl_amount,
@@ -195,6 +455,700 @@ impl CsprToCowl {
     }
 }
 
+versioned_event! {
+    3,
+    pub struct Reconcile {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub cspr_expected: U512,
+        pub cspr_actual: U512,
+        pub cowl_expected: U256,
+        pub cowl_actual: U256,
+        pub swept: bool,
+    }
+}
+
+impl Reconcile {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        cspr_expected: U512,
+        cspr_actual: U512,
+        cowl_expected: U256,
+        cowl_actual: U256,
+        swept: bool,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            cspr_expected,
+            cspr_actual,
+            cowl_expected,
+            cowl_actual,
+            swept,
+        }
+    }
+}
+
+versioned_event! {
+    3,
+    pub struct ReserveLimitsUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub min_reserve_cspr: U512,
+        pub min_reserve_cowl: U256,
+        pub warning_reserve_cspr: U512,
+        pub warning_reserve_cowl: U256,
+    }
+}
+
+impl ReserveLimitsUpdate {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        min_reserve_cspr: U512,
+        min_reserve_cowl: U256,
+        warning_reserve_cspr: U512,
+        warning_reserve_cowl: U256,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            min_reserve_cspr,
+            min_reserve_cowl,
+            warning_reserve_cspr,
+            warning_reserve_cowl,
+        }
+    }
+}
+
+versioned_event! {
+    3,
+    pub struct CsprReserveLow {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub reserve: U512,
+        pub threshold: U512,
+    }
+}
+
+impl CsprReserveLow {
+    pub fn new(header: EventHeader, pool: Key, reserve: U512, threshold: U512) -> Self {
+        Self {
+            header,
+            pool,
+            reserve,
+            threshold,
+        }
+    }
+}
+
+versioned_event! {
+    3,
+    pub struct CowlReserveLow {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub reserve: U256,
+        pub threshold: U256,
+    }
+}
+
+impl CowlReserveLow {
+    pub fn new(header: EventHeader, pool: Key, reserve: U256, threshold: U256) -> Self {
+        Self {
+            header,
+            pool,
+            reserve,
+            threshold,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct Migrated {
+        pub header: EventHeader,
+        pub from: u32,
+        pub to: u32,
+    }
+}
+
+impl Migrated {
+    pub fn new(header: EventHeader, from: u32, to: u32) -> Self {
+        Self { header, from, to }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct Rollback {
+        pub header: EventHeader,
+        pub from_contract_hash: Key,
+        pub from_contract_version: u32,
+        pub to_contract_hash: Key,
+        pub to_contract_version: u32,
+        pub storage_version: u32,
+    }
+}
+
+impl Rollback {
+    pub fn new(
+        header: EventHeader,
+        from_contract_hash: Key,
+        from_contract_version: u32,
+        to_contract_hash: Key,
+        to_contract_version: u32,
+        storage_version: u32,
+    ) -> Self {
+        Self {
+            header,
+            from_contract_hash,
+            from_contract_version,
+            to_contract_hash,
+            to_contract_version,
+            storage_version,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct PoolCreated {
+        pub header: EventHeader,
+        pub index: u64,
+        pub name: String,
+        pub package_hash: Key,
+        pub contract_hash: Key,
+        pub token_package: Key,
+        pub start_time: u64,
+        pub end_time: u64,
+        pub creator: Key,
+    }
+}
+
+impl PoolCreated {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        header: EventHeader,
+        index: u64,
+        name: String,
+        package_hash: Key,
+        contract_hash: Key,
+        token_package: Key,
+        start_time: u64,
+        end_time: u64,
+        creator: Key,
+    ) -> Self {
+        Self {
+            header,
+            index,
+            name,
+            package_hash,
+            contract_hash,
+            token_package,
+            start_time,
+            end_time,
+            creator,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct TokenPairUpdate {
+        pub header: EventHeader,
+        pub input_package: Key,
+        pub output_package: Key,
+        pub input_decimals: u8,
+        pub output_decimals: u8,
+        pub rate: U512,
+        pub min_input_amount: U512,
+        pub enabled: bool,
+    }
+}
+
+impl TokenPairUpdate {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        header: EventHeader,
+        input_package: Key,
+        output_package: Key,
+        input_decimals: u8,
+        output_decimals: u8,
+        rate: U512,
+        min_input_amount: U512,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            header,
+            input_package,
+            output_package,
+            input_decimals,
+            output_decimals,
+            rate,
+            min_input_amount,
+            enabled,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct TokenToToken {
+        pub header: EventHeader,
+        pub owner: Key,
+        pub input_package: Key,
+        pub output_package: Key,
+        pub input_amount: U256,
+        pub output_amount: U256,
+        pub rate: U512,
+        pub tax_amount: U256,
+    }
+}
+
+impl TokenToToken {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        header: EventHeader,
+        owner: Key,
+        input_package: Key,
+        output_package: Key,
+        input_amount: U256,
+        output_amount: U256,
+        rate: U512,
+        tax_amount: U256,
+    ) -> Self {
+        Self {
+            header,
+            owner,
+            input_package,
+            output_package,
+            input_amount,
+            output_amount,
+            rate,
+            tax_amount,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct PoolUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub token_decimals: u8,
+        pub rate_tiers: Vec<(U512, U512)>,
+        pub tax_rate: U512,
+        pub start_time: u64,
+        pub end_time: u64,
+        pub paused: bool,
+    }
+}
+
+impl PoolUpdate {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        token_decimals: u8,
+        rate_tiers: Vec<(U512, U512)>,
+        tax_rate: U512,
+        start_time: u64,
+        end_time: u64,
+        paused: bool,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            token_decimals,
+            rate_tiers,
+            tax_rate,
+            start_time,
+            end_time,
+            paused,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct PricingModeUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub pricing_mode: u8,
+    }
+}
+
+impl PricingModeUpdate {
+    pub fn new(header: EventHeader, pool: Key, pricing_mode: u8) -> Self {
+        Self {
+            header,
+            pool,
+            pricing_mode,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct AddLiquidity {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub provider: Key,
+        pub cspr_amount: U512,
+        pub token_amount: U256,
+        pub shares: U512,
+    }
+}
+
+impl AddLiquidity {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        provider: Key,
+        cspr_amount: U512,
+        token_amount: U256,
+        shares: U512,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            provider,
+            cspr_amount,
+            token_amount,
+            shares,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct RemoveLiquidity {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub provider: Key,
+        pub cspr_amount: U512,
+        pub token_amount: U256,
+        pub cspr_fees: U512,
+        pub token_fees: U256,
+        pub shares: U512,
+    }
+}
+
+impl RemoveLiquidity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        provider: Key,
+        cspr_amount: U512,
+        token_amount: U256,
+        cspr_fees: U512,
+        token_fees: U256,
+        shares: U512,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            provider,
+            cspr_amount,
+            token_amount,
+            cspr_fees,
+            token_fees,
+            shares,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct OracleConfigUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub max_age: u64,
+        pub max_deviation: u32,
+        pub spread: u32,
+    }
+}
+
+impl OracleConfigUpdate {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        max_age: u64,
+        max_deviation: u32,
+        spread: u32,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            max_age,
+            max_deviation,
+            spread,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct OraclePriceUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub updater: Key,
+        pub price: U512,
+        pub timestamp: u64,
+    }
+}
+
+impl OraclePriceUpdate {
+    pub fn new(header: EventHeader, pool: Key, updater: Key, price: U512, timestamp: u64) -> Self {
+        Self {
+            header,
+            pool,
+            updater,
+            price,
+            timestamp,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct BondingCurveUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub curve_steps: Vec<(U512, U512)>,
+        pub sold: U512,
+    }
+}
+
+impl BondingCurveUpdate {
+    pub fn new(header: EventHeader, pool: Key, curve_steps: Vec<(U512, U512)>, sold: U512) -> Self {
+        Self {
+            header,
+            pool,
+            curve_steps,
+            sold,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct ReferralBonusUpdate {
+        pub header: EventHeader,
+        pub referral_bonus: u32,
+    }
+}
+
+impl ReferralBonusUpdate {
+    pub fn new(header: EventHeader, referral_bonus: u32) -> Self {
+        Self {
+            header,
+            referral_bonus,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct ReferralReward {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub referrer: Key,
+        pub buyer: Key,
+        pub token_amount: U256,
+        pub reward_amount: U256,
+    }
+}
+
+impl ReferralReward {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        referrer: Key,
+        buyer: Key,
+        token_amount: U256,
+        reward_amount: U256,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            referrer,
+            buyer,
+            token_amount,
+            reward_amount,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct VestingScheduleUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub phase: Option<u64>,
+        pub enabled: bool,
+        pub start_time: u64,
+        pub cliff_duration: u64,
+        pub vesting_duration: u64,
+    }
+}
+
+impl VestingScheduleUpdate {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        phase: Option<u64>,
+        enabled: bool,
+        start_time: u64,
+        cliff_duration: u64,
+        vesting_duration: u64,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            phase,
+            enabled,
+            start_time,
+            cliff_duration,
+            vesting_duration,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct TokensVested {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub phase: Option<u64>,
+        pub beneficiary: Key,
+        pub amount: U256,
+        pub total: U256,
+    }
+}
+
+impl TokensVested {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        phase: Option<u64>,
+        beneficiary: Key,
+        amount: U256,
+        total: U256,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            phase,
+            beneficiary,
+            amount,
+            total,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct TokensClaimed {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub phase: Option<u64>,
+        pub beneficiary: Key,
+        pub amount: U256,
+    }
+}
+
+impl TokensClaimed {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        phase: Option<u64>,
+        beneficiary: Key,
+        amount: U256,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            phase,
+            beneficiary,
+            amount,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct ClaimStartTimeUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub claim_start_time: u64,
+    }
+}
+
+impl ClaimStartTimeUpdate {
+    pub fn new(header: EventHeader, pool: Key, claim_start_time: u64) -> Self {
+        Self {
+            header,
+            pool,
+            claim_start_time,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct SaleStateUpdate {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub state: u8,
+        pub soft_cap: U512,
+        pub raised: U512,
+    }
+}
+
+impl SaleStateUpdate {
+    pub fn new(header: EventHeader, pool: Key, state: u8, soft_cap: U512, raised: U512) -> Self {
+        Self {
+            header,
+            pool,
+            state,
+            soft_cap,
+            raised,
+        }
+    }
+}
+
+versioned_event! {
+    1,
+    pub struct Refund {
+        pub header: EventHeader,
+        pub pool: Key,
+        pub buyer: Key,
+        pub cspr_amount: U512,
+        pub token_amount: U256,
+    }
+}
+
+impl Refund {
+    pub fn new(
+        header: EventHeader,
+        pool: Key,
+        buyer: Key,
+        cspr_amount: U512,
+        token_amount: U256,
+    ) -> Self {
+        Self {
+            header,
+            pool,
+            buyer,
+            cspr_amount,
+            token_amount,
+        }
+    }
+}
+
 #[cfg(feature = "contract-support")]
 fn ces(event: Event) {
     match event {
@@ -203,35 +1157,167 @@ fn ces(event: Event) {
         Event::ChangeSecurity(ev) => emit(ev),
         Event::CowlCep18ContractPackageUpdate(ev) => emit(ev),
         Event::UpdateTimes(ev) => emit(ev),
+        Event::DepositCowl(ev) => emit(ev),
         Event::DepositCspr(ev) => emit(ev),
         Event::WithdrawCowl(ev) => emit(ev),
         Event::WithdrawCspr(ev) => emit(ev),
         Event::CowlToCspr(ev) => emit(ev),
         Event::CsprToCowl(ev) => emit(ev),
+        Event::Reconcile(ev) => emit(ev),
+        Event::ReserveLimitsUpdate(ev) => emit(ev),
+        Event::CsprReserveLow(ev) => emit(ev),
+        Event::CowlReserveLow(ev) => emit(ev),
+        Event::Migrated(ev) => emit(ev),
+        Event::Rollback(ev) => emit(ev),
+        Event::PoolCreated(ev) => emit(ev),
+        Event::TokenPairUpdate(ev) => emit(ev),
+        Event::TokenToToken(ev) => emit(ev),
+        Event::PoolUpdate(ev) => emit(ev),
+        Event::PricingModeUpdate(ev) => emit(ev),
+        Event::AddLiquidity(ev) => emit(ev),
+        Event::RemoveLiquidity(ev) => emit(ev),
+        Event::OracleConfigUpdate(ev) => emit(ev),
+        Event::OraclePriceUpdate(ev) => emit(ev),
+        Event::BondingCurveUpdate(ev) => emit(ev),
+        Event::ReferralBonusUpdate(ev) => emit(ev),
+        Event::ReferralReward(ev) => emit(ev),
+        Event::VestingScheduleUpdate(ev) => emit(ev),
+        Event::TokensVested(ev) => emit(ev),
+        Event::TokensClaimed(ev) => emit(ev),
+        Event::ClaimStartTimeUpdate(ev) => emit(ev),
+        Event::SaleStateUpdate(ev) => emit(ev),
+        Event::Refund(ev) => emit(ev),
+    }
+}
+
+#[cfg(feature = "contract-support")]
+fn event_bytes(event: &Event) -> Vec<u8> {
+    match event {
+        Event::SetModalities(ev) => ev.to_bytes(),
+        Event::Upgrade(ev) => ev.to_bytes(),
+        Event::ChangeSecurity(ev) => ev.to_bytes(),
+        Event::CowlCep18ContractPackageUpdate(ev) => ev.to_bytes(),
+        Event::UpdateTimes(ev) => ev.to_bytes(),
+        Event::DepositCowl(ev) => ev.to_bytes(),
+        Event::DepositCspr(ev) => ev.to_bytes(),
+        Event::WithdrawCowl(ev) => ev.to_bytes(),
+        Event::WithdrawCspr(ev) => ev.to_bytes(),
+        Event::CowlToCspr(ev) => ev.to_bytes(),
+        Event::CsprToCowl(ev) => ev.to_bytes(),
+        Event::Reconcile(ev) => ev.to_bytes(),
+        Event::ReserveLimitsUpdate(ev) => ev.to_bytes(),
+        Event::CsprReserveLow(ev) => ev.to_bytes(),
+        Event::CowlReserveLow(ev) => ev.to_bytes(),
+        Event::Migrated(ev) => ev.to_bytes(),
+        Event::Rollback(ev) => ev.to_bytes(),
+        Event::PoolCreated(ev) => ev.to_bytes(),
+        Event::TokenPairUpdate(ev) => ev.to_bytes(),
+        Event::TokenToToken(ev) => ev.to_bytes(),
+        Event::PoolUpdate(ev) => ev.to_bytes(),
+        Event::PricingModeUpdate(ev) => ev.to_bytes(),
+        Event::AddLiquidity(ev) => ev.to_bytes(),
+        Event::RemoveLiquidity(ev) => ev.to_bytes(),
+        Event::OracleConfigUpdate(ev) => ev.to_bytes(),
+        Event::OraclePriceUpdate(ev) => ev.to_bytes(),
+        Event::BondingCurveUpdate(ev) => ev.to_bytes(),
+        Event::ReferralBonusUpdate(ev) => ev.to_bytes(),
+        Event::ReferralReward(ev) => ev.to_bytes(),
+        Event::VestingScheduleUpdate(ev) => ev.to_bytes(),
+        Event::TokensVested(ev) => ev.to_bytes(),
+        Event::TokensClaimed(ev) => ev.to_bytes(),
+        Event::ClaimStartTimeUpdate(ev) => ev.to_bytes(),
+        Event::SaleStateUpdate(ev) => ev.to_bytes(),
+        Event::Refund(ev) => ev.to_bytes(),
     }
+    .unwrap_or_revert()
+}
+
+/// Appends the event to the `native_events` dictionary, in the same layout as CES.
+#[cfg(feature = "contract-support")]
+fn native(event: &Event) {
+    let event_id: u64 = get_stored_value_with_user_errors(
+        ARG_NATIVE_EVENTS_COUNT,
+        SwapError::MissingNativeEventsCount,
+        SwapError::InvalidNativeEventsCount,
+    );
+    set_dictionary_value_for_key(
+        DICT_NATIVE_EVENTS,
+        &event_id.to_string(),
+        &Bytes::from(event_bytes(event)),
+    );
+    set_stored_value(
+        ARG_NATIVE_EVENTS_COUNT,
+        event_id
+            .checked_add(1)
+            .unwrap_or_revert_with(SwapError::Overflow),
+    );
 }
 
 #[cfg(feature = "contract-support")]
 pub fn init_events() {
-    use casper_contract::contract_api::runtime::get_key;
+    use casper_contract::contract_api::{
+        runtime::{get_key, put_key},
+        storage::{new_dictionary, new_uref},
+    };
 
     let events_mode =
         EventsMode::try_from(get_stored_value::<u8>(ARG_EVENTS_MODE)).unwrap_or_revert();
 
-    if [EventsMode::CES].contains(&events_mode)
-        && get_key(casper_event_standard::EVENTS_DICT).is_none()
+    if [EventsMode::Native, EventsMode::NativeAndCES].contains(&events_mode)
+        && get_key(DICT_NATIVE_EVENTS).is_none()
     {
-        let schemas = Schemas::new()
-            .with::<SetModalities>()
-            .with::<Upgrade>()
-            .with::<CowlCep18ContractPackageUpdate>()
-            .with::<UpdateTimes>()
-            .with::<DepositCspr>()
-            .with::<WithdrawCowl>()
-            .with::<WithdrawCspr>()
-            .with::<CowlToCspr>()
-            .with::<CsprToCowl>()
-            .with::<ChangeSecurity>();
-        casper_event_standard::init(schemas);
+        new_dictionary(DICT_NATIVE_EVENTS).unwrap_or_revert();
+        put_key(ARG_NATIVE_EVENTS_COUNT, new_uref(0_u64).into());
     }
+
+    if [EventsMode::CES, EventsMode::NativeAndCES].contains(&events_mode) {
+        if get_key(casper_event_standard::EVENTS_DICT).is_none() {
+            casper_event_standard::init(event_schemas());
+        } else {
+            // Schemas of earlier layouts stay registered so events already on chain still decode
+            let mut schemas: Schemas = get_stored_value(casper_event_standard::EVENTS_SCHEMA);
+            schemas.0.extend(event_schemas().0);
+            set_stored_value(casper_event_standard::EVENTS_SCHEMA, schemas);
+        }
+    }
+}
+
+#[cfg(feature = "contract-support")]
+fn event_schemas() -> Schemas {
+    Schemas::new()
+        .with::<SetModalities>()
+        .with::<Upgrade>()
+        .with::<CowlCep18ContractPackageUpdate>()
+        .with::<UpdateTimes>()
+        .with::<DepositCowl>()
+        .with::<DepositCspr>()
+        .with::<WithdrawCowl>()
+        .with::<WithdrawCspr>()
+        .with::<CowlToCspr>()
+        .with::<CsprToCowl>()
+        .with::<Reconcile>()
+        .with::<ReserveLimitsUpdate>()
+        .with::<CsprReserveLow>()
+        .with::<CowlReserveLow>()
+        .with::<Migrated>()
+        .with::<Rollback>()
+        .with::<PoolCreated>()
+        .with::<TokenPairUpdate>()
+        .with::<TokenToToken>()
+        .with::<PoolUpdate>()
+        .with::<PricingModeUpdate>()
+        .with::<AddLiquidity>()
+        .with::<RemoveLiquidity>()
+        .with::<OracleConfigUpdate>()
+        .with::<OraclePriceUpdate>()
+        .with::<BondingCurveUpdate>()
+        .with::<ReferralBonusUpdate>()
+        .with::<ReferralReward>()
+        .with::<VestingScheduleUpdate>()
+        .with::<TokensVested>()
+        .with::<TokensClaimed>()
+        .with::<ClaimStartTimeUpdate>()
+        .with::<SaleStateUpdate>()
+        .with::<Refund>()
+        .with::<ChangeSecurity>()
 }
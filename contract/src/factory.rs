@@ -0,0 +1,78 @@
+//! Registry of the swap pools deployed by a factory contract.
+use alloc::string::String;
+use casper_types::Key;
+
+use crate::utils::tuple_typed;
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{ARG_POOL_COUNT, DICT_POOLS, MAX_POOLS_PAGE},
+    error::SwapError,
+    utils::{
+        get_dictionary_value_from_key, get_stored_value_with_user_errors,
+        set_dictionary_value_for_key, set_stored_value,
+    },
+};
+#[cfg(feature = "contract-support")]
+use alloc::{string::ToString, vec::Vec};
+#[cfg(feature = "contract-support")]
+use casper_contract::{contract_api::runtime::revert, unwrap_or_revert::UnwrapOrRevert};
+
+/// Metadata recorded for every pool created by the factory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolInfo {
+    pub name: String,
+    pub package_hash: Key,
+    pub contract_hash: Key,
+    pub token_package: Key,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub creator: Key,
+    pub created_at: u64,
+}
+
+tuple_typed! {
+    PoolInfo as (String, (Key, Key, Key), ((u64, u64), Key, u64)) {
+        name, package_hash, contract_hash, token_package, start_time, end_time, creator, created_at
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_pool_count() -> u64 {
+    get_stored_value_with_user_errors(
+        ARG_POOL_COUNT,
+        SwapError::MissingPoolCount,
+        SwapError::InvalidPoolCount,
+    )
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_pool(index: u64) -> Option<PoolInfo> {
+    get_dictionary_value_from_key(DICT_POOLS, &index.to_string())
+}
+
+/// Appends a pool to the registry and returns its index.
+#[cfg(feature = "contract-support")]
+pub fn register_pool(pool: &PoolInfo) -> u64 {
+    let index = get_pool_count();
+    set_dictionary_value_for_key(DICT_POOLS, &index.to_string(), pool);
+    set_stored_value(
+        ARG_POOL_COUNT,
+        index
+            .checked_add(1)
+            .unwrap_or_revert_with(SwapError::Overflow),
+    );
+    index
+}
+
+/// Pools with an index in `offset..offset + limit`, stopping at the last registered pool.
+#[cfg(feature = "contract-support")]
+pub fn list_pools(offset: u64, limit: u64) -> Vec<PoolInfo> {
+    if limit == 0 || limit > MAX_POOLS_PAGE {
+        revert(SwapError::InvalidPoolRange);
+    }
+    let end = offset.saturating_add(limit).min(get_pool_count());
+    (offset..end)
+        .map(|index| get_pool(index).unwrap_or_revert_with(SwapError::InvalidPoolCount))
+        .collect()
+}
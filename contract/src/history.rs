@@ -0,0 +1,121 @@
+//! Per-account swap history kept on-chain, for the whole package and for each pool.
+#[cfg(feature = "contract-support")]
+use casper_types::Key;
+use casper_types::{U256, U512};
+
+use crate::utils::tuple_typed;
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{ARG_SWAP_COUNT, DICT_POOL_SWAP_STATS, DICT_SWAP_STATS},
+    error::SwapError,
+    pools::{account_item_key, Pool},
+    utils::{
+        get_current_time_in_seconds, get_dictionary_value_from_key,
+        get_stored_value_with_user_errors, key_to_dictionary_item_key,
+        set_dictionary_value_for_key, set_stored_value,
+    },
+};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+
+/// Swap totals of a single account, `*_in` being what it paid and `*_out` what it received.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapStats {
+    pub cspr_in: U512,
+    pub cspr_out: U512,
+    pub cowl_in: U256,
+    pub cowl_out: U256,
+    pub swap_count: u64,
+    pub last_swap_time: u64,
+}
+
+tuple_typed! {
+    SwapStats as ((U512, U512), (U256, U256), (u64, u64)) {
+        cspr_in, cspr_out, cowl_in, cowl_out, swap_count, last_swap_time
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_swap_stats(account: &Key) -> SwapStats {
+    get_dictionary_value_from_key(DICT_SWAP_STATS, &key_to_dictionary_item_key(account))
+        .unwrap_or_default()
+}
+
+/// Totals of the account's swaps in `pool`.
+#[cfg(feature = "contract-support")]
+pub fn get_pool_swap_stats(pool: &Pool, account: &Key) -> SwapStats {
+    get_dictionary_value_from_key(DICT_POOL_SWAP_STATS, &account_item_key(pool, account))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_swap_count() -> u64 {
+    get_stored_value_with_user_errors(
+        ARG_SWAP_COUNT,
+        SwapError::MissingSwapCount,
+        SwapError::InvalidSwapCount,
+    )
+}
+
+#[cfg(feature = "contract-support")]
+pub fn record_cspr_to_cowl(pool: &Pool, account: &Key, cspr_amount: U512, cowl_amount: U256) {
+    record_swap(pool, account, |stats| {
+        stats.cspr_in = stats
+            .cspr_in
+            .checked_add(cspr_amount)
+            .unwrap_or_revert_with(SwapError::Overflow);
+        stats.cowl_out = stats
+            .cowl_out
+            .checked_add(cowl_amount)
+            .unwrap_or_revert_with(SwapError::Overflow);
+    });
+}
+
+#[cfg(feature = "contract-support")]
+pub fn record_cowl_to_cspr(pool: &Pool, account: &Key, cowl_amount: U256, cspr_amount: U512) {
+    record_swap(pool, account, |stats| {
+        stats.cowl_in = stats
+            .cowl_in
+            .checked_add(cowl_amount)
+            .unwrap_or_revert_with(SwapError::Overflow);
+        stats.cspr_out = stats
+            .cspr_out
+            .checked_add(cspr_amount)
+            .unwrap_or_revert_with(SwapError::Overflow);
+    });
+}
+
+#[cfg(feature = "contract-support")]
+fn record_swap(pool: &Pool, account: &Key, update: impl Fn(&mut SwapStats)) {
+    let swap_time = get_current_time_in_seconds();
+    let apply = |stats: &mut SwapStats| {
+        update(stats);
+        stats.swap_count = stats
+            .swap_count
+            .checked_add(1)
+            .unwrap_or_revert_with(SwapError::Overflow);
+        stats.last_swap_time = swap_time;
+    };
+
+    let mut stats = get_swap_stats(account);
+    apply(&mut stats);
+    set_dictionary_value_for_key(
+        DICT_SWAP_STATS,
+        &key_to_dictionary_item_key(account),
+        &stats,
+    );
+
+    let mut pool_stats = get_pool_swap_stats(pool, account);
+    apply(&mut pool_stats);
+    set_dictionary_value_for_key(
+        DICT_POOL_SWAP_STATS,
+        &account_item_key(pool, account),
+        &pool_stats,
+    );
+
+    let swap_count = get_swap_count()
+        .checked_add(1)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_stored_value(ARG_SWAP_COUNT, swap_count);
+}
@@ -2,10 +2,24 @@
 extern crate alloc;
 
 pub mod constants;
+pub mod curve;
 pub mod entry_points;
 pub mod enums;
 pub mod error;
 pub mod events;
+pub mod factory;
+pub mod history;
+pub mod liquidity;
+pub mod migrations;
+pub mod oracle;
+pub mod pairs;
+pub mod pools;
+pub mod pricing;
 pub mod rate;
+pub mod referrals;
+pub mod reserves;
+pub mod sale;
 pub mod security;
+pub mod sessions;
 pub mod utils;
+pub mod vesting;
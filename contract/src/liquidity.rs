@@ -0,0 +1,351 @@
+//! Liquidity added by any account against shares of a pool, token amounts being kept in U512.
+use casper_types::U512;
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{DICT_LIQUIDITY, DICT_LIQUIDITY_POSITIONS},
+    pools::{account_item_key, pool_item_key, Pool},
+    utils::{get_dictionary_value_from_key, set_dictionary_value_for_key, u256_to_u512},
+};
+use crate::{error::SwapError, utils::tuple_typed};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+#[cfg(feature = "contract-support")]
+use casper_types::{Key, U256};
+
+/// Fixed-point scale of the tax accrued per share.
+pub const FEE_PER_SHARE_PRECISION: U512 = U512([1_000_000_000_000_000_000u64, 0, 0, 0, 0, 0, 0, 0]);
+
+/// Provider side of a pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LiquidityState {
+    pub total_shares: U512,
+    /// Part of the current reserves owned by providers, moved by the swaps they take part in.
+    pub cspr_liquidity: U512,
+    pub token_liquidity: U512,
+    /// Tax accrued per share since the pool opened, scaled by [`FEE_PER_SHARE_PRECISION`].
+    pub cspr_fee_per_share: U512,
+    pub token_fee_per_share: U512,
+    /// Tax earned by providers and not paid out yet, kept in the reserves.
+    pub cspr_fees_owed: U512,
+    pub token_fees_owed: U512,
+}
+
+/// Shares of one provider, the debts being the tax accrued per share before it joined.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LiquidityPosition {
+    pub shares: U512,
+    pub cspr_fee_debt: U512,
+    pub token_fee_debt: U512,
+}
+
+/// Amounts paid back when shares are removed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LiquidityWithdrawal {
+    pub cspr_amount: U512,
+    pub token_amount: U512,
+    pub cspr_fees: U512,
+    pub token_fees: U512,
+}
+
+fn accrued(shares: U512, fee_per_share: U512) -> Result<U512, SwapError> {
+    shares
+        .checked_mul(fee_per_share)
+        .map(|value| value / FEE_PER_SHARE_PRECISION)
+        .ok_or(SwapError::Overflow)
+}
+
+fn proportion(amount: U512, numerator: U512, denominator: U512) -> Result<U512, SwapError> {
+    amount
+        .checked_mul(numerator)
+        .and_then(|value| value.checked_div(denominator))
+        .ok_or(SwapError::Overflow)
+}
+
+fn add(value: U512, amount: U512) -> Result<U512, SwapError> {
+    value.checked_add(amount).ok_or(SwapError::Overflow)
+}
+
+fn sub(value: U512, amount: U512) -> Result<U512, SwapError> {
+    value.checked_sub(amount).ok_or(SwapError::Overflow)
+}
+
+impl LiquidityState {
+    /// Shares minted for a contribution, the geometric mean of both amounts for the first
+    /// provider and the smaller of their proportions of the provider liquidity afterwards.
+    pub fn shares_for(&self, cspr_amount: U512, token_amount: U512) -> Result<U512, SwapError> {
+        if cspr_amount.is_zero() || token_amount.is_zero() {
+            return Err(SwapError::InvalidAmount);
+        }
+        let cspr_shares = (!self.cspr_liquidity.is_zero())
+            .then(|| proportion(cspr_amount, self.total_shares, self.cspr_liquidity))
+            .transpose()?;
+        let token_shares = (!self.token_liquidity.is_zero())
+            .then(|| proportion(token_amount, self.total_shares, self.token_liquidity))
+            .transpose()?;
+        let shares = match (cspr_shares, token_shares) {
+            _ if self.total_shares.is_zero() => geometric_mean(cspr_amount, token_amount)?,
+            (Some(cspr_shares), Some(token_shares)) => cspr_shares.min(token_shares),
+            (Some(shares), None) | (None, Some(shares)) => shares,
+            (None, None) => geometric_mean(cspr_amount, token_amount)?,
+        };
+        if shares.is_zero() {
+            return Err(SwapError::InvalidAmount);
+        }
+        Ok(shares)
+    }
+
+    /// Adds a contribution to the pool and the position, returning the minted shares.
+    pub fn add(
+        &mut self,
+        position: &mut LiquidityPosition,
+        cspr_amount: U512,
+        token_amount: U512,
+    ) -> Result<U512, SwapError> {
+        let shares = self.shares_for(cspr_amount, token_amount)?;
+
+        self.total_shares = add(self.total_shares, shares)?;
+        self.cspr_liquidity = add(self.cspr_liquidity, cspr_amount)?;
+        self.token_liquidity = add(self.token_liquidity, token_amount)?;
+
+        // New shares do not earn the tax accrued before they were minted
+        position.shares = add(position.shares, shares)?;
+        position.cspr_fee_debt = add(
+            position.cspr_fee_debt,
+            accrued(shares, self.cspr_fee_per_share)?,
+        )?;
+        position.token_fee_debt = add(
+            position.token_fee_debt,
+            accrued(shares, self.token_fee_per_share)?,
+        )?;
+
+        Ok(shares)
+    }
+
+    /// Removes `shares` from the position, paying back their part and the tax it accrued.
+    pub fn remove(
+        &mut self,
+        position: &mut LiquidityPosition,
+        shares: U512,
+    ) -> Result<LiquidityWithdrawal, SwapError> {
+        if shares.is_zero() || shares > position.shares {
+            return Err(SwapError::InsufficientShares);
+        }
+
+        let (cspr_fees, token_fees) = position.pending_fees(self)?;
+        let cspr_amount = proportion(self.cspr_liquidity, shares, self.total_shares)?;
+        let token_amount = proportion(self.token_liquidity, shares, self.total_shares)?;
+
+        self.total_shares = sub(self.total_shares, shares)?;
+        self.cspr_liquidity = sub(self.cspr_liquidity, cspr_amount)?;
+        self.token_liquidity = sub(self.token_liquidity, token_amount)?;
+        // Positions round their tax down separately, which can leave them a unit above the total
+        self.cspr_fees_owed = self.cspr_fees_owed.saturating_sub(cspr_fees);
+        self.token_fees_owed = self.token_fees_owed.saturating_sub(token_fees);
+
+        position.shares = sub(position.shares, shares)?;
+        position.cspr_fee_debt = accrued(position.shares, self.cspr_fee_per_share)?;
+        position.token_fee_debt = accrued(position.shares, self.token_fee_per_share)?;
+
+        Ok(LiquidityWithdrawal {
+            cspr_amount,
+            token_amount,
+            cspr_fees,
+            token_fees,
+        })
+    }
+
+    /// Providers' part of `amount` traded against a reserve, the tax owed to them left out of it.
+    pub fn provider_part(
+        amount: U512,
+        liquidity: U512,
+        fees_owed: U512,
+        reserve: U512,
+    ) -> Result<U512, SwapError> {
+        let tradeable = reserve.saturating_sub(fees_owed);
+        if tradeable.is_zero() {
+            return Ok(U512::zero());
+        }
+        Ok(proportion(amount, liquidity, tradeable)?.min(amount))
+    }
+
+    /// Providers' part of a CSPR → token swap, `token_reserve` being the reserve before it.
+    pub fn sell_tokens(
+        &mut self,
+        cspr_in: U512,
+        token_out: U512,
+        token_fee: U512,
+        token_reserve: U512,
+    ) -> Result<(), SwapError> {
+        if self.total_shares.is_zero() {
+            return Ok(());
+        }
+        let part = |amount| {
+            Self::provider_part(
+                amount,
+                self.token_liquidity,
+                self.token_fees_owed,
+                token_reserve,
+            )
+        };
+        let sold = part(add(token_out, token_fee)?)?;
+        let fee = part(token_fee)?;
+        let cspr = part(cspr_in)?;
+
+        self.token_liquidity = self
+            .token_liquidity
+            .checked_sub(sold)
+            .ok_or(SwapError::InsufficientLiquidity)?;
+        self.cspr_liquidity = add(self.cspr_liquidity, cspr)?;
+        self.token_fees_owed = add(self.token_fees_owed, fee)?;
+        self.token_fee_per_share = add(
+            self.token_fee_per_share,
+            proportion(fee, FEE_PER_SHARE_PRECISION, self.total_shares)?,
+        )?;
+        Ok(())
+    }
+
+    /// Providers' part of a token → CSPR swap, `cspr_reserve` being the reserve before it.
+    pub fn buy_tokens(
+        &mut self,
+        token_in: U512,
+        cspr_out: U512,
+        cspr_fee: U512,
+        cspr_reserve: U512,
+    ) -> Result<(), SwapError> {
+        if self.total_shares.is_zero() {
+            return Ok(());
+        }
+        let part = |amount| {
+            Self::provider_part(
+                amount,
+                self.cspr_liquidity,
+                self.cspr_fees_owed,
+                cspr_reserve,
+            )
+        };
+        let sold = part(add(cspr_out, cspr_fee)?)?;
+        let fee = part(cspr_fee)?;
+        let tokens = part(token_in)?;
+
+        self.cspr_liquidity = self
+            .cspr_liquidity
+            .checked_sub(sold)
+            .ok_or(SwapError::InsufficientLiquidity)?;
+        self.token_liquidity = add(self.token_liquidity, tokens)?;
+        self.cspr_fees_owed = add(self.cspr_fees_owed, fee)?;
+        self.cspr_fee_per_share = add(
+            self.cspr_fee_per_share,
+            proportion(fee, FEE_PER_SHARE_PRECISION, self.total_shares)?,
+        )?;
+        Ok(())
+    }
+
+    /// CSPR owned by providers, liquidity and unpaid tax, which admins cannot withdraw.
+    pub fn cspr_held(&self) -> Result<U512, SwapError> {
+        add(self.cspr_liquidity, self.cspr_fees_owed)
+    }
+
+    /// Tokens owned by providers, liquidity and unpaid tax, which admins cannot withdraw.
+    pub fn token_held(&self) -> Result<U512, SwapError> {
+        add(self.token_liquidity, self.token_fees_owed)
+    }
+}
+
+fn geometric_mean(cspr_amount: U512, token_amount: U512) -> Result<U512, SwapError> {
+    Ok(cspr_amount
+        .checked_mul(token_amount)
+        .ok_or(SwapError::Overflow)?
+        .integer_sqrt())
+}
+
+impl LiquidityPosition {
+    /// CSPR and token tax accrued by the position and not paid out yet.
+    pub fn pending_fees(&self, state: &LiquidityState) -> Result<(U512, U512), SwapError> {
+        Ok((
+            accrued(self.shares, state.cspr_fee_per_share)?.saturating_sub(self.cspr_fee_debt),
+            accrued(self.shares, state.token_fee_per_share)?.saturating_sub(self.token_fee_debt),
+        ))
+    }
+}
+
+tuple_typed! {
+    LiquidityState as ((U512, U512, U512), (U512, U512), (U512, U512)) {
+        total_shares, cspr_liquidity, token_liquidity, cspr_fee_per_share, token_fee_per_share,
+        cspr_fees_owed, token_fees_owed
+    }
+}
+
+tuple_typed! {
+    LiquidityPosition as (U512, U512, U512) {
+        shares, cspr_fee_debt, token_fee_debt
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_liquidity_state(pool: &Pool) -> LiquidityState {
+    get_dictionary_value_from_key(DICT_LIQUIDITY, &pool_item_key(pool)).unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_liquidity_state(pool: &Pool, state: &LiquidityState) {
+    set_dictionary_value_for_key(DICT_LIQUIDITY, &pool_item_key(pool), state)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_liquidity_position(pool: &Pool, provider: &Key) -> LiquidityPosition {
+    get_dictionary_value_from_key(DICT_LIQUIDITY_POSITIONS, &account_item_key(pool, provider))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_liquidity_position(pool: &Pool, provider: &Key, position: &LiquidityPosition) {
+    set_dictionary_value_for_key(
+        DICT_LIQUIDITY_POSITIONS,
+        &account_item_key(pool, provider),
+        position,
+    )
+}
+
+/// Moves the providers' part of a CSPR → token swap.
+#[cfg(feature = "contract-support")]
+pub fn share_token_sale(
+    pool: &Pool,
+    cspr_in: U512,
+    token_out: U256,
+    token_fee: U256,
+    token_reserve: U256,
+) {
+    let mut state = get_liquidity_state(pool);
+    if state.total_shares.is_zero() {
+        return;
+    }
+    state
+        .sell_tokens(
+            cspr_in,
+            u256_to_u512(token_out),
+            u256_to_u512(token_fee),
+            u256_to_u512(token_reserve),
+        )
+        .unwrap_or_revert();
+    save_liquidity_state(pool, &state);
+}
+
+/// Moves the providers' part of a token → CSPR swap.
+#[cfg(feature = "contract-support")]
+pub fn share_token_purchase(
+    pool: &Pool,
+    token_in: U256,
+    cspr_out: U512,
+    cspr_fee: U512,
+    cspr_reserve: U512,
+) {
+    let mut state = get_liquidity_state(pool);
+    if state.total_shares.is_zero() {
+        return;
+    }
+    state
+        .buy_tokens(u256_to_u512(token_in), cspr_out, cspr_fee, cspr_reserve)
+        .unwrap_or_revert();
+    save_liquidity_state(pool, &state);
+}
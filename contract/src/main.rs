@@ -11,88 +11,195 @@ use alloc::{
 use casper_contract::{
     contract_api::{
         runtime::{
-            call_contract, call_versioned_contract, get_caller, get_key, get_named_arg, put_key,
-            ret, revert,
+            call_contract, call_versioned_contract, get_caller, get_key, get_named_arg,
+            list_named_keys, put_key, ret, revert,
         },
         storage::{
-            add_contract_version, disable_contract_version, new_contract, new_dictionary, new_uref,
-        },
-        system::{
-            create_purse, get_purse_balance, transfer_from_purse_to_account,
-            transfer_from_purse_to_purse,
+            add_contract_version, disable_contract_version, enable_contract_version, new_contract,
+            new_dictionary, new_uref, read, write,
         },
+        system::{create_purse, transfer_from_purse_to_account, transfer_from_purse_to_purse},
     },
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    contracts::NamedKeys, runtime_args, system::handle_payment::ARG_PURSE, CLValue, ContractHash,
-    ContractPackageHash, Key, RuntimeArgs, URef, U256, U512,
+    bytesrepr::{FromBytes, ToBytes},
+    contracts::NamedKeys,
+    runtime_args,
+    system::handle_payment::ARG_PURSE,
+    CLTyped, CLValue, ContractHash, ContractPackageHash, Key, RuntimeArgs, URef, U256, U512,
 };
 use cowl_swap::{
     constants::{
-        ADMIN_LIST, ARG_AMOUNT, ARG_BALANCE_COWL, ARG_BALANCE_CSPR, ARG_CONTRACT_HASH,
-        ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_DURATION, ARG_END_TIME, ARG_EVENTS_MODE,
-        ARG_INSTALLER, ARG_NAME, ARG_OWNER, ARG_PACKAGE_HASH, ARG_RECIPIENT, ARG_START_TIME,
-        ARG_UPGRADE_FLAG, DICT_SECURITY_BADGES, ENTRY_POINT_BALANCE_COWL, ENTRY_POINT_BALANCE_CSPR,
-        ENTRY_POINT_INSTALL, ENTRY_POINT_TRANSFER, ENTRY_POINT_TRANSFER_FROM, ENTRY_POINT_UPGRADE,
-        NONE_LIST, PREFIX_ACCESS_KEY_NAME, PREFIX_CONTRACT_NAME, PREFIX_CONTRACT_PACKAGE_NAME,
-        PREFIX_CONTRACT_VERSION, RATE_TIERS, TAX_RATE,
+        ADMIN_LIST, ARG_ADDRESS, ARG_AMOUNT, ARG_BALANCE_COWL, ARG_BALANCE_CSPR,
+        ARG_CLAIM_START_TIME, ARG_CLIFF_DURATION, ARG_CONTRACT_HASH, ARG_CONTRACT_VERSION,
+        ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_CURVE_STEPS, ARG_DURATION, ARG_ENABLED, ARG_END_TIME,
+        ARG_EVENTS_MODE, ARG_EVENT_SEQUENCE, ARG_FACTORY, ARG_FACTORY_PACKAGE, ARG_INPUT_DECIMALS,
+        ARG_INPUT_TOKEN_PACKAGE, ARG_INSTALLER, ARG_LIMIT, ARG_MAX_AGE, ARG_MAX_DEVIATION,
+        ARG_MIN_INPUT_AMOUNT, ARG_MIN_RESERVE_COWL, ARG_MIN_RESERVE_CSPR, ARG_NAME, ARG_OFFSET,
+        ARG_OUTPUT_DECIMALS, ARG_OUTPUT_TOKEN_PACKAGE, ARG_OWNER, ARG_PACKAGE_HASH, ARG_PAIR_RATE,
+        ARG_PAUSED, ARG_PHASE, ARG_PHASE_END_TIME, ARG_POOL, ARG_POOL_COUNT, ARG_POOL_TOKENS,
+        ARG_PREVIOUS_CONTRACT_HASH, ARG_PREVIOUS_CONTRACT_VERSION, ARG_PRICE, ARG_PRICING_MODE,
+        ARG_RATE_TIERS, ARG_RECIPIENT, ARG_REFERRAL_BONUS, ARG_REFERRER, ARG_ROLLBACK_VERSION,
+        ARG_SHARES, ARG_SOFT_CAP, ARG_SPREAD, ARG_START_TIME, ARG_STORAGE_VERSION, ARG_SWAP_COUNT,
+        ARG_SWEEP, ARG_TAX_RATE, ARG_TIMESTAMP, ARG_TOKEN_AMOUNT, ARG_TOKEN_CONTRACT_PACKAGE,
+        ARG_TOKEN_DECIMALS, ARG_TOKEN_SYMBOL, ARG_UPGRADE_FLAG, ARG_VESTING_DURATION,
+        ARG_VESTING_START_TIME, ARG_WARNING_RESERVE_COWL, ARG_WARNING_RESERVE_CSPR,
+        DEFAULT_TOKEN_DECIMALS, DEFAULT_TOKEN_SYMBOL, DICT_BONDING_CURVES, DICT_CLAIM_START_TIMES,
+        DICT_CONTRACT_NAMED_KEYS, DICT_CONTRACT_VERSIONS, DICT_CONTRIBUTIONS, DICT_COWL_DEPOSITS,
+        DICT_LIQUIDITY, DICT_LIQUIDITY_POSITIONS, DICT_ORACLE_FEEDS, DICT_POOLS,
+        DICT_POOL_PRICING_MODES, DICT_POOL_SWAP_STATS, DICT_REFERRALS, DICT_RESERVES, DICT_SALES,
+        DICT_SECURITY_BADGES, DICT_SWAP_POOLS, DICT_SWAP_STATS, DICT_TOKEN_PAIRS,
+        DICT_VESTING_ALLOCATIONS, DICT_VESTING_PHASES, DICT_VESTING_SCHEDULES,
+        ENTRY_POINT_CREATE_POOL, ENTRY_POINT_INSTALL, ENTRY_POINT_INSTALL_FACTORY,
+        ENTRY_POINT_ROLLBACK, ENTRY_POINT_TRANSFER, ENTRY_POINT_TRANSFER_FROM, ENTRY_POINT_UPGRADE,
+        INSTALLED_SWAPS, MAX_TOKEN_DECIMALS, NONE_LIST, ORACLE_LIST, PREFIX_ACCESS_KEY_NAME,
+        PREFIX_CONTRACT_NAME, PREFIX_CONTRACT_PACKAGE_NAME, PREFIX_CONTRACT_VERSION,
+        PREFIX_FACTORY_ACCESS_KEY_NAME, PREFIX_FACTORY_CONTRACT_NAME, PREFIX_FACTORY_PACKAGE_NAME,
+        RATE_TIERS, TAX_RATE,
+    },
+    curve::{
+        get_bonding_curve, record_curve_purchase, record_curve_sale, save_bonding_curve,
+        validate_curve_steps, BondingCurve, CurveStep,
     },
-    entry_points::generate_entry_points,
-    enums::EventsMode,
+    entry_points::{generate_entry_points, generate_factory_entry_points},
+    enums::{EventsMode, PricingMode, SaleState},
     error::SwapError,
     events::{
-        init_events, record_event_dictionary, ChangeSecurity, CowlCep18ContractPackageUpdate,
-        CowlToCspr, CsprToCowl, DepositCspr, Event, SetModalities, UpdateTimes, Upgrade,
+        init_events, record_event_dictionary, AddLiquidity, BondingCurveUpdate, ChangeSecurity,
+        ClaimStartTimeUpdate, CowlCep18ContractPackageUpdate, CowlToCspr, CsprToCowl, DepositCowl,
+        DepositCspr, Event, EventHeader, Migrated, OracleConfigUpdate, OraclePriceUpdate,
+        PoolCreated, PoolUpdate, PricingModeUpdate, Reconcile, ReferralBonusUpdate, ReferralReward,
+        Refund, RemoveLiquidity, ReserveLimitsUpdate, Rollback, SetModalities, TokenPairUpdate,
+        TokenToToken, TokensClaimed, TokensVested, UpdateTimes, Upgrade, VestingScheduleUpdate,
         WithdrawCowl, WithdrawCspr,
     },
-    rate::{get_swap_rate, validate_amount, validate_rate, verify_swap_active},
+    factory::{get_pool_count, list_pools, register_pool, PoolInfo},
+    history::{
+        get_pool_swap_stats, get_swap_count, get_swap_stats, record_cowl_to_cspr,
+        record_cspr_to_cowl,
+    },
+    liquidity::{
+        get_liquidity_position, get_liquidity_state, save_liquidity_position, save_liquidity_state,
+        share_token_purchase, share_token_sale,
+    },
+    migrations::{
+        get_contract_named_keys, get_contract_version_entry, get_storage_version, migrate_storage,
+        register_contract_version, save_contract_named_keys, STORAGE_VERSION,
+    },
+    oracle::{get_oracle_feed, save_oracle_feed},
+    pairs::{get_token_pair, save_token_pair, TokenPair},
+    pools::{
+        account_item_key, all_pools, default_pool_from_named_keys, get_default_pool, get_pool,
+        get_pool_arg, get_pool_tokens, insert_pool, save_pool, Pool,
+    },
+    pricing::{
+        get_cspr_to_token_quote, get_pricing_mode, get_token_to_cspr_quote, save_pricing_mode,
+    },
+    rate::{validate_amount, validate_rate_tiers, validate_tax_rate, verify_swap_active, RateTier},
+    referrals::{
+        add_referral_reward, get_referral_bonus, get_referral_total, referral_reward,
+        validate_referral_bonus,
+    },
+    reserves::{
+        add_cowl_reserve, add_cspr_reserve, add_package_cspr_reserve, cowl_reserves_breakdown,
+        cspr_reserves_breakdown, expected_cowl_reserve, expected_cspr_reserve, get_locked_cowl,
+        get_reserve_limit, lock_cowl, record_cowl_reserve_warning, record_cspr_reserve_warning,
+        set_reserve_limit, unlock_cowl, verify_cowl_liquidity, verify_cspr_liquidity,
+        ReserveCounter,
+    },
+    sale::{
+        add_contribution, get_contribution, get_sale, get_settled_sale, keeps_raised_cspr,
+        record_sale_state, save_contribution, save_sale,
+    },
     security::{change_sec_badge, sec_check, SecurityBadge},
     utils::{
-        get_cowl_cep18_balance_for_key, get_cowl_cep18_contract_package,
-        get_cowl_swap_contract_package, get_named_arg_with_user_errors,
+        cache_cowl_balance, cache_cspr_balance, get_contract_cspr_balance,
+        get_cowl_swap_contract_package, get_current_time_in_seconds, get_dictionary_value_from_key,
+        get_executing_contract, get_named_arg_with_user_errors,
         get_optional_named_arg_with_user_errors, get_stored_value_with_user_errors,
-        get_verified_caller,
+        get_verified_caller, set_dictionary_value_for_key, set_stored_value, u256_to_u512,
+        u512_to_u256,
+    },
+    vesting::{
+        add_vesting_allocation, get_claim_start_time, get_phase_schedule, get_vesting_allocation,
+        get_vesting_phases, get_vesting_schedule, save_claim_start_time, save_vesting_allocation,
+        save_vesting_phases, save_vesting_schedule, set_vesting_phase, vesting_phase_at,
+        VestingPhase, VestingSchedule,
     },
 };
 
 #[no_mangle]
 pub extern "C" fn balance_cowl() {
-    let owner = get_key(ARG_PACKAGE_HASH).unwrap_or_revert();
-    let balance = get_cowl_cep18_balance_for_key(&owner);
-
-    put_key(ARG_BALANCE_COWL, new_uref(balance).into());
-    ret(CLValue::from_t(balance).unwrap_or_revert())
+    ret(CLValue::from_t(get_pool_arg().token_balance()).unwrap_or_revert())
 }
 
 #[no_mangle]
 pub extern "C" fn balance_cspr() {
-    let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
-    let balance = get_purse_balance(contract_purse.as_uref().unwrap_or_revert().into_read())
-        .unwrap_or_revert();
-    put_key(ARG_BALANCE_CSPR, new_uref(balance).into());
-    ret(CLValue::from_t(balance).unwrap_or_revert())
+    ret(CLValue::from_t(get_contract_cspr_balance()).unwrap_or_revert())
 }
 
+/// COWL alias of `cspr_to_token`.
 #[no_mangle]
 pub extern "C" fn cspr_to_cowl() {
-    verify_swap_active().unwrap_or_revert();
+    swap_cspr_to_token()
+}
+
+#[no_mangle]
+pub extern "C" fn cspr_to_token() {
+    swap_cspr_to_token()
+}
+
+fn swap_cspr_to_token() {
+    let pool = get_pool_arg();
+    verify_swap_active(&pool).unwrap_or_revert();
 
     let cspr_amount: U512 = get_named_arg(ARG_AMOUNT);
 
     validate_amount(cspr_amount).unwrap_or_revert();
 
-    let base_rate = get_swap_rate(cspr_amount).unwrap_or_revert();
+    let quote = get_cspr_to_token_quote(&pool, cspr_amount).unwrap_or_revert();
+    let base_rate = quote.base_rate;
+    let cowl_amount = quote.output_amount;
+
+    let cowl_amount_u256 =
+        u512_to_u256(cowl_amount).unwrap_or_revert_with(SwapError::InvalidAmount);
+    let fee_amount_u256 =
+        u512_to_u256(quote.fee_amount).unwrap_or_revert_with(SwapError::InvalidAmount);
+    validate_amount(cowl_amount).unwrap_or_revert();
 
-    validate_rate(base_rate).unwrap_or_revert();
+    let (recipient, recipient_package) = get_verified_caller();
 
-    let cowl_amount = cspr_amount
-        .checked_mul(base_rate)
-        .unwrap_or_revert_with(SwapError::Overflow);
+    let mut sale = get_sale(&pool);
+    let raising = sale.state == SaleState::Raising;
 
-    let cowl_amount_u256 = U256::from_dec_str(&cowl_amount.to_string())
-        .unwrap_or_else(|_| revert(SwapError::InvalidAmount));
-    validate_amount(cowl_amount).unwrap_or_revert();
+    // No referral bonus is paid out of a sale that may still be refunded, so referred purchases
+    // are refused rather than recorded without one
+    let referrer: Option<Key> =
+        get_optional_named_arg_with_user_errors(ARG_REFERRER, SwapError::InvalidReferrer);
+    let reward_amount = match referrer {
+        Some(referrer) if referrer == recipient || Some(referrer) == recipient_package => {
+            revert(SwapError::SelfReferral)
+        }
+        Some(_) if raising => revert(SwapError::ReferralWhileRaising),
+        Some(_) => referral_reward(cowl_amount_u256, get_referral_bonus()).unwrap_or_revert(),
+        None => U256::zero(),
+    };
+
+    let paid_amount = cowl_amount_u256
+        .checked_add(reward_amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    let cowl_reserve = expected_cowl_reserve(&pool);
+    verify_cowl_liquidity(&pool, cowl_reserve, paid_amount).unwrap_or_revert();
+    // Liquidity providers stay out of a sale that may still be refunded, so its tokens are paid
+    // from those outside their liquidity
+    if raising {
+        let provided = get_liquidity_state(&pool).token_held().unwrap_or_revert();
+        match u256_to_u512(cowl_reserve).checked_sub(u256_to_u512(paid_amount)) {
+            Some(remaining) if remaining >= provided => {}
+            _ => revert(SwapError::InsufficientLiquidity),
+        }
+    }
 
     let source_purse: URef = get_named_arg(ARG_PURSE);
     let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
@@ -107,19 +214,78 @@ pub extern "C" fn cspr_to_cowl() {
     )
     .unwrap_or_revert();
 
-    let (recipient, _) = get_verified_caller();
+    // Tokens bought while the pool vests or raises toward its soft cap stay in the contract until
+    // claimed, like those of a pool with a claim start time. Purchases made during a phase vest
+    // with its schedule, except while raising: they then go to the pool-wide allocation refunds
+    // are taken from.
+    let phase = vesting_phase_at(&get_vesting_phases(&pool), get_current_time_in_seconds())
+        .filter(|_| !raising)
+        .map(|phase| phase.start_time);
+    let vesting = phase.is_some()
+        || get_vesting_schedule(&pool).is_some()
+        || get_claim_start_time(&pool).is_some();
+    let vested_total = if raising || vesting {
+        lock_cowl(&pool, cowl_amount_u256);
+        Some(add_vesting_allocation(
+            &pool,
+            &recipient,
+            phase,
+            cowl_amount_u256,
+        ))
+    } else {
+        call_versioned_contract::<()>(
+            pool.token_package_hash(),
+            None,
+            ENTRY_POINT_TRANSFER,
+            runtime_args! {
+                ARG_RECIPIENT => recipient,
+                ARG_AMOUNT => cowl_amount_u256
+            },
+        );
+        None
+    };
 
-    call_versioned_contract::<()>(
-        get_cowl_cep18_contract_package(),
-        None,
-        ENTRY_POINT_TRANSFER,
-        runtime_args! {
-            ARG_RECIPIENT => recipient,
-            ARG_AMOUNT => cowl_amount_u256
-        },
-    );
+    add_cspr_reserve(&pool, ReserveCounter::SwappedIn, cspr_amount);
+    add_cowl_reserve(&pool, ReserveCounter::SwappedOut, cowl_amount_u256);
+    let on_curve = get_pricing_mode(&pool) == PricingMode::BondingCurve;
+    if on_curve {
+        record_curve_purchase(&pool, cowl_amount);
+    }
+    if !fee_amount_u256.is_zero() {
+        add_cowl_reserve(&pool, ReserveCounter::Fees, fee_amount_u256);
+    }
+    if !raising {
+        share_token_sale(
+            &pool,
+            cspr_amount,
+            paid_amount,
+            fee_amount_u256,
+            cowl_reserve,
+        );
+    }
+    record_cspr_to_cowl(&pool, &recipient, cspr_amount, cowl_amount_u256);
+
+    let mut soft_cap_met = false;
+    if raising {
+        let curve_amount = if on_curve {
+            cowl_amount_u256
+        } else {
+            U256::zero()
+        };
+        add_contribution(
+            &pool,
+            &recipient,
+            cspr_amount,
+            cowl_amount_u256,
+            curve_amount,
+        );
+        soft_cap_met = sale.contribute(cspr_amount).unwrap_or_revert();
+        save_sale(&pool, &sale);
+    }
 
     record_event_dictionary(Event::CsprToCowl(CsprToCowl {
+        header: EventHeader::next(),
+        pool: pool.token_package,
         source_purse,
         recipient,
         cowl_amount: cowl_amount_u256,
@@ -127,49 +293,126 @@ pub extern "C" fn cspr_to_cowl() {
         base_rate,
     }));
 
-    let cowl_swap_contract_package = get_cowl_swap_contract_package();
+    if let Some(total) = vested_total {
+        record_event_dictionary(Event::TokensVested(TokensVested {
+            header: EventHeader::next(),
+            pool: pool.token_package,
+            phase,
+            beneficiary: recipient,
+            amount: cowl_amount_u256,
+            total,
+        }));
+    }
 
-    call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
-        None,
-        ENTRY_POINT_BALANCE_CSPR,
-        runtime_args! {},
-    );
+    if soft_cap_met {
+        record_sale_state(&pool, &sale);
+    }
 
-    call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
-        None,
-        ENTRY_POINT_BALANCE_COWL,
-        runtime_args! {},
-    );
+    if let Some(referrer) = referrer.filter(|_| !reward_amount.is_zero()) {
+        pay_referral_reward(
+            &pool,
+            referrer,
+            recipient,
+            cowl_amount_u256,
+            reward_amount,
+            vesting,
+            phase,
+        );
+    }
+
+    record_cowl_reserve_warning(&pool, cowl_reserve, expected_cowl_reserve(&pool));
+
+    cache_cspr_balance();
+    cache_pool_balance(&pool);
+}
+
+/// Pays the bonus of `referrer`, credited to its allocation in `phase` like the purchase when the
+/// pool vests.
+fn pay_referral_reward(
+    pool: &Pool,
+    referrer: Key,
+    buyer: Key,
+    token_amount: U256,
+    reward_amount: U256,
+    vesting: bool,
+    phase: Option<u64>,
+) {
+    let vested_total = if vesting {
+        lock_cowl(pool, reward_amount);
+        Some(add_vesting_allocation(
+            pool,
+            &referrer,
+            phase,
+            reward_amount,
+        ))
+    } else {
+        call_versioned_contract::<()>(
+            pool.token_package_hash(),
+            None,
+            ENTRY_POINT_TRANSFER,
+            runtime_args! {
+                ARG_RECIPIENT => referrer,
+                ARG_AMOUNT => reward_amount
+            },
+        );
+        None
+    };
+
+    add_cowl_reserve(pool, ReserveCounter::SwappedOut, reward_amount);
+    add_referral_reward(pool, &referrer, reward_amount);
+
+    record_event_dictionary(Event::ReferralReward(ReferralReward {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        referrer,
+        buyer,
+        token_amount,
+        reward_amount,
+    }));
+
+    if let Some(total) = vested_total {
+        record_event_dictionary(Event::TokensVested(TokensVested {
+            header: EventHeader::next(),
+            pool: pool.token_package,
+            phase,
+            beneficiary: referrer,
+            amount: reward_amount,
+            total,
+        }));
+    }
 }
 
+/// COWL alias of `token_to_cspr`.
 #[no_mangle]
 pub extern "C" fn cowl_to_cspr() {
-    verify_swap_active().unwrap_or_revert();
+    swap_token_to_cspr()
+}
+
+#[no_mangle]
+pub extern "C" fn token_to_cspr() {
+    swap_token_to_cspr()
+}
+
+fn swap_token_to_cspr() {
+    let pool = get_pool_arg();
+    verify_swap_active(&pool).unwrap_or_revert();
     let cowl_amount_u256: U256 = get_named_arg(ARG_AMOUNT);
-    let cowl_amount_u512: U512 = U512::from_dec_str(&cowl_amount_u256.to_string())
-        .unwrap_or_else(|_| revert(SwapError::InvalidAmount));
+    let cowl_amount_u512 = u256_to_u512(cowl_amount_u256);
 
     validate_amount(cowl_amount_u512).unwrap_or_revert();
 
-    let base_rate = RATE_TIERS.first().unwrap_or_revert().rate;
-    validate_rate(base_rate).unwrap_or_revert();
-
-    let cspr_amount: U512 = cowl_amount_u512
-        .checked_div(base_rate)
-        .unwrap_or_revert_with(SwapError::InvalidAmount);
+    let quote = get_token_to_cspr_quote(&pool, cowl_amount_u512).unwrap_or_revert();
+    let base_rate = quote.base_rate;
+    let cspr_amount = quote.output_amount;
+    let tax_amount = quote.fee_amount;
 
     validate_amount(cspr_amount).unwrap_or_revert();
 
-    let tax_amount = cspr_amount
-        .checked_mul(TAX_RATE)
-        .and_then(|mul_result| mul_result.checked_div(U512::from(100)))
-        .unwrap_or_revert_with(SwapError::InvalidRate);
-
-    let cspr_amount = cspr_amount
-        .checked_sub(tax_amount)
-        .unwrap_or_revert_with(SwapError::InvalidAmount);
+    let cspr_reserve = expected_cspr_reserve(&pool);
+    verify_cspr_liquidity(&pool, cspr_reserve, cspr_amount).unwrap_or_revert();
+    if !keeps_raised_cspr(cspr_amount) {
+        revert(SwapError::InsufficientLiquidity);
+    }
 
     let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
 
@@ -178,7 +421,7 @@ pub extern "C" fn cowl_to_cspr() {
     let recipient = get_key(ARG_PACKAGE_HASH).unwrap_or_revert();
 
     call_versioned_contract::<()>(
-        get_cowl_cep18_contract_package(),
+        pool.token_package_hash(),
         None,
         ENTRY_POINT_TRANSFER_FROM,
         runtime_args! {
@@ -200,7 +443,24 @@ pub extern "C" fn cowl_to_cspr() {
     )
     .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
 
+    add_cowl_reserve(&pool, ReserveCounter::SwappedIn, cowl_amount_u256);
+    add_cspr_reserve(&pool, ReserveCounter::SwappedOut, cspr_amount);
+    add_cspr_reserve(&pool, ReserveCounter::Fees, tax_amount);
+    if get_pricing_mode(&pool) == PricingMode::BondingCurve {
+        record_curve_sale(&pool, cowl_amount_u512);
+    }
+    share_token_purchase(
+        &pool,
+        cowl_amount_u256,
+        cspr_amount,
+        tax_amount,
+        cspr_reserve,
+    );
+    record_cowl_to_cspr(&pool, &owner, cowl_amount_u256, cspr_amount);
+
     record_event_dictionary(Event::CowlToCspr(CowlToCspr {
+        header: EventHeader::next(),
+        pool: pool.token_package,
         owner,
         recipient,
         cowl_amount: cowl_amount_u256,
@@ -209,21 +469,10 @@ pub extern "C" fn cowl_to_cspr() {
         tax_amount,
     }));
 
-    let cowl_swap_contract_package = get_cowl_swap_contract_package();
-
-    call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
-        None,
-        ENTRY_POINT_BALANCE_CSPR,
-        runtime_args! {},
-    );
+    record_cspr_reserve_warning(&pool, cspr_reserve, expected_cspr_reserve(&pool));
 
-    call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
-        None,
-        ENTRY_POINT_BALANCE_COWL,
-        runtime_args! {},
-    );
+    cache_cspr_balance();
+    cache_pool_balance(&pool);
 }
 
 #[no_mangle]
@@ -233,8 +482,21 @@ pub extern "C" fn withdraw_cspr() {
     let amount: U512 = get_named_arg(ARG_AMOUNT);
     validate_amount(amount).unwrap_or_revert();
 
+    let pool = get_pool_arg();
     let (recipient, _) = get_verified_caller();
     let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
+    let cspr_reserve = expected_cspr_reserve(&pool);
+
+    // CSPR raised toward a soft cap is only withdrawable once the cap is met
+    if !keeps_raised_cspr(amount) {
+        revert(SwapError::SoftCapNotReached);
+    }
+    // Nor is the CSPR owned by liquidity providers
+    let provided = get_liquidity_state(&pool).cspr_held().unwrap_or_revert();
+    match cspr_reserve.checked_sub(amount) {
+        Some(remaining) if remaining >= provided => {}
+        _ => revert(SwapError::InsufficientLiquidity),
+    }
 
     transfer_from_purse_to_account(
         *contract_purse
@@ -248,16 +510,18 @@ pub extern "C" fn withdraw_cspr() {
     )
     .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
 
-    record_event_dictionary(Event::WithdrawCspr(WithdrawCspr { recipient, amount }));
+    add_cspr_reserve(&pool, ReserveCounter::Withdrawn, amount);
+
+    record_event_dictionary(Event::WithdrawCspr(WithdrawCspr {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        recipient,
+        amount,
+    }));
 
-    let cowl_swap_contract_package = get_cowl_swap_contract_package();
+    record_cspr_reserve_warning(&pool, cspr_reserve, expected_cspr_reserve(&pool));
 
-    let balance = call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
-        None,
-        ENTRY_POINT_BALANCE_CSPR,
-        runtime_args! {},
-    );
+    let balance = cache_cspr_balance();
 
     ret(CLValue::from_t(balance).unwrap_or_revert());
 }
@@ -269,10 +533,23 @@ pub extern "C" fn withdraw_cowl() {
     let amount: U256 = get_named_arg(ARG_AMOUNT);
     validate_amount(amount).unwrap_or_revert();
 
+    let pool = get_pool_arg();
     let (recipient, _) = get_verified_caller();
+    let cowl_reserve = expected_cowl_reserve(&pool);
+
+    // Tokens locked for buyers or owned by liquidity providers are not the contract's to withdraw
+    match pool.token_balance().checked_sub(amount) {
+        Some(remaining) if remaining >= get_locked_cowl(&pool) => {}
+        _ => revert(SwapError::InsufficientLiquidity),
+    }
+    let provided = get_liquidity_state(&pool).token_held().unwrap_or_revert();
+    match u256_to_u512(cowl_reserve).checked_sub(u256_to_u512(amount)) {
+        Some(remaining) if remaining >= provided => {}
+        _ => revert(SwapError::InsufficientLiquidity),
+    }
 
     call_versioned_contract::<()>(
-        get_cowl_cep18_contract_package(),
+        pool.token_package_hash(),
         None,
         ENTRY_POINT_TRANSFER,
         runtime_args! {
@@ -281,17 +558,70 @@ pub extern "C" fn withdraw_cowl() {
         },
     );
 
-    record_event_dictionary(Event::WithdrawCowl(WithdrawCowl { recipient, amount }));
+    add_cowl_reserve(&pool, ReserveCounter::Withdrawn, amount);
+
+    record_event_dictionary(Event::WithdrawCowl(WithdrawCowl {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        recipient,
+        amount,
+    }));
+
+    record_cowl_reserve_warning(&pool, cowl_reserve, expected_cowl_reserve(&pool));
+
+    let balance = cache_pool_balance(&pool);
 
-    let cowl_swap_contract_package = get_cowl_swap_contract_package();
+    ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+/// Pulls tokens approved to this package into a pool and records them on the depositor's ledger.
+///
+/// Unlike `deposit_cspr`, anyone may deposit: the caller only moves its own approved tokens, which
+/// a plain CEP-18 transfer to the package could already do without being recorded, and the
+/// liquidity account topping up the reserves is not an admin.
+#[no_mangle]
+pub extern "C" fn deposit_cowl() {
+    let amount: U256 = get_named_arg(ARG_AMOUNT);
+    validate_amount(amount).unwrap_or_revert();
+
+    let pool = get_pool_arg();
+    let (owner, _) = get_verified_caller();
+    let recipient = get_key(ARG_PACKAGE_HASH).unwrap_or_revert();
 
-    let balance = call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
+    call_versioned_contract::<()>(
+        pool.token_package_hash(),
         None,
-        ENTRY_POINT_BALANCE_COWL,
-        runtime_args! {},
+        ENTRY_POINT_TRANSFER_FROM,
+        runtime_args! {
+            ARG_OWNER => owner,
+            ARG_RECIPIENT => recipient,
+            ARG_AMOUNT => amount
+        },
     );
 
+    // Deposits into added pools are kept under the pool prefix, next to the default pool ones
+    let owner_item_key = account_item_key(&pool, &owner);
+    let deposited: U256 =
+        get_dictionary_value_from_key(DICT_COWL_DEPOSITS, &owner_item_key).unwrap_or_default();
+    set_dictionary_value_for_key(
+        DICT_COWL_DEPOSITS,
+        &owner_item_key,
+        &deposited
+            .checked_add(amount)
+            .unwrap_or_revert_with(SwapError::Overflow),
+    );
+
+    add_cowl_reserve(&pool, ReserveCounter::Deposited, amount);
+
+    record_event_dictionary(Event::DepositCowl(DepositCowl {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        owner,
+        amount,
+    }));
+
+    let balance = cache_pool_balance(&pool);
+
     ret(CLValue::from_t(balance).unwrap_or_revert());
 }
 
@@ -302,6 +632,7 @@ pub extern "C" fn deposit_cspr() {
     let amount: U512 = get_named_arg(ARG_AMOUNT);
     validate_amount(amount).unwrap_or_revert();
 
+    let pool = get_pool_arg();
     let source_purse: URef = get_named_arg(ARG_PURSE);
     let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
 
@@ -315,135 +646,1365 @@ pub extern "C" fn deposit_cspr() {
     )
     .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
 
+    add_cspr_reserve(&pool, ReserveCounter::Deposited, amount);
+
     record_event_dictionary(Event::DepositCspr(DepositCspr {
+        header: EventHeader::next(),
+        pool: pool.token_package,
         source_purse,
         amount,
     }));
 
-    let cowl_swap_contract_package = get_cowl_swap_contract_package();
+    let balance = cache_cspr_balance();
+
+    ret(CLValue::from_t(balance).unwrap_or_revert());
+}
+
+/// Adds CSPR from `purse` and tokens approved to this package to a pool, against shares of the
+/// liquidity provided by accounts.
+#[no_mangle]
+pub extern "C" fn add_liquidity() {
+    let cspr_amount: U512 = get_named_arg(ARG_AMOUNT);
+    let token_amount: U256 = get_named_arg(ARG_TOKEN_AMOUNT);
+    validate_amount(cspr_amount).unwrap_or_revert();
+    validate_amount(token_amount).unwrap_or_revert();
+
+    let pool = get_pool_arg();
+    let (provider, provider_package) = get_verified_caller();
+    // Withdrawals pay CSPR to the provider's account, which a contract does not have
+    if provider_package.is_some() {
+        revert(SwapError::InvalidLiquidityProvider);
+    }
+
+    let mut state = get_liquidity_state(&pool);
+    let mut position = get_liquidity_position(&pool, &provider);
+    let shares = state
+        .add(&mut position, cspr_amount, u256_to_u512(token_amount))
+        .unwrap_or_revert();
+
+    let source_purse: URef = get_named_arg(ARG_PURSE);
+    let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
+
+    transfer_from_purse_to_purse(
+        source_purse,
+        *contract_purse
+            .as_uref()
+            .unwrap_or_revert_with(SwapError::MissingPurse),
+        cspr_amount,
+        None,
+    )
+    .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
 
-    let balance = call_versioned_contract::<U256>(
-        cowl_swap_contract_package,
+    call_versioned_contract::<()>(
+        pool.token_package_hash(),
         None,
-        ENTRY_POINT_BALANCE_CSPR,
-        runtime_args! {},
+        ENTRY_POINT_TRANSFER_FROM,
+        runtime_args! {
+            ARG_OWNER => provider,
+            ARG_RECIPIENT => get_key(ARG_PACKAGE_HASH).unwrap_or_revert(),
+            ARG_AMOUNT => token_amount
+        },
     );
 
-    ret(CLValue::from_t(balance).unwrap_or_revert());
+    add_cspr_reserve(&pool, ReserveCounter::Deposited, cspr_amount);
+    add_cowl_reserve(&pool, ReserveCounter::Deposited, token_amount);
+    save_liquidity_state(&pool, &state);
+    save_liquidity_position(&pool, &provider, &position);
+
+    record_event_dictionary(Event::AddLiquidity(AddLiquidity {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        provider,
+        cspr_amount,
+        token_amount,
+        shares,
+    }));
+
+    cache_cspr_balance();
+    cache_pool_balance(&pool);
+
+    ret(CLValue::from_t(shares).unwrap_or_revert());
 }
 
+/// Burns shares of the caller and pays back their part of the provided reserves, along with the
+/// tax accrued by the caller's position.
 #[no_mangle]
-pub extern "C" fn update_times() {
-    sec_check(vec![SecurityBadge::Admin]);
+pub extern "C" fn remove_liquidity() {
+    let shares: U512 = get_named_arg(ARG_SHARES);
 
-    let new_start_time: u64 = get_named_arg(ARG_START_TIME);
-    let duration: u64 = get_named_arg(ARG_DURATION);
+    let pool = get_pool_arg();
+    let (provider, _) = get_verified_caller();
 
-    let new_end_time = new_start_time
-        .checked_add(duration)
-        .unwrap_or_revert_with(SwapError::InvalidTimeWindow);
+    let mut state = get_liquidity_state(&pool);
+    let mut position = get_liquidity_position(&pool, &provider);
+    let withdrawal = state.remove(&mut position, shares).unwrap_or_revert();
 
-    if new_end_time <= new_start_time {
-        revert(SwapError::InvalidTimeWindow)
+    let cspr_fees = withdrawal.cspr_fees;
+    let cspr_amount = withdrawal
+        .cspr_amount
+        .checked_add(cspr_fees)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    let token_fees = u512_to_u256(withdrawal.token_fees).unwrap_or_revert();
+    let token_amount = u512_to_u256(withdrawal.token_amount)
+        .unwrap_or_revert()
+        .checked_add(token_fees)
+        .unwrap_or_revert_with(SwapError::Overflow);
+
+    // The provider liquidity is part of the reserves, only CSPR raised by a sale is held back
+    let cspr_reserve = expected_cspr_reserve(&pool);
+    let cowl_reserve = expected_cowl_reserve(&pool);
+    if !keeps_raised_cspr(cspr_amount) {
+        revert(SwapError::InsufficientLiquidity);
     }
-    put_key(ARG_START_TIME, new_uref(new_start_time).into());
-    put_key(ARG_END_TIME, new_uref(new_end_time).into());
-    record_event_dictionary(Event::UpdateTimes(UpdateTimes {
-        new_start_time,
-        new_end_time,
+
+    if !cspr_amount.is_zero() {
+        let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
+        transfer_from_purse_to_account(
+            *contract_purse
+                .as_uref()
+                .unwrap_or_revert_with(SwapError::MissingPurse),
+            provider
+                .into_account()
+                .unwrap_or_revert_with(SwapError::InvalidKey),
+            cspr_amount,
+            None,
+        )
+        .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
+        add_cspr_reserve(&pool, ReserveCounter::Withdrawn, cspr_amount);
+    }
+
+    if !token_amount.is_zero() {
+        call_versioned_contract::<()>(
+            pool.token_package_hash(),
+            None,
+            ENTRY_POINT_TRANSFER,
+            runtime_args! {
+                ARG_RECIPIENT => provider,
+                ARG_AMOUNT => token_amount
+            },
+        );
+        add_cowl_reserve(&pool, ReserveCounter::Withdrawn, token_amount);
+    }
+
+    save_liquidity_state(&pool, &state);
+    save_liquidity_position(&pool, &provider, &position);
+
+    record_event_dictionary(Event::RemoveLiquidity(RemoveLiquidity {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        provider,
+        cspr_amount,
+        token_amount,
+        cspr_fees,
+        token_fees,
+        shares,
     }));
+
+    record_cspr_reserve_warning(&pool, cspr_reserve, expected_cspr_reserve(&pool));
+    record_cowl_reserve_warning(&pool, cowl_reserve, expected_cowl_reserve(&pool));
+
+    cache_cspr_balance();
+    cache_pool_balance(&pool);
 }
 
+/// Shares of `address` in a pool with the CSPR and token tax it can collect.
 #[no_mangle]
-pub extern "C" fn set_cowl_cep18_contract_package() {
-    sec_check(vec![SecurityBadge::Admin]);
+pub extern "C" fn liquidity_position() {
+    let pool = get_pool_arg();
+    let address: Key = get_named_arg(ARG_ADDRESS);
+    let position = get_liquidity_position(&pool, &address);
+    let (cspr_fees, token_fees) = position
+        .pending_fees(&get_liquidity_state(&pool))
+        .unwrap_or_revert();
+    ret(CLValue::from_t((position.shares, cspr_fees, token_fees)).unwrap_or_revert())
+}
 
-    let (caller, _) = get_verified_caller();
+#[no_mangle]
+pub extern "C" fn reserves() {
+    let pool = get_pool_arg();
+    ret(CLValue::from_t((
+        cspr_reserves_breakdown(&pool),
+        cowl_reserves_breakdown(&pool),
+    ))
+    .unwrap_or_revert())
+}
 
-    let cowl_cep18_contract_package_key: Key = get_named_arg(ARG_COWL_CEP18_CONTRACT_PACKAGE);
+#[no_mangle]
+pub extern "C" fn swap_stats() {
+    let address: Key = get_named_arg(ARG_ADDRESS);
+    let stats =
+        match get_optional_named_arg_with_user_errors::<Key>(ARG_POOL, SwapError::InvalidPool) {
+            Some(token_package) => get_pool_swap_stats(
+                &get_pool(&token_package).unwrap_or_revert_with(SwapError::MissingPool),
+                &address,
+            ),
+            None => get_swap_stats(&address),
+        };
+    ret(CLValue::from_t(stats).unwrap_or_revert())
+}
 
-    let cowl_cep18_contract_package_key_hash = ContractPackageHash::from(
-        cowl_cep18_contract_package_key
-            .into_hash()
-            .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
-    );
+#[no_mangle]
+pub extern "C" fn swap_count() {
+    ret(CLValue::from_t(get_swap_count()).unwrap_or_revert())
+}
 
-    put_key(
-        ARG_COWL_CEP18_CONTRACT_PACKAGE,
-        new_uref(cowl_cep18_contract_package_key_hash).into(),
+#[no_mangle]
+pub extern "C" fn token_symbol() {
+    let token_symbol = get_stored_value_with_user_errors::<String>(
+        ARG_TOKEN_SYMBOL,
+        SwapError::InvalidTokenSymbol,
+        SwapError::InvalidTokenSymbol,
     );
-
-    record_event_dictionary(Event::CowlCep18ContractPackageUpdate(
-        CowlCep18ContractPackageUpdate {
-            key: caller,
-            cowl_cep18_contract_package_key,
-        },
-    ));
+    ret(CLValue::from_t(token_symbol).unwrap_or_revert())
 }
 
+/// Opens a pool for another token next to the default one, with its own tiers, tax, window and
+/// reserve limits.
 #[no_mangle]
-pub extern "C" fn set_modalities() {
-    // Only the installing account can change the mutable variables.
+pub extern "C" fn add_pool() {
     sec_check(vec![SecurityBadge::Admin]);
 
-    if let Some(optional_events_mode) =
-        get_optional_named_arg_with_user_errors::<u8>(ARG_EVENTS_MODE, SwapError::InvalidEventsMode)
-    {
-        let old_events_mode: EventsMode = get_stored_value_with_user_errors::<u8>(
-            ARG_EVENTS_MODE,
-            SwapError::MissingEventsMode,
-            SwapError::InvalidEventsMode,
-        )
-        .try_into()
-        .unwrap_or_revert();
+    let token_package: Key =
+        get_named_arg_with_user_errors(ARG_POOL, SwapError::MissingPool, SwapError::InvalidPool)
+            .unwrap_or_revert();
+    if token_package.into_hash().is_none() {
+        revert(SwapError::InvalidTokenContractPackage);
+    }
 
-        put_key(ARG_EVENTS_MODE, new_uref(optional_events_mode).into());
+    let token_decimals: u8 = get_named_arg_with_user_errors(
+        ARG_TOKEN_DECIMALS,
+        SwapError::MissingTokenDecimals,
+        SwapError::InvalidTokenDecimals,
+    )
+    .unwrap_or_revert();
+    if token_decimals > MAX_TOKEN_DECIMALS {
+        revert(SwapError::InvalidTokenDecimals);
+    }
 
-        let new_events_mode: EventsMode = optional_events_mode
-            .try_into()
-            .unwrap_or_revert_with(SwapError::InvalidEventsMode);
+    let tax_rate: U512 =
+        get_optional_named_arg_with_user_errors(ARG_TAX_RATE, SwapError::InvalidTaxRate)
+            .unwrap_or(TAX_RATE);
+    validate_tax_rate(tax_rate).unwrap_or_revert();
 
-        // Check if current_events_mode and requested_events_mode are both CES
-        if old_events_mode != EventsMode::CES && new_events_mode == EventsMode::CES {
-            // Initialize events structures
-            init_events();
-        }
+    let start_time: u64 = get_named_arg_with_user_errors(
+        ARG_START_TIME,
+        SwapError::MissingStartTime,
+        SwapError::InvalidStartTime,
+    )
+    .unwrap_or_revert();
+    let duration: u64 = get_named_arg_with_user_errors(
+        ARG_DURATION,
+        SwapError::MissingEndTime,
+        SwapError::InvalidTimeWindow,
+    )
+    .unwrap_or_revert();
+    let end_time = start_time
+        .checked_add(duration)
+        .unwrap_or_revert_with(SwapError::InvalidTimeWindow);
+    if end_time <= start_time {
+        revert(SwapError::InvalidTimeWindow);
+    }
+
+    let pool = insert_pool(Pool {
+        token_package,
+        token_decimals,
+        rate_tiers: get_rate_tiers_arg().unwrap_or_else(|| RATE_TIERS.to_vec()),
+        tax_rate,
+        start_time,
+        end_time,
+        paused: false,
+        reserve_prefix: String::new(),
+    });
+
+    let reserve_limits = get_reserve_limits_args();
+    if reserve_limits != (None, None, None, None) {
+        update_reserve_limits(&pool, reserve_limits);
     }
 
-    record_event_dictionary(Event::SetModalities(SetModalities {}));
+    record_pool_update(&pool);
 }
 
-/// Beware: do not remove the last Admin because that will lock out all admin functionality.
+/// Changes the tiers, tax or pause flag of a pool, the window goes through `update_times`.
 #[no_mangle]
-pub extern "C" fn change_security() {
+pub extern "C" fn update_pool() {
     sec_check(vec![SecurityBadge::Admin]);
 
-    let admin_list: Option<Vec<Key>> =
-        get_optional_named_arg_with_user_errors(ADMIN_LIST, SwapError::InvalidAdminList);
-    let none_list: Option<Vec<Key>> =
-        get_optional_named_arg_with_user_errors(NONE_LIST, SwapError::InvalidNoneList);
-
-    let mut badge_map: BTreeMap<Key, SecurityBadge> = BTreeMap::new();
+    let mut pool = get_pool_arg();
 
-    if let Some(admin_list) = admin_list {
-        for account_key in admin_list {
-            badge_map.insert(account_key, SecurityBadge::Admin);
-        }
+    if let Some(rate_tiers) = get_rate_tiers_arg() {
+        pool.rate_tiers = rate_tiers;
     }
-    if let Some(none_list) = none_list {
-        for account_key in none_list {
-            badge_map.insert(account_key, SecurityBadge::None);
-        }
+    if let Some(tax_rate) =
+        get_optional_named_arg_with_user_errors(ARG_TAX_RATE, SwapError::InvalidTaxRate)
+    {
+        validate_tax_rate(tax_rate).unwrap_or_revert();
+        pool.tax_rate = tax_rate;
+    }
+    if let Some(paused) =
+        get_optional_named_arg_with_user_errors(ARG_PAUSED, SwapError::InvalidPaused)
+    {
+        pool.paused = paused;
     }
 
-    let (caller, _) = get_verified_caller();
-    badge_map.remove(&caller);
+    save_pool(&pool);
+    record_pool_update(&pool);
+}
 
-    change_sec_badge(&badge_map);
-    record_event_dictionary(Event::ChangeSecurity(ChangeSecurity {
-        admin: caller,
-        sec_change_map: badge_map,
-    }));
+#[no_mangle]
+pub extern "C" fn pool() {
+    let token_package: Key = get_named_arg(ARG_POOL);
+    ret(CLValue::from_t(get_pool(&token_package)).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn pool_tokens() {
+    ret(CLValue::from_t(get_pool_tokens()).unwrap_or_revert())
+}
+
+/// Switches how a pool prices swaps: rate tiers, a constant-product curve over its reserves, the
+/// oracle price or its bonding curve.
+#[no_mangle]
+pub extern "C" fn set_pricing_mode() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+    let pricing_mode: u8 = get_named_arg_with_user_errors(
+        ARG_PRICING_MODE,
+        SwapError::MissingPricingMode,
+        SwapError::InvalidPricingMode,
+    )
+    .unwrap_or_revert();
+
+    save_pricing_mode(
+        &pool,
+        PricingMode::try_from(pricing_mode).unwrap_or_revert(),
+    );
+
+    record_event_dictionary(Event::PricingModeUpdate(PricingModeUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        pricing_mode,
+    }));
+}
+
+#[no_mangle]
+pub extern "C" fn pricing_mode() {
+    let pool = get_pool_arg();
+    ret(CLValue::from_t(get_pricing_mode(&pool) as u8).unwrap_or_revert())
+}
+
+/// Tokens paid out and tokens kept as fee for `amount` motes at the current pool state.
+#[no_mangle]
+pub extern "C" fn quote_cspr_to_token() {
+    let pool = get_pool_arg();
+    let cspr_amount: U512 = get_named_arg(ARG_AMOUNT);
+    let quote = get_cspr_to_token_quote(&pool, cspr_amount).unwrap_or_revert();
+    ret(CLValue::from_t((quote.output_amount, quote.fee_amount)).unwrap_or_revert())
+}
+
+/// Motes paid out and motes kept as tax for `amount` tokens at the current pool state.
+#[no_mangle]
+pub extern "C" fn quote_token_to_cspr() {
+    let pool = get_pool_arg();
+    let token_amount: U256 = get_named_arg(ARG_AMOUNT);
+    let token_amount = u256_to_u512(token_amount);
+    let quote = get_token_to_cspr_quote(&pool, token_amount).unwrap_or_revert();
+    ret(CLValue::from_t((quote.output_amount, quote.fee_amount)).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn set_oracle_config() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+    let max_age: u64 = get_named_arg_with_user_errors(
+        ARG_MAX_AGE,
+        SwapError::MissingOracleConfig,
+        SwapError::InvalidOracleConfig,
+    )
+    .unwrap_or_revert();
+    let max_deviation: u32 = get_named_arg_with_user_errors(
+        ARG_MAX_DEVIATION,
+        SwapError::MissingOracleConfig,
+        SwapError::InvalidOracleConfig,
+    )
+    .unwrap_or_revert();
+    let spread: u32 = get_named_arg_with_user_errors(
+        ARG_SPREAD,
+        SwapError::MissingOracleConfig,
+        SwapError::InvalidOracleConfig,
+    )
+    .unwrap_or_revert();
+
+    let mut feed = get_oracle_feed(&pool);
+    feed.configure(max_age, max_deviation, spread)
+        .unwrap_or_revert();
+    save_oracle_feed(&pool, &feed);
+
+    record_event_dictionary(Event::OracleConfigUpdate(OracleConfigUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        max_age,
+        max_deviation,
+        spread,
+    }));
+}
+
+/// Records a reference price of the pool, in whole tokens per CSPR scaled by
+/// `ORACLE_PRICE_PRECISION`, observed at `timestamp` seconds.
+#[no_mangle]
+pub extern "C" fn update_price() {
+    sec_check(vec![SecurityBadge::Admin, SecurityBadge::OracleUpdater]);
+
+    let pool = get_pool_arg();
+    let price: U512 = get_named_arg_with_user_errors(
+        ARG_PRICE,
+        SwapError::MissingOraclePrice,
+        SwapError::InvalidOraclePrice,
+    )
+    .unwrap_or_revert();
+    let timestamp: u64 = get_named_arg_with_user_errors(
+        ARG_TIMESTAMP,
+        SwapError::MissingOraclePrice,
+        SwapError::InvalidOracleTimestamp,
+    )
+    .unwrap_or_revert();
+
+    let mut feed = get_oracle_feed(&pool);
+    feed.update_price(price, timestamp, get_current_time_in_seconds())
+        .unwrap_or_revert();
+    save_oracle_feed(&pool, &feed);
+
+    let (updater, _) = get_verified_caller();
+    record_event_dictionary(Event::OraclePriceUpdate(OraclePriceUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        updater,
+        price,
+        timestamp,
+    }));
+}
+
+#[no_mangle]
+pub extern "C" fn set_referral_bonus() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let referral_bonus: u32 = get_named_arg_with_user_errors(
+        ARG_REFERRAL_BONUS,
+        SwapError::MissingReferralBonus,
+        SwapError::InvalidReferralBonus,
+    )
+    .unwrap_or_revert();
+    validate_referral_bonus(referral_bonus).unwrap_or_revert();
+    set_stored_value(ARG_REFERRAL_BONUS, referral_bonus);
+
+    record_event_dictionary(Event::ReferralBonusUpdate(ReferralBonusUpdate {
+        header: EventHeader::next(),
+        referral_bonus,
+    }));
+}
+
+/// Tokens of the pool earned by `address` as a referrer.
+#[no_mangle]
+pub extern "C" fn referral_rewards() {
+    let pool = get_pool_arg();
+    let referrer: Key = get_named_arg(ARG_ADDRESS);
+    ret(CLValue::from_t(get_referral_total(&pool, &referrer)).unwrap_or_revert())
+}
+
+/// Sets the schedule purchases of the pool vest with, or transfers them right away again when
+/// `enabled` is false. The schedule applies to allocations already bought, and disabling it
+/// releases them in full. With `phase`, sets or removes the schedule of the sale phase starting
+/// then and ending at `phase_end_time` instead.
+#[no_mangle]
+pub extern "C" fn set_vesting_schedule() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+    let enabled: bool =
+        get_optional_named_arg_with_user_errors(ARG_ENABLED, SwapError::InvalidVestingSchedule)
+            .unwrap_or(true);
+    let phase: Option<u64> =
+        get_optional_named_arg_with_user_errors(ARG_PHASE, SwapError::InvalidVestingSchedule);
+
+    let schedule = if enabled {
+        let schedule = VestingSchedule {
+            start_time: get_named_arg_with_user_errors(
+                ARG_VESTING_START_TIME,
+                SwapError::MissingVestingSchedule,
+                SwapError::InvalidVestingSchedule,
+            )
+            .unwrap_or_revert(),
+            cliff_duration: get_named_arg_with_user_errors(
+                ARG_CLIFF_DURATION,
+                SwapError::MissingVestingSchedule,
+                SwapError::InvalidVestingSchedule,
+            )
+            .unwrap_or_revert(),
+            vesting_duration: get_named_arg_with_user_errors(
+                ARG_VESTING_DURATION,
+                SwapError::MissingVestingSchedule,
+                SwapError::InvalidVestingSchedule,
+            )
+            .unwrap_or_revert(),
+        };
+        schedule.validate().unwrap_or_revert();
+        Some(schedule)
+    } else {
+        None
+    };
+
+    match phase {
+        Some(start_time) => {
+            let mut phases = get_vesting_phases(&pool);
+            match schedule {
+                Some(schedule) => set_vesting_phase(
+                    &mut phases,
+                    VestingPhase {
+                        start_time,
+                        end_time: get_named_arg_with_user_errors(
+                            ARG_PHASE_END_TIME,
+                            SwapError::MissingVestingSchedule,
+                            SwapError::InvalidVestingSchedule,
+                        )
+                        .unwrap_or_revert(),
+                        schedule,
+                    },
+                )
+                .unwrap_or_revert(),
+                None => {
+                    let count = phases.len();
+                    phases.retain(|phase| phase.start_time != start_time);
+                    if phases.len() == count {
+                        revert(SwapError::MissingVestingSchedule);
+                    }
+                }
+            }
+            save_vesting_phases(&pool, phases);
+        }
+        None => save_vesting_schedule(&pool, schedule),
+    }
+
+    let schedule = schedule.unwrap_or_default();
+    record_event_dictionary(Event::VestingScheduleUpdate(VestingScheduleUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        phase,
+        enabled,
+        start_time: schedule.start_time,
+        cliff_duration: schedule.cliff_duration,
+        vesting_duration: schedule.vesting_duration,
+    }));
+}
+
+/// Transfers `amount` tokens of the pool released to the caller, everything released so far by
+/// default, and returns the amount claimed. Tokens bought during a sale phase are claimed with
+/// its `phase`.
+#[no_mangle]
+pub extern "C" fn claim() {
+    let pool = get_pool_arg();
+    let (beneficiary, _) = get_verified_caller();
+    let phase: Option<u64> =
+        get_optional_named_arg_with_user_errors(ARG_PHASE, SwapError::InvalidVestingSchedule);
+
+    if matches!(
+        get_settled_sale(&pool).state,
+        SaleState::Raising | SaleState::Failed
+    ) {
+        revert(SwapError::SoftCapNotReached);
+    }
+
+    let mut allocation = get_vesting_allocation(&pool, &beneficiary, phase);
+    let releasable = allocation
+        .claimable(
+            get_phase_schedule(&pool, phase).as_ref(),
+            get_claim_start_time(&pool),
+            get_current_time_in_seconds(),
+        )
+        .unwrap_or_revert();
+    if releasable.is_zero() {
+        revert(SwapError::NothingToClaim);
+    }
+
+    let amount: U256 =
+        get_optional_named_arg_with_user_errors(ARG_AMOUNT, SwapError::InvalidAmount)
+            .unwrap_or(releasable);
+    validate_amount(amount).unwrap_or_revert();
+    if amount > releasable {
+        revert(SwapError::InsufficientClaimable);
+    }
+
+    allocation.claimed = allocation
+        .claimed
+        .checked_add(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    save_vesting_allocation(&pool, &beneficiary, phase, &allocation);
+    unlock_cowl(&pool, amount);
+
+    call_versioned_contract::<()>(
+        pool.token_package_hash(),
+        None,
+        ENTRY_POINT_TRANSFER,
+        runtime_args! {
+            ARG_RECIPIENT => beneficiary,
+            ARG_AMOUNT => amount
+        },
+    );
+
+    record_event_dictionary(Event::TokensClaimed(TokensClaimed {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        phase,
+        beneficiary,
+        amount,
+    }));
+
+    cache_pool_balance(&pool);
+
+    ret(CLValue::from_t(amount).unwrap_or_revert());
+}
+
+/// Switches the pool to claim-based delivery: purchases are credited to the buyers, who claim
+/// them from `claim_start_time`, the end of the sale window by default. Setting it again moves
+/// the claim start of the tokens already bought. Allocations still vest with their schedule, the
+/// claim start only holding them until then.
+#[no_mangle]
+pub extern "C" fn set_claim_start_time() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+    let claim_start_time: u64 = get_optional_named_arg_with_user_errors(
+        ARG_CLAIM_START_TIME,
+        SwapError::InvalidClaimStartTime,
+    )
+    .unwrap_or(pool.end_time);
+
+    save_claim_start_time(&pool, claim_start_time);
+
+    record_event_dictionary(Event::ClaimStartTimeUpdate(ClaimStartTimeUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        claim_start_time,
+    }));
+}
+
+/// Sets the soft cap of the pool's sale, in motes. From then on the CSPR paid for its tokens is
+/// held for refunds and the tokens credited to the buyers until the cap is met, purchases with a
+/// referrer being refused meanwhile.
+#[no_mangle]
+pub extern "C" fn set_soft_cap() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+    let soft_cap: U512 = get_named_arg_with_user_errors(
+        ARG_SOFT_CAP,
+        SwapError::MissingSoftCap,
+        SwapError::InvalidSoftCap,
+    )
+    .unwrap_or_revert();
+
+    let mut sale = get_settled_sale(&pool);
+    sale.set_soft_cap(soft_cap).unwrap_or_revert();
+    save_sale(&pool, &sale);
+
+    record_sale_state(&pool, &sale);
+}
+
+/// Pays back the CSPR the caller paid while the pool raised, once its sale failed, and returns
+/// the tokens credited for it to the pool, those bought on its bonding curve going back on the
+/// curve. Returns the CSPR refunded.
+#[no_mangle]
+pub extern "C" fn refund() {
+    let pool = get_pool_arg();
+    let (buyer, _) = get_verified_caller();
+
+    let mut sale = get_settled_sale(&pool);
+    if sale.state != SaleState::Failed {
+        revert(SwapError::RefundsNotOpen);
+    }
+
+    let (cspr_amount, token_amount, curve_amount) = get_contribution(&pool, &buyer);
+    if cspr_amount.is_zero() {
+        revert(SwapError::NothingToRefund);
+    }
+    save_contribution(&pool, &buyer, Default::default());
+    sale.refunded = sale
+        .refunded
+        .checked_add(cspr_amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    save_sale(&pool, &sale);
+
+    let mut allocation = get_vesting_allocation(&pool, &buyer, None);
+    allocation.total = allocation
+        .total
+        .checked_sub(token_amount)
+        .filter(|total| *total >= allocation.claimed)
+        .unwrap_or_revert_with(SwapError::InsufficientClaimable);
+    save_vesting_allocation(&pool, &buyer, None, &allocation);
+    unlock_cowl(&pool, token_amount);
+    add_cowl_reserve(&pool, ReserveCounter::SwappedIn, token_amount);
+    if !curve_amount.is_zero() && get_bonding_curve(&pool).is_some() {
+        record_curve_sale(&pool, u256_to_u512(curve_amount));
+    }
+
+    let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
+    transfer_from_purse_to_account(
+        *contract_purse
+            .as_uref()
+            .unwrap_or_revert_with(SwapError::MissingPurse),
+        buyer
+            .into_account()
+            .unwrap_or_revert_with(SwapError::InvalidKey),
+        cspr_amount,
+        None,
+    )
+    .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
+    add_cspr_reserve(&pool, ReserveCounter::SwappedOut, cspr_amount);
+
+    record_event_dictionary(Event::Refund(Refund {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        buyer,
+        cspr_amount,
+        token_amount,
+    }));
+
+    cache_cspr_balance();
+
+    ret(CLValue::from_t(cspr_amount).unwrap_or_revert());
+}
+
+/// State of the pool's sale, its soft cap and the CSPR raised toward it.
+#[no_mangle]
+pub extern "C" fn sale() {
+    let pool = get_pool_arg();
+    let mut sale = get_sale(&pool);
+    sale.settle(get_current_time_in_seconds(), pool.end_time);
+    ret(CLValue::from_t((sale.state as u8, sale.soft_cap, sale.raised)).unwrap_or_revert())
+}
+
+/// Tokens of the pool `address` can claim now, from the allocation of `phase` if given.
+#[no_mangle]
+pub extern "C" fn claimable_of() {
+    let pool = get_pool_arg();
+    let beneficiary: Key = get_named_arg(ARG_ADDRESS);
+    let phase: Option<u64> =
+        get_optional_named_arg_with_user_errors(ARG_PHASE, SwapError::InvalidVestingSchedule);
+    let claimable = get_vesting_allocation(&pool, &beneficiary, phase)
+        .claimable(
+            get_phase_schedule(&pool, phase).as_ref(),
+            get_claim_start_time(&pool),
+            get_current_time_in_seconds(),
+        )
+        .unwrap_or_revert();
+    ret(CLValue::from_t(claimable).unwrap_or_revert())
+}
+
+/// Tokens of the pool bought by `address` while it vests, or during `phase` if given, the part
+/// claimed and the part claimable now.
+#[no_mangle]
+pub extern "C" fn vesting_allocation() {
+    let pool = get_pool_arg();
+    let beneficiary: Key = get_named_arg(ARG_ADDRESS);
+    let phase: Option<u64> =
+        get_optional_named_arg_with_user_errors(ARG_PHASE, SwapError::InvalidVestingSchedule);
+    let allocation = get_vesting_allocation(&pool, &beneficiary, phase);
+    let releasable = allocation
+        .claimable(
+            get_phase_schedule(&pool, phase).as_ref(),
+            get_claim_start_time(&pool),
+            get_current_time_in_seconds(),
+        )
+        .unwrap_or_revert();
+    ret(CLValue::from_t((allocation.total, allocation.claimed, releasable)).unwrap_or_revert())
+}
+
+/// Replaces the steps of the bonding curve of the pool, tokens already sold on it staying sold so
+/// the new steps continue from the current point.
+#[no_mangle]
+pub extern "C" fn set_bonding_curve() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+    let curve_steps: Vec<(U512, U512)> = get_named_arg_with_user_errors(
+        ARG_CURVE_STEPS,
+        SwapError::MissingBondingCurve,
+        SwapError::InvalidBondingCurve,
+    )
+    .unwrap_or_revert();
+    let steps: Vec<CurveStep> = curve_steps
+        .iter()
+        .map(|&(sold_until, rate)| CurveStep { sold_until, rate })
+        .collect();
+    validate_curve_steps(&steps).unwrap_or_revert();
+
+    let sold = get_bonding_curve(&pool)
+        .map(|curve| curve.sold)
+        .unwrap_or_default();
+    save_bonding_curve(&pool, &BondingCurve { steps, sold });
+
+    record_event_dictionary(Event::BondingCurveUpdate(BondingCurveUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        curve_steps,
+        sold,
+    }));
+}
+
+/// Tokens sold on the bonding curve of the pool and the rate of the next one, zero once sold out.
+#[no_mangle]
+pub extern "C" fn bonding_curve() {
+    let pool = get_pool_arg();
+    let curve = get_bonding_curve(&pool).unwrap_or_revert_with(SwapError::MissingBondingCurve);
+    ret(CLValue::from_t((curve.sold, curve.current_rate().unwrap_or_default())).unwrap_or_revert())
+}
+
+/// Last price of the pool, its timestamp and whether it is fresh enough to price swaps.
+#[no_mangle]
+pub extern "C" fn oracle_feed() {
+    let pool = get_pool_arg();
+    let feed = get_oracle_feed(&pool);
+    let fresh = feed.fresh_price(get_current_time_in_seconds()).is_some();
+    ret(CLValue::from_t((feed.price, feed.timestamp, fresh)).unwrap_or_revert())
+}
+
+/// Optional `(cspr_amount, rate)` tiers argument, validated.
+fn get_rate_tiers_arg() -> Option<Vec<RateTier>> {
+    let rate_tiers: Vec<RateTier> = get_optional_named_arg_with_user_errors::<Vec<(U512, U512)>>(
+        ARG_RATE_TIERS,
+        SwapError::InvalidRateTiers,
+    )?
+    .into_iter()
+    .map(|(cspr_amount, rate)| RateTier { cspr_amount, rate })
+    .collect();
+    validate_rate_tiers(&rate_tiers).unwrap_or_revert();
+    Some(rate_tiers)
+}
+
+fn record_pool_update(pool: &Pool) {
+    record_event_dictionary(Event::PoolUpdate(PoolUpdate::new(
+        EventHeader::next(),
+        pool.token_package,
+        pool.token_decimals,
+        pool.rate_tiers
+            .iter()
+            .map(|tier| (tier.cspr_amount, tier.rate))
+            .collect(),
+        pool.tax_rate,
+        pool.start_time,
+        pool.end_time,
+        pool.paused,
+    )));
+}
+
+/// Refreshes the cached token balance for the default pool, whose token is the one cached, and
+/// returns the pool's token balance.
+fn cache_pool_balance(pool: &Pool) -> U256 {
+    if pool.is_default() {
+        cache_cowl_balance()
+    } else {
+        pool.token_balance()
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn set_token_pair() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let input_package: Key = get_named_arg_with_user_errors(
+        ARG_INPUT_TOKEN_PACKAGE,
+        SwapError::MissingTokenContractPackage,
+        SwapError::InvalidTokenContractPackage,
+    )
+    .unwrap_or_revert();
+    let output_package: Key = get_named_arg_with_user_errors(
+        ARG_OUTPUT_TOKEN_PACKAGE,
+        SwapError::MissingTokenContractPackage,
+        SwapError::InvalidTokenContractPackage,
+    )
+    .unwrap_or_revert();
+    let input_decimals: u8 = get_named_arg_with_user_errors(
+        ARG_INPUT_DECIMALS,
+        SwapError::MissingTokenDecimals,
+        SwapError::InvalidTokenDecimals,
+    )
+    .unwrap_or_revert();
+    let output_decimals: u8 = get_named_arg_with_user_errors(
+        ARG_OUTPUT_DECIMALS,
+        SwapError::MissingTokenDecimals,
+        SwapError::InvalidTokenDecimals,
+    )
+    .unwrap_or_revert();
+    let rate: U512 = get_named_arg_with_user_errors(
+        ARG_PAIR_RATE,
+        SwapError::InvalidRate,
+        SwapError::InvalidRate,
+    )
+    .unwrap_or_revert();
+    let min_input_amount: U512 = get_named_arg_with_user_errors(
+        ARG_MIN_INPUT_AMOUNT,
+        SwapError::InvalidAmount,
+        SwapError::InvalidAmount,
+    )
+    .unwrap_or_revert();
+    let enabled: bool =
+        get_optional_named_arg_with_user_errors(ARG_ENABLED, SwapError::InvalidTokenPair)
+            .unwrap_or(true);
+
+    if input_package.into_hash().is_none() || output_package.into_hash().is_none() {
+        revert(SwapError::InvalidTokenContractPackage);
+    }
+    if input_package == output_package {
+        revert(SwapError::InvalidTokenPair);
+    }
+    if input_decimals > MAX_TOKEN_DECIMALS || output_decimals > MAX_TOKEN_DECIMALS {
+        revert(SwapError::InvalidTokenDecimals);
+    }
+    if rate.is_zero() {
+        revert(SwapError::InvalidRate);
+    }
+    // The tax stays with the output token, only a pool accounts for it
+    if get_pool(&output_package).is_none() {
+        revert(SwapError::MissingPool);
+    }
+
+    save_token_pair(&TokenPair {
+        input_package,
+        output_package,
+        input_decimals,
+        output_decimals,
+        rate,
+        min_input_amount,
+        enabled,
+    });
+
+    record_event_dictionary(Event::TokenPairUpdate(TokenPairUpdate::new(
+        EventHeader::next(),
+        input_package,
+        output_package,
+        input_decimals,
+        output_decimals,
+        rate,
+        min_input_amount,
+        enabled,
+    )));
+}
+
+#[no_mangle]
+pub extern "C" fn token_pair() {
+    let input_package: Key = get_named_arg(ARG_INPUT_TOKEN_PACKAGE);
+    let output_package: Key = get_named_arg(ARG_OUTPUT_TOKEN_PACKAGE);
+    ret(CLValue::from_t(get_token_pair(&input_package, &output_package)).unwrap_or_revert())
+}
+
+/// Swaps `amount` of the input token for the output token of a configured pair. The input is
+/// taken with `transfer_from`, so the caller must have approved this package beforehand.
+#[no_mangle]
+pub extern "C" fn token_to_token() {
+    let input_package: Key = get_named_arg(ARG_INPUT_TOKEN_PACKAGE);
+    let output_package: Key = get_named_arg(ARG_OUTPUT_TOKEN_PACKAGE);
+    let input_amount: U256 = get_named_arg(ARG_AMOUNT);
+
+    // Pairs follow the window of the default pool, the pools on both sides must be open as well
+    let input_pool = get_pool(&input_package);
+    let output_pool = get_pool(&output_package).unwrap_or_revert_with(SwapError::MissingPool);
+    verify_swap_active(&get_default_pool()).unwrap_or_revert();
+    for pool in input_pool.iter().chain(Some(&output_pool)) {
+        verify_swap_active(pool).unwrap_or_revert();
+    }
+
+    validate_amount(input_amount).unwrap_or_revert();
+
+    let pair = get_token_pair(&input_package, &output_package)
+        .unwrap_or_revert_with(SwapError::MissingTokenPair);
+    if !pair.enabled {
+        revert(SwapError::TokenPairDisabled);
+    }
+
+    let input_amount_u512 = u256_to_u512(input_amount);
+    let quote = pair.quote(input_amount_u512).unwrap_or_revert();
+
+    validate_amount(quote.output_amount).unwrap_or_revert();
+
+    let output_amount = u512_to_u256(quote.output_amount).unwrap_or_revert();
+    let tax_amount = u512_to_u256(quote.tax_amount).unwrap_or_revert();
+
+    let input_package_hash = ContractPackageHash::from(
+        input_package
+            .into_hash()
+            .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
+    );
+    let output_package_hash = ContractPackageHash::from(
+        output_package
+            .into_hash()
+            .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
+    );
+
+    // The output token always has a pool, whose reserves account for the payout and the tax.
+    // Liquidity providers do not take part in pair swaps, so the payout leaves their tokens alone.
+    let contract_package = get_key(ARG_PACKAGE_HASH).unwrap_or_revert();
+    let output_reserve = expected_cowl_reserve(&output_pool);
+    verify_cowl_liquidity(&output_pool, output_reserve, output_amount).unwrap_or_revert();
+    let provided = get_liquidity_state(&output_pool)
+        .token_held()
+        .unwrap_or_revert();
+    match u256_to_u512(output_reserve).checked_sub(quote.output_amount) {
+        Some(remaining) if remaining >= provided => {}
+        _ => revert(SwapError::InsufficientLiquidity),
+    }
+
+    let (owner, _) = get_verified_caller();
+
+    call_versioned_contract::<()>(
+        input_package_hash,
+        None,
+        ENTRY_POINT_TRANSFER_FROM,
+        runtime_args! {
+            ARG_OWNER => owner,
+            ARG_RECIPIENT => contract_package,
+            ARG_AMOUNT => input_amount
+        },
+    );
+
+    call_versioned_contract::<()>(
+        output_package_hash,
+        None,
+        ENTRY_POINT_TRANSFER,
+        runtime_args! {
+            ARG_RECIPIENT => owner,
+            ARG_AMOUNT => output_amount
+        },
+    );
+
+    if let Some(pool) = &input_pool {
+        add_cowl_reserve(pool, ReserveCounter::SwappedIn, input_amount);
+    }
+    add_cowl_reserve(&output_pool, ReserveCounter::SwappedOut, output_amount);
+    if !tax_amount.is_zero() {
+        add_cowl_reserve(&output_pool, ReserveCounter::Fees, tax_amount);
+    }
+
+    record_event_dictionary(Event::TokenToToken(TokenToToken::new(
+        EventHeader::next(),
+        owner,
+        input_package,
+        output_package,
+        input_amount,
+        output_amount,
+        pair.rate,
+        tax_amount,
+    )));
+
+    record_cowl_reserve_warning(
+        &output_pool,
+        output_reserve,
+        expected_cowl_reserve(&output_pool),
+    );
+    for pool in input_pool.iter().chain(Some(&output_pool)) {
+        cache_pool_balance(pool);
+    }
+
+    ret(CLValue::from_t(output_amount).unwrap_or_revert())
+}
+
+/// Compares the accounted reserves with the actual balances and optionally sweeps any surplus
+/// (stray transfers, rounding leftovers) to the calling admin. The purse is shared, so CSPR is
+/// compared with the reserves of all pools, its sweeps being counted package-wide, while the
+/// token side is the one of the given pool, tokens locked for buyers being expected on top of its
+/// reserve.
+#[no_mangle]
+pub extern "C" fn reconcile() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    let pool = get_pool_arg();
+
+    let sweep: bool =
+        get_optional_named_arg_with_user_errors(ARG_SWEEP, SwapError::InvalidSweepFlag)
+            .unwrap_or_default();
+
+    let contract_purse = *get_key(ARG_PURSE)
+        .unwrap_or_revert_with(SwapError::MissingPurse)
+        .as_uref()
+        .unwrap_or_revert_with(SwapError::MissingPurse);
+
+    let cspr_expected = all_pools()
+        .iter()
+        .map(expected_cspr_reserve)
+        .fold(U512::zero(), |total, reserve| total.saturating_add(reserve));
+    let cspr_actual = get_contract_cspr_balance();
+    let cowl_expected = expected_cowl_reserve(&pool).saturating_add(get_locked_cowl(&pool));
+    let cowl_actual = pool.token_balance();
+
+    let cspr_surplus = cspr_actual.saturating_sub(cspr_expected);
+    let cowl_surplus = cowl_actual.saturating_sub(cowl_expected);
+
+    if sweep {
+        let (recipient, _) = get_verified_caller();
+
+        if !cspr_surplus.is_zero() {
+            transfer_from_purse_to_account(
+                contract_purse,
+                recipient
+                    .into_account()
+                    .unwrap_or_revert_with(SwapError::InvalidKey),
+                cspr_surplus,
+                None,
+            )
+            .unwrap_or_revert_with(SwapError::InvalidPurseTransfer);
+            add_package_cspr_reserve(ReserveCounter::Swept, cspr_surplus);
+        }
+
+        if !cowl_surplus.is_zero() {
+            call_versioned_contract::<()>(
+                pool.token_package_hash(),
+                None,
+                ENTRY_POINT_TRANSFER,
+                runtime_args! {
+                    ARG_RECIPIENT => recipient,
+                    ARG_AMOUNT => cowl_surplus
+                },
+            );
+            add_cowl_reserve(&pool, ReserveCounter::Swept, cowl_surplus);
+        }
+    }
+
+    record_event_dictionary(Event::Reconcile(Reconcile {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        cspr_expected,
+        cspr_actual,
+        cowl_expected,
+        cowl_actual,
+        swept: sweep,
+    }));
+
+    ret(CLValue::from_t((cspr_surplus, cowl_surplus)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn set_reserve_limits() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    update_reserve_limits(&get_pool_arg(), get_reserve_limits_args());
+}
+
+/// Optional reserve limit arguments, in the order min CSPR, min COWL, warning CSPR, warning COWL.
+type ReserveLimitsArgs = (Option<U512>, Option<U256>, Option<U512>, Option<U256>);
+
+fn get_reserve_limits_args() -> ReserveLimitsArgs {
+    (
+        get_optional_named_arg_with_user_errors(
+            ARG_MIN_RESERVE_CSPR,
+            SwapError::InvalidReserveLimit,
+        ),
+        get_optional_named_arg_with_user_errors(
+            ARG_MIN_RESERVE_COWL,
+            SwapError::InvalidReserveLimit,
+        ),
+        get_optional_named_arg_with_user_errors(
+            ARG_WARNING_RESERVE_CSPR,
+            SwapError::InvalidReserveLimit,
+        ),
+        get_optional_named_arg_with_user_errors(
+            ARG_WARNING_RESERVE_COWL,
+            SwapError::InvalidReserveLimit,
+        ),
+    )
+}
+
+fn update_reserve_limits(pool: &Pool, reserve_limits: ReserveLimitsArgs) {
+    let (min_reserve_cspr, min_reserve_cowl, warning_reserve_cspr, warning_reserve_cowl) =
+        reserve_limits;
+
+    if let Some(min_reserve_cspr) = min_reserve_cspr {
+        set_reserve_limit(pool, ARG_MIN_RESERVE_CSPR, min_reserve_cspr);
+    }
+    if let Some(min_reserve_cowl) = min_reserve_cowl {
+        set_reserve_limit(pool, ARG_MIN_RESERVE_COWL, min_reserve_cowl);
+    }
+    if let Some(warning_reserve_cspr) = warning_reserve_cspr {
+        set_reserve_limit(pool, ARG_WARNING_RESERVE_CSPR, warning_reserve_cspr);
+    }
+    if let Some(warning_reserve_cowl) = warning_reserve_cowl {
+        set_reserve_limit(pool, ARG_WARNING_RESERVE_COWL, warning_reserve_cowl);
+    }
+
+    // A warning below the floor could never fire before swaps are refused, zero disabling it
+    let min_reserve_cspr: U512 = get_reserve_limit(pool, ARG_MIN_RESERVE_CSPR);
+    let warning_reserve_cspr: U512 = get_reserve_limit(pool, ARG_WARNING_RESERVE_CSPR);
+    let min_reserve_cowl: U256 = get_reserve_limit(pool, ARG_MIN_RESERVE_COWL);
+    let warning_reserve_cowl: U256 = get_reserve_limit(pool, ARG_WARNING_RESERVE_COWL);
+    if (!warning_reserve_cspr.is_zero() && warning_reserve_cspr < min_reserve_cspr)
+        || (!warning_reserve_cowl.is_zero() && warning_reserve_cowl < min_reserve_cowl)
+    {
+        revert(SwapError::InvalidReserveWarning);
+    }
+
+    record_event_dictionary(Event::ReserveLimitsUpdate(ReserveLimitsUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        min_reserve_cspr: get_reserve_limit(pool, ARG_MIN_RESERVE_CSPR),
+        min_reserve_cowl: get_reserve_limit(pool, ARG_MIN_RESERVE_COWL),
+        warning_reserve_cspr: get_reserve_limit(pool, ARG_WARNING_RESERVE_CSPR),
+        warning_reserve_cowl: get_reserve_limit(pool, ARG_WARNING_RESERVE_COWL),
+    }));
+}
+
+#[no_mangle]
+pub extern "C" fn update_times() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    update_time_window(
+        get_pool_arg(),
+        get_named_arg(ARG_START_TIME),
+        get_named_arg(ARG_DURATION),
+    );
+}
+
+fn update_time_window(mut pool: Pool, new_start_time: u64, duration: u64) {
+    let new_end_time = new_start_time
+        .checked_add(duration)
+        .unwrap_or_revert_with(SwapError::InvalidTimeWindow);
+
+    if new_end_time <= new_start_time {
+        revert(SwapError::InvalidTimeWindow)
+    }
+    // A sale that ended below its soft cap stays failed, so the window it was raised in is kept
+    if get_settled_sale(&pool).state == SaleState::Failed {
+        revert(SwapError::InvalidSaleState)
+    }
+    pool.start_time = new_start_time;
+    pool.end_time = new_end_time;
+    save_pool(&pool);
+    record_event_dictionary(Event::UpdateTimes(UpdateTimes {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        new_start_time,
+        new_end_time,
+    }));
+}
+
+#[no_mangle]
+pub extern "C" fn set_cowl_cep18_contract_package() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    update_cowl_cep18_contract_package(get_named_arg(ARG_COWL_CEP18_CONTRACT_PACKAGE));
+}
+
+fn update_cowl_cep18_contract_package(cowl_cep18_contract_package_key: Key) {
+    let (caller, _) = get_verified_caller();
+
+    let cowl_cep18_contract_package_key_hash = ContractPackageHash::from(
+        cowl_cep18_contract_package_key
+            .into_hash()
+            .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
+    );
+
+    // The default pool takes the new token over, it cannot already have a pool of its own
+    if get_pool_tokens().contains(&cowl_cep18_contract_package_key) {
+        revert(SwapError::PoolAlreadyExists);
+    }
+
+    set_stored_value(
+        ARG_COWL_CEP18_CONTRACT_PACKAGE,
+        cowl_cep18_contract_package_key_hash,
+    );
+
+    record_event_dictionary(Event::CowlCep18ContractPackageUpdate(
+        CowlCep18ContractPackageUpdate {
+            header: EventHeader::next(),
+            key: caller,
+            cowl_cep18_contract_package_key,
+        },
+    ));
+}
+
+#[no_mangle]
+pub extern "C" fn set_modalities() {
+    // Only the installing account can change the mutable variables.
+    sec_check(vec![SecurityBadge::Admin]);
+
+    update_events_mode(get_optional_named_arg_with_user_errors(
+        ARG_EVENTS_MODE,
+        SwapError::InvalidEventsMode,
+    ));
+}
+
+fn update_events_mode(events_mode: Option<u8>) {
+    let old_events_mode_u8 = get_stored_value_with_user_errors::<u8>(
+        ARG_EVENTS_MODE,
+        SwapError::MissingEventsMode,
+        SwapError::InvalidEventsMode,
+    );
+    let mut new_events_mode_u8 = old_events_mode_u8;
+
+    if let Some(optional_events_mode) = events_mode {
+        let old_events_mode: EventsMode = old_events_mode_u8.try_into().unwrap_or_revert();
+
+        set_stored_value(ARG_EVENTS_MODE, optional_events_mode);
+        new_events_mode_u8 = optional_events_mode;
+
+        let new_events_mode: EventsMode = optional_events_mode
+            .try_into()
+            .unwrap_or_revert_with(SwapError::InvalidEventsMode);
+
+        // Initialize any storage the new mode needs; already existing structures are kept
+        if old_events_mode != new_events_mode {
+            init_events();
+        }
+    }
+
+    record_event_dictionary(Event::SetModalities(SetModalities {
+        header: EventHeader::next(),
+        old_events_mode: old_events_mode_u8,
+        new_events_mode: new_events_mode_u8,
+    }));
+}
+
+/// Beware: do not remove the last Admin because that will lock out all admin functionality.
+#[no_mangle]
+pub extern "C" fn change_security() {
+    sec_check(vec![SecurityBadge::Admin]);
+
+    update_security(
+        get_optional_named_arg_with_user_errors(ADMIN_LIST, SwapError::InvalidAdminList),
+        get_optional_named_arg_with_user_errors(NONE_LIST, SwapError::InvalidNoneList),
+        get_optional_named_arg_with_user_errors(ORACLE_LIST, SwapError::InvalidOracleList),
+    );
+}
+
+fn update_security(
+    admin_list: Option<Vec<Key>>,
+    none_list: Option<Vec<Key>>,
+    oracle_list: Option<Vec<Key>>,
+) {
+    let mut badge_map: BTreeMap<Key, SecurityBadge> = BTreeMap::new();
+
+    if let Some(admin_list) = admin_list {
+        for account_key in admin_list {
+            badge_map.insert(account_key, SecurityBadge::Admin);
+        }
+    }
+    if let Some(oracle_list) = oracle_list {
+        for account_key in oracle_list {
+            badge_map.insert(account_key, SecurityBadge::OracleUpdater);
+        }
+    }
+    if let Some(none_list) = none_list {
+        for account_key in none_list {
+            badge_map.insert(account_key, SecurityBadge::None);
+        }
+    }
+
+    let (caller, _) = get_verified_caller();
+    badge_map.remove(&caller);
+
+    change_sec_badge(&badge_map);
+    record_event_dictionary(Event::ChangeSecurity(ChangeSecurity {
+        header: EventHeader::next(),
+        admin: caller,
+        sec_change_map: badge_map,
+    }));
 }
 
 #[no_mangle]
@@ -452,28 +2013,270 @@ pub extern "C" fn install() {
         revert(SwapError::ContractAlreadyInitialized);
     }
 
-    let swap_contract_package_key = get_named_arg_with_user_errors::<Key>(
-        ARG_PACKAGE_HASH,
-        SwapError::MissingPackageHash,
-        SwapError::InvalidPackageHash,
-    )
-    .unwrap_or_revert();
+    let swap_contract_package_key = get_named_arg_with_user_errors::<Key>(
+        ARG_PACKAGE_HASH,
+        SwapError::MissingPackageHash,
+        SwapError::InvalidPackageHash,
+    )
+    .unwrap_or_revert();
+
+    put_key(ARG_PACKAGE_HASH, swap_contract_package_key);
+
+    let swap_contract_hash_key = get_named_arg_with_user_errors::<Key>(
+        ARG_CONTRACT_HASH,
+        SwapError::MissingContractHash,
+        SwapError::InvalidContractHash,
+    )
+    .unwrap_or_revert();
+
+    put_key(ARG_CONTRACT_HASH, swap_contract_hash_key);
+
+    // Each contract version keeps its own named keys, so the version number is put in a new uref
+    let contract_version: u32 = get_named_arg(ARG_CONTRACT_VERSION);
+    put_key(ARG_CONTRACT_VERSION, new_uref(contract_version).into());
+
+    init_events();
+
+    new_dictionary(DICT_SECURITY_BADGES).unwrap_or_revert();
+    new_dictionary(DICT_CONTRACT_VERSIONS).unwrap_or_revert();
+    new_dictionary(DICT_CONTRACT_NAMED_KEYS).unwrap_or_revert();
+    register_contract_version(contract_version, swap_contract_hash_key, STORAGE_VERSION);
+    new_dictionary(DICT_COWL_DEPOSITS).unwrap_or_revert();
+    new_dictionary(DICT_RESERVES).unwrap_or_revert();
+    new_dictionary(DICT_SWAP_STATS).unwrap_or_revert();
+    new_dictionary(DICT_POOL_SWAP_STATS).unwrap_or_revert();
+    new_dictionary(DICT_TOKEN_PAIRS).unwrap_or_revert();
+    new_dictionary(DICT_SWAP_POOLS).unwrap_or_revert();
+    new_dictionary(DICT_POOL_PRICING_MODES).unwrap_or_revert();
+    new_dictionary(DICT_LIQUIDITY).unwrap_or_revert();
+    new_dictionary(DICT_LIQUIDITY_POSITIONS).unwrap_or_revert();
+    new_dictionary(DICT_ORACLE_FEEDS).unwrap_or_revert();
+    new_dictionary(DICT_BONDING_CURVES).unwrap_or_revert();
+    new_dictionary(DICT_REFERRALS).unwrap_or_revert();
+    new_dictionary(DICT_VESTING_SCHEDULES).unwrap_or_revert();
+    new_dictionary(DICT_VESTING_ALLOCATIONS).unwrap_or_revert();
+    new_dictionary(DICT_VESTING_PHASES).unwrap_or_revert();
+    new_dictionary(DICT_CLAIM_START_TIMES).unwrap_or_revert();
+    new_dictionary(DICT_SALES).unwrap_or_revert();
+    new_dictionary(DICT_CONTRIBUTIONS).unwrap_or_revert();
+    save_pool(&default_pool_from_named_keys());
+
+    init_security_badges();
+
+    let contract_purse = create_purse();
+    put_key(ARG_PURSE, contract_purse.into());
+}
+
+#[no_mangle]
+pub extern "C" fn upgrade() {
+    // Only the admin can upgrade
+    sec_check(vec![SecurityBadge::Admin]);
+
+    // Only the installer session upgrading the package may call this entry point
+    let (caller, caller_package) = get_verified_caller();
+    let installer = get_key(ARG_INSTALLER).unwrap_or_revert_with(SwapError::MissingInstaller);
+    if caller_package.is_some() || caller != installer {
+        revert(SwapError::InvalidUpgradeCaller);
+    }
+
+    let contract_hash: Key = get_named_arg_with_user_errors(
+        ARG_CONTRACT_HASH,
+        SwapError::MissingContractHash,
+        SwapError::InvalidContractHash,
+    )
+    .unwrap_or_revert();
+    let previous_contract_hash: Key = get_named_arg(ARG_PREVIOUS_CONTRACT_HASH);
+    let previous_contract_version: u32 = get_named_arg(ARG_PREVIOUS_CONTRACT_VERSION);
+    let contract_version: u32 = get_named_arg(ARG_CONTRACT_VERSION);
+
+    // The host only executes enabled versions, so the new hash must be the executing contract of
+    // this package, and the stored hash must still point to the version being replaced.
+    let (executing_contract_hash, executing_contract_package) = get_executing_contract();
+    if contract_hash == previous_contract_hash
+        || contract_hash != Key::from(executing_contract_hash)
+        || executing_contract_package != get_cowl_swap_contract_package()
+        || get_key(ARG_CONTRACT_HASH) != Some(previous_contract_hash)
+    {
+        revert(SwapError::InvalidContractHash);
+    }
+
+    // The package copied the named keys of the version being replaced into this one
+    let previous_named_keys = list_named_keys();
+
+    put_key(ARG_CONTRACT_HASH, contract_hash);
+    put_key(ARG_CONTRACT_VERSION, new_uref(contract_version).into());
+
+    let from_storage_version = migrate_storage();
+    save_contract_named_keys(previous_contract_version, &previous_named_keys);
+
+    // Versions installed before the registry existed ran on the storage found before migrating
+    if get_contract_version_entry(previous_contract_version).is_none() {
+        register_contract_version(
+            previous_contract_version,
+            previous_contract_hash,
+            from_storage_version,
+        );
+    }
+    register_contract_version(contract_version, contract_hash, STORAGE_VERSION);
+
+    // Registers the schemas of this version next to the ones already on chain
+    init_events();
+
+    record_event_dictionary(Event::Upgrade(Upgrade {
+        header: EventHeader::next(),
+        old_contract_hash: previous_contract_hash,
+        old_contract_version: previous_contract_version,
+        new_contract_hash: contract_hash,
+        new_contract_version: contract_version,
+    }));
+
+    if from_storage_version != STORAGE_VERSION {
+        record_event_dictionary(Event::Migrated(Migrated {
+            header: EventHeader::next(),
+            from: from_storage_version,
+            to: STORAGE_VERSION,
+        }));
+    }
+
+    // Configuration forwarded by the installer goes through the same checks as the setters
+    let start_time: Option<u64> =
+        get_optional_named_arg_with_user_errors(ARG_START_TIME, SwapError::InvalidStartTime);
+    let duration: Option<u64> =
+        get_optional_named_arg_with_user_errors(ARG_DURATION, SwapError::InvalidTimeWindow);
+    match (start_time, duration) {
+        (Some(start_time), Some(duration)) => {
+            update_time_window(get_default_pool(), start_time, duration)
+        }
+        (None, None) => {}
+        _ => revert(SwapError::InvalidTimeWindow),
+    }
+
+    if let Some(cowl_cep18_contract_package_key) = get_optional_token_package_arg() {
+        update_cowl_cep18_contract_package(cowl_cep18_contract_package_key);
+    }
+
+    let reserve_limits = get_reserve_limits_args();
+    if reserve_limits != (None, None, None, None) {
+        update_reserve_limits(&get_default_pool(), reserve_limits);
+    }
+
+    let admin_list: Option<Vec<Key>> =
+        get_optional_named_arg_with_user_errors(ADMIN_LIST, SwapError::InvalidAdminList);
+    let none_list: Option<Vec<Key>> =
+        get_optional_named_arg_with_user_errors(NONE_LIST, SwapError::InvalidNoneList);
+    let oracle_list: Option<Vec<Key>> =
+        get_optional_named_arg_with_user_errors(ORACLE_LIST, SwapError::InvalidOracleList);
+    if admin_list.is_some() || none_list.is_some() || oracle_list.is_some() {
+        update_security(admin_list, none_list, oracle_list);
+    }
+
+    // Applied last so the previous events are still recorded in the mode they were emitted under
+    let events_mode: Option<u8> =
+        get_optional_named_arg_with_user_errors(ARG_EVENTS_MODE, SwapError::InvalidEventsMode);
+    if events_mode.is_some() {
+        update_events_mode(events_mode);
+    }
+}
 
-    put_key(ARG_PACKAGE_HASH, swap_contract_package_key);
+/// Validates a rollback to an earlier contract version and returns its contract hash. Switching
+/// the enabled version is left to the installer session, which holds the package access key.
+#[no_mangle]
+pub extern "C" fn rollback() {
+    sec_check(vec![SecurityBadge::Admin]);
 
-    let swap_contract_hash_key = get_named_arg_with_user_errors::<Key>(
-        ARG_CONTRACT_HASH,
-        SwapError::MissingContractHash,
-        SwapError::InvalidContractHash,
+    // Only the installer session switching the package back may call this entry point, so the
+    // event is recorded in the same deploy that enables the earlier version
+    let (caller, caller_package) = get_verified_caller();
+    let installer = get_key(ARG_INSTALLER).unwrap_or_revert_with(SwapError::MissingInstaller);
+    if caller_package.is_some() || caller != installer {
+        revert(SwapError::InvalidUpgradeCaller);
+    }
+
+    let rollback_version: u32 = get_named_arg_with_user_errors(
+        ARG_ROLLBACK_VERSION,
+        SwapError::InvalidRollbackVersion,
+        SwapError::InvalidRollbackVersion,
     )
     .unwrap_or_revert();
+    let contract_version: u32 = get_stored_value_with_user_errors(
+        ARG_CONTRACT_VERSION,
+        SwapError::UnknownContractVersion,
+        SwapError::UnknownContractVersion,
+    );
 
-    put_key(ARG_CONTRACT_HASH, swap_contract_hash_key);
+    if rollback_version >= contract_version {
+        revert(SwapError::InvalidRollbackVersion);
+    }
+
+    let (rollback_contract_hash, rollback_storage_version) =
+        get_contract_version_entry(rollback_version)
+            .unwrap_or_revert_with(SwapError::UnknownContractVersion);
+
+    // Migrations only move forward, an earlier version must run on the current storage layout
+    let storage_version = get_storage_version();
+    if rollback_storage_version != storage_version {
+        revert(SwapError::IncompatibleStorageVersion);
+    }
+
+    // Keys put since the earlier version was replaced only exist in the later versions, which
+    // the earlier one would not see
+    let rollback_named_keys = get_contract_named_keys(rollback_version)
+        .unwrap_or_revert_with(SwapError::IncompatibleNamedKeys);
+    if list_named_keys().iter().any(|(name, key)| {
+        ![ARG_CONTRACT_HASH, ARG_CONTRACT_VERSION].contains(&name.as_str())
+            && rollback_named_keys.get(name) != Some(key)
+    }) {
+        revert(SwapError::IncompatibleNamedKeys);
+    }
+
+    record_event_dictionary(Event::Rollback(Rollback {
+        header: EventHeader::next(),
+        from_contract_hash: get_key(ARG_CONTRACT_HASH).unwrap_or_revert(),
+        from_contract_version: contract_version,
+        to_contract_hash: rollback_contract_hash,
+        to_contract_version: rollback_version,
+        storage_version,
+    }));
+
+    ret(CLValue::from_t(rollback_contract_hash).unwrap_or_revert())
+}
+
+/// Initializes a factory created by the installer, mirroring `install` for swap contracts.
+#[no_mangle]
+pub extern "C" fn install_factory() {
+    if get_key(ARG_PACKAGE_HASH).is_some() {
+        revert(SwapError::ContractAlreadyInitialized);
+    }
+
+    put_key(
+        ARG_PACKAGE_HASH,
+        get_named_arg_with_user_errors::<Key>(
+            ARG_PACKAGE_HASH,
+            SwapError::MissingPackageHash,
+            SwapError::InvalidPackageHash,
+        )
+        .unwrap_or_revert(),
+    );
+    put_key(
+        ARG_CONTRACT_HASH,
+        get_named_arg_with_user_errors::<Key>(
+            ARG_CONTRACT_HASH,
+            SwapError::MissingContractHash,
+            SwapError::InvalidContractHash,
+        )
+        .unwrap_or_revert(),
+    );
 
     init_events();
 
     new_dictionary(DICT_SECURITY_BADGES).unwrap_or_revert();
+    new_dictionary(DICT_POOLS).unwrap_or_revert();
+
+    init_security_badges();
+}
 
+/// Grants the install-time admin and none lists, the caller becoming admin without an admin list.
+fn init_security_badges() {
     let mut badge_map: BTreeMap<Key, SecurityBadge> = BTreeMap::new();
 
     let admin_list: Option<Vec<Key>> =
@@ -481,16 +2284,14 @@ pub extern "C" fn install() {
     let none_list: Option<Vec<Key>> =
         get_optional_named_arg_with_user_errors(NONE_LIST, SwapError::InvalidNoneList);
 
-    if admin_list.is_none()
-        || admin_list
-            .as_ref()
-            .unwrap_or_revert_with(SwapError::InvalidAdminList)
-            .is_empty()
-    {
-        badge_map.insert(get_verified_caller().0, SecurityBadge::Admin);
-    } else if let Some(admin_list) = admin_list {
-        for account_key in admin_list {
-            badge_map.insert(account_key, SecurityBadge::Admin);
+    match admin_list {
+        Some(admin_list) if !admin_list.is_empty() => {
+            for account_key in admin_list {
+                badge_map.insert(account_key, SecurityBadge::Admin);
+            }
+        }
+        _ => {
+            badge_map.insert(get_verified_caller().0, SecurityBadge::Admin);
         }
     }
     if let Some(none_list) = none_list {
@@ -500,35 +2301,100 @@ pub extern "C" fn install() {
     }
 
     change_sec_badge(&badge_map);
-
-    let contract_purse = create_purse();
-    put_key(ARG_PURSE, contract_purse.into());
 }
 
+/// Deploys a new swap package running this wasm and records it in the pool registry. The
+/// creator becomes admin of the pool unless an admin list is given, and gets the package access
+/// key back with the hashes so an installer session can upgrade and roll the pool back.
 #[no_mangle]
-pub extern "C" fn upgrade() {
-    // Only the admin can upgrade
+pub extern "C" fn create_pool() {
     sec_check(vec![SecurityBadge::Admin]);
 
-    put_key(
-        ARG_CONTRACT_HASH,
-        get_named_arg_with_user_errors::<Key>(
-            ARG_CONTRACT_HASH,
-            SwapError::MissingContractHash,
-            SwapError::InvalidContractHash,
-        )
-        .unwrap_or_revert(),
-    );
+    let name: String = get_named_arg_with_user_errors(
+        ARG_NAME,
+        SwapError::MissingSwapName,
+        SwapError::InvalidSwapName,
+    )
+    .unwrap_or_revert();
+
+    if get_key(&format!("{PREFIX_ACCESS_KEY_NAME}_{name}")).is_some() {
+        revert(SwapError::SwapAlreadyInstalled);
+    }
+
+    let start_time: u64 = get_named_arg(ARG_START_TIME);
+    let end_time = start_time
+        .checked_add(get_named_arg(ARG_DURATION))
+        .unwrap_or_revert_with(SwapError::InvalidTimeWindow);
+    if end_time <= start_time {
+        revert(SwapError::InvalidTimeWindow);
+    }
+
+    let (creator, _) = get_verified_caller();
+
+    let admin_list = match get_optional_named_arg_with_user_errors::<Vec<Key>>(
+        ADMIN_LIST,
+        SwapError::InvalidAdminList,
+    ) {
+        Some(admin_list) if !admin_list.is_empty() => admin_list,
+        _ => vec![creator],
+    };
+
+    let (package_hash, contract_hash, contract_version) = install_contract(&name, Some(admin_list));
+    let access_uref = get_key(&format!("{PREFIX_ACCESS_KEY_NAME}_{name}"))
+        .and_then(|key| key.into_uref())
+        .unwrap_or_revert_with(SwapError::MissingPackageHashForUpgrade);
+
+    let pool = PoolInfo {
+        name,
+        package_hash,
+        contract_hash,
+        token_package: get_token_package_arg(),
+        start_time,
+        end_time,
+        creator,
+        created_at: get_current_time_in_seconds(),
+    };
+    let index = register_pool(&pool);
+
+    record_event_dictionary(Event::PoolCreated(PoolCreated {
+        header: EventHeader::next(),
+        index,
+        name: pool.name,
+        package_hash,
+        contract_hash,
+        token_package: pool.token_package,
+        start_time,
+        end_time,
+        creator,
+    }));
+
+    ret(
+        CLValue::from_t((package_hash, (contract_hash, contract_version), access_uref))
+            .unwrap_or_revert(),
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn pool_count() {
+    ret(CLValue::from_t(get_pool_count()).unwrap_or_revert())
+}
 
-    record_event_dictionary(Event::Upgrade(Upgrade {}));
+#[no_mangle]
+pub extern "C" fn pools() {
+    let offset: u64 = get_named_arg(ARG_OFFSET);
+    let limit: u64 = get_named_arg(ARG_LIMIT);
+    ret(CLValue::from_t(list_pools(offset, limit)).unwrap_or_revert())
 }
 
-fn install_contract(name: &str) {
+/// Creates the swap package for `name` and returns its package hash, contract hash and contract
+/// version. Access key and hashes are stored in the context running it, the installer account or
+/// a factory.
+fn install_contract(name: &str, admin_list: Option<Vec<Key>>) -> (Key, Key, u32) {
     let events_mode: u8 =
         get_optional_named_arg_with_user_errors(ARG_EVENTS_MODE, SwapError::InvalidEventsMode)
             .unwrap_or_default();
 
-    let cowl_cep18_contract_package_key: Key = get_named_arg(ARG_COWL_CEP18_CONTRACT_PACKAGE);
+    let cowl_cep18_contract_package_key: Key = get_token_package_arg();
 
     let cowl_cep18_contract_package = ContractPackageHash::from(
         cowl_cep18_contract_package_key
@@ -536,9 +2402,46 @@ fn install_contract(name: &str) {
             .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
     );
 
+    let token_symbol: String =
+        get_optional_named_arg_with_user_errors(ARG_TOKEN_SYMBOL, SwapError::InvalidTokenSymbol)
+            .unwrap_or_else(|| DEFAULT_TOKEN_SYMBOL.to_string());
+    let token_decimals: u8 = get_optional_named_arg_with_user_errors(
+        ARG_TOKEN_DECIMALS,
+        SwapError::InvalidTokenDecimals,
+    )
+    .unwrap_or(DEFAULT_TOKEN_DECIMALS);
+
+    if token_symbol.is_empty() {
+        revert(SwapError::InvalidTokenSymbol);
+    }
+    if token_decimals > MAX_TOKEN_DECIMALS {
+        revert(SwapError::InvalidTokenDecimals);
+    }
+
     let start_time: u64 = get_named_arg(ARG_START_TIME);
     let duration: u64 = get_named_arg(ARG_DURATION);
 
+    let min_reserve_cspr: U512 = get_optional_named_arg_with_user_errors(
+        ARG_MIN_RESERVE_CSPR,
+        SwapError::InvalidReserveLimit,
+    )
+    .unwrap_or_default();
+    let min_reserve_cowl: U256 = get_optional_named_arg_with_user_errors(
+        ARG_MIN_RESERVE_COWL,
+        SwapError::InvalidReserveLimit,
+    )
+    .unwrap_or_default();
+    let warning_reserve_cspr: U512 = get_optional_named_arg_with_user_errors(
+        ARG_WARNING_RESERVE_CSPR,
+        SwapError::InvalidReserveLimit,
+    )
+    .unwrap_or_default();
+    let warning_reserve_cowl: U256 = get_optional_named_arg_with_user_errors(
+        ARG_WARNING_RESERVE_COWL,
+        SwapError::InvalidReserveLimit,
+    )
+    .unwrap_or_default();
+
     let end_time: u64 = start_time
         .checked_add(duration)
         .unwrap_or_revert_with(SwapError::InvalidEndTime);
@@ -549,10 +2452,44 @@ fn install_contract(name: &str) {
         (ARG_INSTALLER.to_string(), get_caller().into()),
         (ARG_START_TIME.to_string(), new_uref(start_time).into()),
         (ARG_END_TIME.to_string(), new_uref(end_time).into()),
+        (ARG_BALANCE_CSPR.to_string(), new_uref(U512::zero()).into()),
+        (ARG_BALANCE_COWL.to_string(), new_uref(U256::zero()).into()),
+        (ARG_SWAP_COUNT.to_string(), new_uref(0_u64).into()),
+        (ARG_EVENT_SEQUENCE.to_string(), new_uref(0_u64).into()),
+        (
+            ARG_STORAGE_VERSION.to_string(),
+            new_uref(STORAGE_VERSION).into(),
+        ),
+        (
+            ARG_MIN_RESERVE_CSPR.to_string(),
+            new_uref(min_reserve_cspr).into(),
+        ),
+        (
+            ARG_MIN_RESERVE_COWL.to_string(),
+            new_uref(min_reserve_cowl).into(),
+        ),
+        (
+            ARG_WARNING_RESERVE_CSPR.to_string(),
+            new_uref(warning_reserve_cspr).into(),
+        ),
+        (
+            ARG_WARNING_RESERVE_COWL.to_string(),
+            new_uref(warning_reserve_cowl).into(),
+        ),
         (
             ARG_COWL_CEP18_CONTRACT_PACKAGE.to_string(),
             new_uref(cowl_cep18_contract_package).into(),
         ),
+        (ARG_TOKEN_SYMBOL.to_string(), new_uref(token_symbol).into()),
+        (
+            ARG_TOKEN_DECIMALS.to_string(),
+            new_uref(token_decimals).into(),
+        ),
+        (
+            ARG_POOL_TOKENS.to_string(),
+            new_uref(Vec::<Key>::new()).into(),
+        ),
+        (ARG_REFERRAL_BONUS.to_string(), new_uref(0_u32).into()),
     ];
 
     let mut named_keys = NamedKeys::new();
@@ -580,14 +2517,14 @@ fn install_contract(name: &str) {
     );
 
     let package_hash_key = get_key(&package_key_name).unwrap_or_revert();
+    record_installed_swap(name, package_hash_key, contract_version);
 
     let mut init_args = runtime_args! {
         ARG_CONTRACT_HASH => contract_hash_key,
+        ARG_CONTRACT_VERSION => contract_version,
         ARG_PACKAGE_HASH => package_hash_key,
     };
 
-    let admin_list: Option<Vec<Key>> =
-        get_optional_named_arg_with_user_errors(ADMIN_LIST, SwapError::InvalidAdminList);
     let none_list: Option<Vec<Key>> =
         get_optional_named_arg_with_user_errors(NONE_LIST, SwapError::InvalidNoneList);
 
@@ -600,23 +2537,178 @@ fn install_contract(name: &str) {
     }
 
     call_contract::<()>(contract_hash, ENTRY_POINT_INSTALL, init_args);
+
+    (package_hash_key, contract_hash_key, contract_version)
 }
 
-fn upgrade_contract(name: &str) {
-    let entry_points = generate_entry_points();
+/// Creates a pool through the factory package `factory_package` and keeps the access key and
+/// hashes it hands back under the same names as `install_contract`, so the pool upgrades and
+/// rolls back from this account like an installed swap.
+fn create_factory_pool(name: &str, factory_package: Key) {
+    let factory_package_hash = factory_package
+        .into_hash()
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert_with(SwapError::InvalidFactoryPackage);
+
+    let mut runtime_args = runtime_args! {
+        ARG_NAME => name,
+        ARG_START_TIME => get_named_arg::<u64>(ARG_START_TIME),
+        ARG_DURATION => get_named_arg::<u64>(ARG_DURATION),
+    };
+    forward_optional_arg::<u8>(
+        &mut runtime_args,
+        ARG_EVENTS_MODE,
+        SwapError::InvalidEventsMode,
+    );
+    forward_optional_arg::<Key>(
+        &mut runtime_args,
+        ARG_COWL_CEP18_CONTRACT_PACKAGE,
+        SwapError::InvalidTokenContractPackage,
+    );
+    forward_optional_arg::<Key>(
+        &mut runtime_args,
+        ARG_TOKEN_CONTRACT_PACKAGE,
+        SwapError::InvalidTokenContractPackage,
+    );
+    forward_optional_arg::<String>(
+        &mut runtime_args,
+        ARG_TOKEN_SYMBOL,
+        SwapError::InvalidTokenSymbol,
+    );
+    forward_optional_arg::<u8>(
+        &mut runtime_args,
+        ARG_TOKEN_DECIMALS,
+        SwapError::InvalidTokenDecimals,
+    );
+    forward_optional_arg::<U512>(
+        &mut runtime_args,
+        ARG_MIN_RESERVE_CSPR,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<U256>(
+        &mut runtime_args,
+        ARG_MIN_RESERVE_COWL,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<U512>(
+        &mut runtime_args,
+        ARG_WARNING_RESERVE_CSPR,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<U256>(
+        &mut runtime_args,
+        ARG_WARNING_RESERVE_COWL,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<Vec<Key>>(&mut runtime_args, ADMIN_LIST, SwapError::InvalidAdminList);
+    forward_optional_arg::<Vec<Key>>(&mut runtime_args, NONE_LIST, SwapError::InvalidNoneList);
+
+    let (package_hash, (contract_hash, contract_version), access_uref): (Key, (Key, u32), URef) =
+        call_versioned_contract(
+            factory_package_hash,
+            None,
+            ENTRY_POINT_CREATE_POOL,
+            runtime_args,
+        );
+
+    put_key(
+        &format!("{PREFIX_ACCESS_KEY_NAME}_{name}"),
+        access_uref.into(),
+    );
+    put_key(
+        &format!("{PREFIX_CONTRACT_PACKAGE_NAME}_{name}"),
+        package_hash,
+    );
+    put_key(&format!("{PREFIX_CONTRACT_NAME}_{name}"), contract_hash);
+    put_key(
+        &format!("{PREFIX_CONTRACT_VERSION}_{name}"),
+        new_uref(contract_version).into(),
+    );
+    record_installed_swap(name, package_hash, contract_version);
+}
+
+fn install_factory_contract(name: &str) {
+    let events_mode: u8 =
+        get_optional_named_arg_with_user_errors(ARG_EVENTS_MODE, SwapError::InvalidEventsMode)
+            .unwrap_or_default();
+
+    let keys = vec![
+        (ARG_NAME.to_string(), new_uref(name).into()),
+        (ARG_EVENTS_MODE.to_string(), new_uref(events_mode).into()),
+        (ARG_INSTALLER.to_string(), get_caller().into()),
+        (ARG_EVENT_SEQUENCE.to_string(), new_uref(0_u64).into()),
+        (ARG_POOL_COUNT.to_string(), new_uref(0_u64).into()),
+    ];
+
+    let mut named_keys = NamedKeys::new();
+    for (key, value) in keys {
+        named_keys.insert(key, value);
+    }
+
+    let package_key_name = format!("{PREFIX_FACTORY_PACKAGE_NAME}_{name}");
+
+    let (contract_hash, _) = new_contract(
+        generate_factory_entry_points(),
+        Some(named_keys),
+        Some(package_key_name.clone()),
+        Some(format!("{PREFIX_FACTORY_ACCESS_KEY_NAME}_{name}")),
+    );
+
+    let contract_hash_key = Key::from(contract_hash);
+    put_key(
+        &format!("{PREFIX_FACTORY_CONTRACT_NAME}_{name}"),
+        contract_hash_key,
+    );
+
+    let mut init_args = runtime_args! {
+        ARG_CONTRACT_HASH => contract_hash_key,
+        ARG_PACKAGE_HASH => get_key(&package_key_name).unwrap_or_revert(),
+    };
+
+    let admin_list: Option<Vec<Key>> =
+        get_optional_named_arg_with_user_errors(ADMIN_LIST, SwapError::InvalidAdminList);
+    let none_list: Option<Vec<Key>> =
+        get_optional_named_arg_with_user_errors(NONE_LIST, SwapError::InvalidNoneList);
+
+    if let Some(admin_list) = admin_list {
+        init_args.insert(ADMIN_LIST, admin_list).unwrap_or_revert();
+    }
 
+    if let Some(none_list) = none_list {
+        init_args.insert(NONE_LIST, none_list).unwrap_or_revert();
+    }
+
+    call_contract::<()>(contract_hash, ENTRY_POINT_INSTALL_FACTORY, init_args);
+}
+
+/// Package, current contract hash and current contract version stored in the installer account.
+fn get_installed_contract(name: &str) -> (ContractPackageHash, ContractHash, u32) {
     let contract_package_hash = get_key(&format!("{PREFIX_CONTRACT_PACKAGE_NAME}_{name}"))
         .unwrap_or_revert()
         .into_hash()
         .map(ContractPackageHash::new)
         .unwrap_or_revert_with(SwapError::MissingPackageHashForUpgrade);
 
-    let previous_contract_hash = get_key(&format!("{PREFIX_CONTRACT_NAME}_{name}"))
+    let contract_hash = get_key(&format!("{PREFIX_CONTRACT_NAME}_{name}"))
         .unwrap_or_revert()
         .into_hash()
         .map(ContractHash::new)
         .unwrap_or_revert_with(SwapError::MissingPackageHashForUpgrade);
 
+    let contract_version: u32 = get_key(&format!("{PREFIX_CONTRACT_VERSION}_{name}"))
+        .and_then(|key| key.into_uref())
+        .and_then(|uref| read(uref).unwrap_or_revert())
+        .unwrap_or_revert_with(SwapError::MissingPackageHashForUpgrade);
+
+    (contract_package_hash, contract_hash, contract_version)
+}
+
+fn upgrade_contract(name: &str) {
+    let entry_points = generate_entry_points();
+
+    let (contract_package_hash, previous_contract_hash, previous_contract_version) =
+        get_installed_contract(name);
+
     let (contract_hash, contract_version) =
         add_contract_version(contract_package_hash, entry_points, NamedKeys::new());
 
@@ -629,16 +2721,143 @@ fn upgrade_contract(name: &str) {
         &format!("{PREFIX_CONTRACT_VERSION}_{name}"),
         new_uref(contract_version).into(),
     );
+    record_installed_swap(name, contract_package_hash.into(), contract_version);
 
     let contract_hash_key = Key::from(contract_hash);
 
-    let runtime_args = runtime_args! {
+    let mut runtime_args = runtime_args! {
         ARG_CONTRACT_HASH => contract_hash_key,
+        ARG_CONTRACT_VERSION => contract_version,
+        ARG_PREVIOUS_CONTRACT_HASH => Key::from(previous_contract_hash),
+        ARG_PREVIOUS_CONTRACT_VERSION => previous_contract_version,
     };
 
+    // Install-time parameters supplied with the upgrade are applied by the new version
+    forward_optional_arg::<u8>(
+        &mut runtime_args,
+        ARG_EVENTS_MODE,
+        SwapError::InvalidEventsMode,
+    );
+    forward_optional_arg::<u64>(
+        &mut runtime_args,
+        ARG_START_TIME,
+        SwapError::InvalidStartTime,
+    );
+    forward_optional_arg::<u64>(
+        &mut runtime_args,
+        ARG_DURATION,
+        SwapError::InvalidTimeWindow,
+    );
+    forward_optional_arg::<Key>(
+        &mut runtime_args,
+        ARG_COWL_CEP18_CONTRACT_PACKAGE,
+        SwapError::InvalidTokenContractPackage,
+    );
+    forward_optional_arg::<Key>(
+        &mut runtime_args,
+        ARG_TOKEN_CONTRACT_PACKAGE,
+        SwapError::InvalidTokenContractPackage,
+    );
+    forward_optional_arg::<U512>(
+        &mut runtime_args,
+        ARG_MIN_RESERVE_CSPR,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<U256>(
+        &mut runtime_args,
+        ARG_MIN_RESERVE_COWL,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<U512>(
+        &mut runtime_args,
+        ARG_WARNING_RESERVE_CSPR,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<U256>(
+        &mut runtime_args,
+        ARG_WARNING_RESERVE_COWL,
+        SwapError::InvalidReserveLimit,
+    );
+    forward_optional_arg::<Vec<Key>>(&mut runtime_args, ADMIN_LIST, SwapError::InvalidAdminList);
+    forward_optional_arg::<Vec<Key>>(&mut runtime_args, NONE_LIST, SwapError::InvalidNoneList);
+
     call_contract::<()>(contract_hash, ENTRY_POINT_UPGRADE, runtime_args);
 }
 
+/// Token package argument, `token_contract_package` or its COWL alias.
+fn get_optional_token_package_arg() -> Option<Key> {
+    get_optional_named_arg_with_user_errors(
+        ARG_TOKEN_CONTRACT_PACKAGE,
+        SwapError::InvalidTokenContractPackage,
+    )
+    .or_else(|| {
+        get_optional_named_arg_with_user_errors(
+            ARG_COWL_CEP18_CONTRACT_PACKAGE,
+            SwapError::InvalidTokenContractPackage,
+        )
+    })
+}
+
+fn get_token_package_arg() -> Key {
+    get_optional_token_package_arg().unwrap_or_revert_with(SwapError::MissingTokenContractPackage)
+}
+
+fn forward_optional_arg<T: CLTyped + FromBytes + ToBytes>(
+    runtime_args: &mut RuntimeArgs,
+    name: &str,
+    invalid: SwapError,
+) {
+    if let Some(value) = get_optional_named_arg_with_user_errors::<T>(name, invalid) {
+        runtime_args.insert(name, value).unwrap_or_revert();
+    }
+}
+
+fn rollback_contract(name: &str, rollback_version: u32) {
+    let (contract_package_hash, contract_hash, _) = get_installed_contract(name);
+
+    let rollback_contract_hash = call_contract::<Key>(
+        contract_hash,
+        ENTRY_POINT_ROLLBACK,
+        runtime_args! {
+            ARG_ROLLBACK_VERSION => rollback_version,
+        },
+    )
+    .into_hash()
+    .map(ContractHash::new)
+    .unwrap_or_revert_with(SwapError::UnknownContractVersion);
+
+    enable_contract_version(contract_package_hash, rollback_contract_hash).unwrap_or_revert();
+    disable_contract_version(contract_package_hash, contract_hash).unwrap_or_revert();
+    put_key(
+        &format!("{PREFIX_CONTRACT_NAME}_{name}"),
+        rollback_contract_hash.into(),
+    );
+    put_key(
+        &format!("{PREFIX_CONTRACT_VERSION}_{name}"),
+        new_uref(rollback_version).into(),
+    );
+    record_installed_swap(name, contract_package_hash.into(), rollback_version);
+}
+
+/// Keeps the installer's index of swaps, name to package hash and current contract version, in
+/// sync with the per-swap named keys.
+fn record_installed_swap(name: &str, package_hash: Key, contract_version: u32) {
+    match get_key(INSTALLED_SWAPS).and_then(|key| key.into_uref()) {
+        Some(uref) => {
+            let mut installed_swaps: BTreeMap<String, (Key, u32)> = read(uref)
+                .unwrap_or_revert_with(SwapError::InvalidInstalledSwaps)
+                .unwrap_or_revert_with(SwapError::InvalidInstalledSwaps);
+            installed_swaps.insert(name.to_string(), (package_hash, contract_version));
+            write(uref, installed_swaps);
+        }
+        None => {
+            let installed_swaps: BTreeMap<String, (Key, u32)> =
+                BTreeMap::from([(name.to_string(), (package_hash, contract_version))]);
+            put_key(INSTALLED_SWAPS, new_uref(installed_swaps).into());
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn call() {
     let name: String = get_named_arg_with_user_errors(
@@ -651,11 +2870,48 @@ pub extern "C" fn call() {
     let upgrade_flag: Option<bool> =
         get_optional_named_arg_with_user_errors(ARG_UPGRADE_FLAG, SwapError::InvalidUpgradeFlag);
 
+    let rollback_version: Option<u32> = get_optional_named_arg_with_user_errors(
+        ARG_ROLLBACK_VERSION,
+        SwapError::InvalidRollbackVersion,
+    );
+
+    let factory_flag: Option<bool> =
+        get_optional_named_arg_with_user_errors(ARG_FACTORY, SwapError::InvalidFactoryFlag);
+
+    let factory_package: Option<Key> = get_optional_named_arg_with_user_errors(
+        ARG_FACTORY_PACKAGE,
+        SwapError::InvalidFactoryPackage,
+    );
+
+    if factory_flag == Some(true) {
+        if get_key(&format!("{PREFIX_FACTORY_ACCESS_KEY_NAME}_{name}")).is_some() {
+            revert(SwapError::SwapAlreadyInstalled);
+        }
+        return install_factory_contract(&name);
+    }
+
     let access_key = get_key(&format!("{PREFIX_ACCESS_KEY_NAME}_{name}"));
 
-    if upgrade_flag.is_some() && upgrade_flag.unwrap() && access_key.is_some() {
+    if let Some(factory_package) = factory_package {
+        if access_key.is_some() {
+            revert(SwapError::SwapAlreadyInstalled);
+        }
+        return create_factory_pool(&name, factory_package);
+    }
+
+    if let Some(rollback_version) = rollback_version {
+        if access_key.is_none() {
+            revert(SwapError::MissingPackageHashForUpgrade);
+        }
+        rollback_contract(&name, rollback_version)
+    } else if upgrade_flag.is_some() && upgrade_flag.unwrap() && access_key.is_some() {
         upgrade_contract(&name)
     } else if access_key.is_none() {
-        install_contract(&name)
+        install_contract(
+            &name,
+            get_optional_named_arg_with_user_errors(ADMIN_LIST, SwapError::InvalidAdminList),
+        );
+    } else {
+        revert(SwapError::SwapAlreadyInstalled)
     }
 }
@@ -0,0 +1,324 @@
+//! Ordered storage migrations executed once per storage version by the `upgrade` entry point.
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{
+        ARG_EVENT_SEQUENCE, ARG_MIN_RESERVE_COWL, ARG_MIN_RESERVE_CSPR, ARG_POOL_TOKENS,
+        ARG_REFERRAL_BONUS, ARG_STORAGE_VERSION, ARG_SWAP_COUNT, ARG_TOKEN_DECIMALS,
+        ARG_TOKEN_SYMBOL, ARG_WARNING_RESERVE_COWL, ARG_WARNING_RESERVE_CSPR,
+        DEFAULT_TOKEN_DECIMALS, DEFAULT_TOKEN_SYMBOL, DICT_BONDING_CURVES, DICT_CLAIM_START_TIMES,
+        DICT_CONTRACT_NAMED_KEYS, DICT_CONTRACT_VERSIONS, DICT_CONTRIBUTIONS, DICT_COWL_DEPOSITS,
+        DICT_LIQUIDITY, DICT_LIQUIDITY_POSITIONS, DICT_ORACLE_FEEDS, DICT_POOL_PRICING_MODES,
+        DICT_POOL_SWAP_STATS, DICT_REFERRALS, DICT_RESERVES, DICT_SALES, DICT_SWAP_POOLS,
+        DICT_SWAP_STATS, DICT_TOKEN_PAIRS, DICT_VESTING_ALLOCATIONS, DICT_VESTING_PHASES,
+        DICT_VESTING_SCHEDULES,
+    },
+    error::SwapError,
+    pools::{default_pool_from_named_keys, save_pool},
+    reserves::{reserve_item_key, ReserveCounter, RESERVE_ASSET_COWL, RESERVE_ASSET_CSPR},
+    utils::{
+        get_contract_cowl_balance, get_contract_cspr_balance, get_dictionary_value_from_key,
+        get_stored_value_with_user_errors, set_dictionary_value_for_key, set_stored_value,
+    },
+};
+#[cfg(feature = "contract-support")]
+use alloc::{string::ToString, vec::Vec};
+#[cfg(feature = "contract-support")]
+use casper_contract::{
+    contract_api::{
+        runtime::{get_key, put_key, revert},
+        storage::{new_dictionary, new_uref},
+    },
+    unwrap_or_revert::UnwrapOrRevert,
+};
+#[cfg(feature = "contract-support")]
+use casper_types::{contracts::NamedKeys, Key, U256, U512};
+
+/// Storage version written by contract 1.0.0, which did not store a version yet.
+pub const INITIAL_STORAGE_VERSION: u32 = 1;
+
+/// Storage version expected by this build of the contract.
+pub const STORAGE_VERSION: u32 = 19;
+
+/// `MIGRATIONS[i]` moves storage from version `i + 1` to `i + 2`.
+///
+/// Steps only create what is missing, so contracts upgraded through intermediate builds that
+/// already carry part of the storage are not reset.
+#[cfg(feature = "contract-support")]
+const MIGRATIONS: [fn(); (STORAGE_VERSION - INITIAL_STORAGE_VERSION) as usize] = [
+    migrate_liquidity_ledgers,
+    migrate_swap_history,
+    migrate_event_sequence,
+    migrate_contract_versions,
+    migrate_token_metadata,
+    migrate_token_pairs,
+    migrate_swap_pools,
+    migrate_pool_pricing_modes,
+    migrate_liquidity_shares,
+    migrate_oracle_feeds,
+    migrate_bonding_curves,
+    migrate_referrals,
+    migrate_vesting,
+    migrate_sales,
+    migrate_pool_swap_stats,
+    migrate_vesting_phases,
+    migrate_claim_start_times,
+    migrate_contract_named_keys,
+];
+
+#[cfg(feature = "contract-support")]
+pub fn get_storage_version() -> u32 {
+    if get_key(ARG_STORAGE_VERSION).is_none() {
+        return INITIAL_STORAGE_VERSION;
+    }
+    get_stored_value_with_user_errors(
+        ARG_STORAGE_VERSION,
+        SwapError::InvalidStorageVersion,
+        SwapError::InvalidStorageVersion,
+    )
+}
+
+/// Runs every migration between the stored version and [`STORAGE_VERSION`] in order and returns
+/// the version storage was migrated from.
+#[cfg(feature = "contract-support")]
+pub fn migrate_storage() -> u32 {
+    let from_version = get_storage_version();
+    if from_version > STORAGE_VERSION {
+        revert(SwapError::InvalidStorageVersion);
+    }
+
+    for version in from_version..STORAGE_VERSION {
+        MIGRATIONS[(version - INITIAL_STORAGE_VERSION) as usize]();
+        set_stored_value(ARG_STORAGE_VERSION, version + 1);
+    }
+
+    from_version
+}
+
+/// Contract hash and storage version recorded for a contract version of the package.
+#[cfg(feature = "contract-support")]
+pub fn get_contract_version_entry(contract_version: u32) -> Option<(Key, u32)> {
+    get_dictionary_value_from_key(DICT_CONTRACT_VERSIONS, &contract_version.to_string())
+}
+
+/// Named keys a contract version of the package ran with, recorded when the next version
+/// replaced it.
+#[cfg(feature = "contract-support")]
+pub fn get_contract_named_keys(contract_version: u32) -> Option<NamedKeys> {
+    get_dictionary_value_from_key(DICT_CONTRACT_NAMED_KEYS, &contract_version.to_string())
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_contract_named_keys(contract_version: u32, named_keys: &NamedKeys) {
+    set_dictionary_value_for_key(
+        DICT_CONTRACT_NAMED_KEYS,
+        &contract_version.to_string(),
+        named_keys,
+    );
+}
+
+#[cfg(feature = "contract-support")]
+pub fn register_contract_version(contract_version: u32, contract_hash: Key, storage_version: u32) {
+    set_dictionary_value_for_key(
+        DICT_CONTRACT_VERSIONS,
+        &contract_version.to_string(),
+        &(contract_hash, storage_version),
+    );
+}
+
+/// 1 → 2: COWL deposit ledger, reserve accounting and reserve limits.
+#[cfg(feature = "contract-support")]
+fn migrate_liquidity_ledgers() {
+    if get_key(DICT_COWL_DEPOSITS).is_none() {
+        new_dictionary(DICT_COWL_DEPOSITS).unwrap_or_revert();
+    }
+
+    if get_key(DICT_RESERVES).is_none() {
+        new_dictionary(DICT_RESERVES).unwrap_or_revert();
+
+        // Liquidity held before reserve accounting existed is accounted as deposited.
+        set_dictionary_value_for_key(
+            DICT_RESERVES,
+            &reserve_item_key(RESERVE_ASSET_CSPR, ReserveCounter::Deposited),
+            &get_contract_cspr_balance(),
+        );
+        set_dictionary_value_for_key(
+            DICT_RESERVES,
+            &reserve_item_key(RESERVE_ASSET_COWL, ReserveCounter::Deposited),
+            &get_contract_cowl_balance(),
+        );
+    }
+
+    if get_key(ARG_MIN_RESERVE_CSPR).is_none() {
+        put_key(ARG_MIN_RESERVE_CSPR, new_uref(U512::zero()).into());
+    }
+    if get_key(ARG_MIN_RESERVE_COWL).is_none() {
+        put_key(ARG_MIN_RESERVE_COWL, new_uref(U256::zero()).into());
+    }
+    if get_key(ARG_WARNING_RESERVE_CSPR).is_none() {
+        put_key(ARG_WARNING_RESERVE_CSPR, new_uref(U512::zero()).into());
+    }
+    if get_key(ARG_WARNING_RESERVE_COWL).is_none() {
+        put_key(ARG_WARNING_RESERVE_COWL, new_uref(U256::zero()).into());
+    }
+}
+
+/// 2 → 3: per-account swap statistics and the global swap counter.
+#[cfg(feature = "contract-support")]
+fn migrate_swap_history() {
+    if get_key(DICT_SWAP_STATS).is_none() {
+        new_dictionary(DICT_SWAP_STATS).unwrap_or_revert();
+    }
+    if get_key(ARG_SWAP_COUNT).is_none() {
+        put_key(ARG_SWAP_COUNT, new_uref(0_u64).into());
+    }
+}
+
+/// 3 → 4: sequence counter used by the common event header.
+#[cfg(feature = "contract-support")]
+fn migrate_event_sequence() {
+    if get_key(ARG_EVENT_SEQUENCE).is_none() {
+        put_key(ARG_EVENT_SEQUENCE, new_uref(0_u64).into());
+    }
+}
+
+/// 4 → 5: registry of contract versions and the storage version each one runs on.
+#[cfg(feature = "contract-support")]
+fn migrate_contract_versions() {
+    if get_key(DICT_CONTRACT_VERSIONS).is_none() {
+        new_dictionary(DICT_CONTRACT_VERSIONS).unwrap_or_revert();
+    }
+}
+
+/// 5 → 6: metadata of the swapped token, which was always COWL before.
+#[cfg(feature = "contract-support")]
+fn migrate_token_metadata() {
+    if get_key(ARG_TOKEN_SYMBOL).is_none() {
+        put_key(ARG_TOKEN_SYMBOL, new_uref(DEFAULT_TOKEN_SYMBOL).into());
+    }
+    if get_key(ARG_TOKEN_DECIMALS).is_none() {
+        put_key(ARG_TOKEN_DECIMALS, new_uref(DEFAULT_TOKEN_DECIMALS).into());
+    }
+}
+
+/// 6 → 7: configuration of token-to-token pairs.
+#[cfg(feature = "contract-support")]
+fn migrate_token_pairs() {
+    if get_key(DICT_TOKEN_PAIRS).is_none() {
+        new_dictionary(DICT_TOKEN_PAIRS).unwrap_or_revert();
+    }
+}
+
+/// 7 → 8: pool registry, seeded with the configured token as the default pool so current
+/// integrations keep swapping without a `pool` argument.
+#[cfg(feature = "contract-support")]
+fn migrate_swap_pools() {
+    if get_key(DICT_SWAP_POOLS).is_none() {
+        new_dictionary(DICT_SWAP_POOLS).unwrap_or_revert();
+        save_pool(&default_pool_from_named_keys());
+    }
+    if get_key(ARG_POOL_TOKENS).is_none() {
+        put_key(ARG_POOL_TOKENS, new_uref(Vec::<Key>::new()).into());
+    }
+}
+
+/// 8 → 9: pricing mode of each pool, pools without an entry keep pricing with their tiers.
+#[cfg(feature = "contract-support")]
+fn migrate_pool_pricing_modes() {
+    if get_key(DICT_POOL_PRICING_MODES).is_none() {
+        new_dictionary(DICT_POOL_PRICING_MODES).unwrap_or_revert();
+    }
+}
+
+/// 9 → 10: shares of the liquidity added by accounts, per pool and per provider.
+#[cfg(feature = "contract-support")]
+fn migrate_liquidity_shares() {
+    if get_key(DICT_LIQUIDITY).is_none() {
+        new_dictionary(DICT_LIQUIDITY).unwrap_or_revert();
+    }
+    if get_key(DICT_LIQUIDITY_POSITIONS).is_none() {
+        new_dictionary(DICT_LIQUIDITY_POSITIONS).unwrap_or_revert();
+    }
+}
+
+/// 10 → 11: oracle configuration and last price of the pools.
+#[cfg(feature = "contract-support")]
+fn migrate_oracle_feeds() {
+    if get_key(DICT_ORACLE_FEEDS).is_none() {
+        new_dictionary(DICT_ORACLE_FEEDS).unwrap_or_revert();
+    }
+}
+
+/// 11 → 12: bonding curves of the pools.
+#[cfg(feature = "contract-support")]
+fn migrate_bonding_curves() {
+    if get_key(DICT_BONDING_CURVES).is_none() {
+        new_dictionary(DICT_BONDING_CURVES).unwrap_or_revert();
+    }
+}
+
+/// 12 → 13: referral bonus, disabled, and rewards earned by referrers.
+#[cfg(feature = "contract-support")]
+fn migrate_referrals() {
+    if get_key(ARG_REFERRAL_BONUS).is_none() {
+        put_key(ARG_REFERRAL_BONUS, new_uref(0_u32).into());
+    }
+    if get_key(DICT_REFERRALS).is_none() {
+        new_dictionary(DICT_REFERRALS).unwrap_or_revert();
+    }
+}
+
+/// 13 → 14: vesting schedules of pools and allocations of their buyers.
+#[cfg(feature = "contract-support")]
+fn migrate_vesting() {
+    if get_key(DICT_VESTING_SCHEDULES).is_none() {
+        new_dictionary(DICT_VESTING_SCHEDULES).unwrap_or_revert();
+    }
+    if get_key(DICT_VESTING_ALLOCATIONS).is_none() {
+        new_dictionary(DICT_VESTING_ALLOCATIONS).unwrap_or_revert();
+    }
+}
+
+/// 14 → 15: soft caps of pool sales and contributions of their buyers.
+#[cfg(feature = "contract-support")]
+fn migrate_sales() {
+    if get_key(DICT_SALES).is_none() {
+        new_dictionary(DICT_SALES).unwrap_or_revert();
+    }
+    if get_key(DICT_CONTRIBUTIONS).is_none() {
+        new_dictionary(DICT_CONTRIBUTIONS).unwrap_or_revert();
+    }
+}
+
+/// 15 → 16: swap statistics of each pool. Earlier swaps stay in the package-wide statistics only.
+#[cfg(feature = "contract-support")]
+fn migrate_pool_swap_stats() {
+    if get_key(DICT_POOL_SWAP_STATS).is_none() {
+        new_dictionary(DICT_POOL_SWAP_STATS).unwrap_or_revert();
+    }
+}
+
+/// 16 → 17: vesting phases of each pool sale. Earlier purchases stay on the pool-wide allocations.
+#[cfg(feature = "contract-support")]
+fn migrate_vesting_phases() {
+    if get_key(DICT_VESTING_PHASES).is_none() {
+        new_dictionary(DICT_VESTING_PHASES).unwrap_or_revert();
+    }
+}
+
+/// 17 → 18: claim start times of each pool, kept apart from its schedule. Pools switched to
+/// claim-based delivery before keep the schedule it was stored as.
+#[cfg(feature = "contract-support")]
+fn migrate_claim_start_times() {
+    if get_key(DICT_CLAIM_START_TIMES).is_none() {
+        new_dictionary(DICT_CLAIM_START_TIMES).unwrap_or_revert();
+    }
+}
+
+/// 18 → 19: named keys of each replaced contract version, checked by rollbacks. Versions replaced
+/// before cannot be rolled back to, their storage version being older anyway.
+#[cfg(feature = "contract-support")]
+fn migrate_contract_named_keys() {
+    if get_key(DICT_CONTRACT_NAMED_KEYS).is_none() {
+        new_dictionary(DICT_CONTRACT_NAMED_KEYS).unwrap_or_revert();
+    }
+}
@@ -0,0 +1,122 @@
+//! Reference price of a pool pushed by oracle updaters, only used while it is fresh.
+use casper_types::U512;
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::DICT_ORACLE_FEEDS,
+    pools::{pool_item_key, Pool},
+    utils::{
+        get_current_time_in_seconds, get_dictionary_value_from_key, set_dictionary_value_for_key,
+    },
+};
+use crate::{constants::MAX_BASIS_POINTS, error::SwapError, utils::tuple_typed};
+
+/// Fixed-point scale of oracle prices, so a price of one whole token per CSPR is `10^9`.
+pub const ORACLE_PRICE_PRECISION: U512 = U512([1_000_000_000u64, 0, 0, 0, 0, 0, 0, 0]);
+
+/// Oracle configuration and last price of a pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OracleFeed {
+    /// Whole tokens per CSPR scaled by [`ORACLE_PRICE_PRECISION`], zero before the first update.
+    pub price: U512,
+    /// Time the price was observed at, in seconds.
+    pub timestamp: u64,
+    /// Age in seconds after which the price is stale.
+    pub max_age: u64,
+    /// Largest move from the previous fresh price accepted in one update, in basis points.
+    pub max_deviation: u32,
+    /// Discount taken from the output of swaps in both directions, in basis points.
+    pub spread: u32,
+}
+
+impl OracleFeed {
+    pub fn is_configured(&self) -> bool {
+        self.max_age != 0
+    }
+
+    /// Sets the bounds of the feed, keeping its last price.
+    pub fn configure(
+        &mut self,
+        max_age: u64,
+        max_deviation: u32,
+        spread: u32,
+    ) -> Result<(), SwapError> {
+        if max_age == 0 || max_deviation == 0 || spread >= MAX_BASIS_POINTS {
+            return Err(SwapError::InvalidOracleConfig);
+        }
+        self.max_age = max_age;
+        self.max_deviation = max_deviation;
+        self.spread = spread;
+        Ok(())
+    }
+
+    /// Last price if it is not older than the maximum age at `current_time`.
+    pub fn fresh_price(&self, current_time: u64) -> Option<U512> {
+        if self.price.is_zero() || current_time.saturating_sub(self.timestamp) > self.max_age {
+            return None;
+        }
+        Some(self.price)
+    }
+
+    /// Records `price` observed at `timestamp`, bounded in deviation from a fresh previous price.
+    pub fn update_price(
+        &mut self,
+        price: U512,
+        timestamp: u64,
+        current_time: u64,
+    ) -> Result<(), SwapError> {
+        if !self.is_configured() {
+            return Err(SwapError::MissingOracleConfig);
+        }
+        if price.is_zero() {
+            return Err(SwapError::InvalidOraclePrice);
+        }
+        if timestamp > current_time || (!self.price.is_zero() && timestamp <= self.timestamp) {
+            return Err(SwapError::InvalidOracleTimestamp);
+        }
+        if current_time - timestamp > self.max_age {
+            return Err(SwapError::StaleOraclePrice);
+        }
+        if let Some(previous_price) = self.fresh_price(current_time) {
+            let difference = if price > previous_price {
+                price - previous_price
+            } else {
+                previous_price - price
+            };
+            let deviation = difference
+                .checked_mul(U512::from(MAX_BASIS_POINTS))
+                .ok_or(SwapError::Overflow)?
+                / previous_price;
+            if deviation > U512::from(self.max_deviation) {
+                return Err(SwapError::OracleDeviationExceeded);
+            }
+        }
+        self.price = price;
+        self.timestamp = timestamp;
+        Ok(())
+    }
+}
+
+tuple_typed! {
+    OracleFeed as ((U512, u64, u64), (u32, u32)) {
+        price, timestamp, max_age, max_deviation, spread
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_oracle_feed(pool: &Pool) -> OracleFeed {
+    get_dictionary_value_from_key(DICT_ORACLE_FEEDS, &pool_item_key(pool)).unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_oracle_feed(pool: &Pool, feed: &OracleFeed) {
+    set_dictionary_value_for_key(DICT_ORACLE_FEEDS, &pool_item_key(pool), feed)
+}
+
+/// Fresh price and spread of the pool, `None` when swaps fall back to the rate tiers.
+#[cfg(feature = "contract-support")]
+pub fn get_fresh_oracle_price(pool: &Pool) -> Option<(U512, u32)> {
+    let feed = get_oracle_feed(pool);
+    feed.fresh_price(get_current_time_in_seconds())
+        .map(|price| (price, feed.spread))
+}
@@ -0,0 +1,106 @@
+//! CEP-18 → CEP-18 swap pairs, priced at their own rate with the same tax as CSPR swaps.
+use casper_types::{Key, U512};
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::DICT_TOKEN_PAIRS,
+    utils::{get_dictionary_value_from_key, set_dictionary_value_for_key},
+};
+use crate::{
+    constants::{PAIR_RATE_PRECISION, TAX_RATE},
+    error::SwapError,
+    utils::tuple_typed,
+};
+#[cfg(feature = "contract-support")]
+use alloc::string::String;
+#[cfg(feature = "contract-support")]
+use casper_contract::{contract_api::runtime::blake2b, unwrap_or_revert::UnwrapOrRevert};
+#[cfg(feature = "contract-support")]
+use casper_types::bytesrepr::ToBytes;
+
+/// Directed pair, its input taken with `transfer_from` and its output paid with `transfer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenPair {
+    pub input_package: Key,
+    pub output_package: Key,
+    pub input_decimals: u8,
+    pub output_decimals: u8,
+    /// Whole output tokens per whole input token, scaled by [`PAIR_RATE_PRECISION`].
+    pub rate: U512,
+    /// Smallest input accepted, in the smallest unit of the input token.
+    pub min_input_amount: U512,
+    pub enabled: bool,
+}
+
+tuple_typed! {
+    TokenPair as ((Key, Key, u8), (u8, U512, U512), bool) {
+        input_package, output_package, input_decimals, output_decimals, rate, min_input_amount,
+        enabled
+    }
+}
+
+/// Quote of a token-to-token swap, amounts in the smallest unit of each token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenPairQuote {
+    pub output_amount: U512,
+    pub tax_amount: U512,
+}
+
+impl TokenPair {
+    /// Prices `input_amount` at the pair's flat rate, [`TAX_RATE`] being kept from the output.
+    pub fn quote(&self, input_amount: U512) -> Result<TokenPairQuote, SwapError> {
+        if input_amount < self.min_input_amount {
+            return Err(SwapError::BelowMinimumSwap);
+        }
+
+        let gross_amount = input_amount
+            .checked_mul(self.rate)
+            .and_then(|value| value.checked_mul(U512::exp10(self.output_decimals.into())))
+            .ok_or(SwapError::Overflow)?
+            .checked_div(
+                PAIR_RATE_PRECISION
+                    .checked_mul(U512::exp10(self.input_decimals.into()))
+                    .ok_or(SwapError::Overflow)?,
+            )
+            .ok_or(SwapError::InvalidRate)?;
+
+        let tax_amount = gross_amount
+            .checked_mul(TAX_RATE)
+            .and_then(|value| value.checked_div(U512::from(100)))
+            .ok_or(SwapError::InvalidRate)?;
+
+        let output_amount = gross_amount
+            .checked_sub(tax_amount)
+            .ok_or(SwapError::InvalidAmount)?;
+
+        Ok(TokenPairQuote {
+            output_amount,
+            tax_amount,
+        })
+    }
+}
+
+/// Hex blake2b hash of both package keys, which in full exceed the 128 byte item key limit.
+#[cfg(feature = "contract-support")]
+pub fn pair_item_key(input_package: &Key, output_package: &Key) -> String {
+    let mut bytes = input_package.to_bytes().unwrap_or_revert();
+    bytes.extend(output_package.to_bytes().unwrap_or_revert());
+    hex::encode(blake2b(bytes))
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_token_pair(input_package: &Key, output_package: &Key) -> Option<TokenPair> {
+    get_dictionary_value_from_key(
+        DICT_TOKEN_PAIRS,
+        &pair_item_key(input_package, output_package),
+    )
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_token_pair(pair: &TokenPair) {
+    set_dictionary_value_for_key(
+        DICT_TOKEN_PAIRS,
+        &pair_item_key(&pair.input_package, &pair.output_package),
+        pair,
+    )
+}
@@ -0,0 +1,204 @@
+//! Swap pools hosted by one package, each swapping CSPR for one CEP-18 token.
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{
+        ARG_END_TIME, ARG_POOL, ARG_POOL_TOKENS, ARG_START_TIME, ARG_TOKEN_DECIMALS,
+        DICT_SWAP_POOLS, RATE_TIERS, TAX_RATE,
+    },
+    error::SwapError,
+    utils::{
+        get_cep18_balance_for_key, get_cowl_cep18_contract_package, get_cowl_swap_contract_package,
+        get_dictionary_value_from_key, get_optional_named_arg_with_user_errors,
+        get_stored_value_with_user_errors, get_token_decimals, key_to_dictionary_item_key,
+        set_dictionary_value_for_key, set_stored_value,
+    },
+};
+use crate::{
+    rate::RateTier,
+    reserves::{RESERVE_ASSET_COWL, RESERVE_ASSET_CSPR},
+    utils::tuple_typed,
+};
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "contract-support")]
+use casper_contract::{contract_api::runtime::revert, unwrap_or_revert::UnwrapOrRevert};
+#[cfg(feature = "contract-support")]
+use casper_types::{ContractPackageHash, U256};
+use casper_types::{Key, U512};
+
+/// Item key of the pool of the configured token, which survives a change of that token.
+pub const DEFAULT_POOL_ITEM_KEY: &str = "default";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pool {
+    pub token_package: Key,
+    pub token_decimals: u8,
+    /// Tiers in increasing CSPR thresholds, the first one being the base rate.
+    pub rate_tiers: Vec<RateTier>,
+    /// Percentage kept from token → CSPR swaps.
+    pub tax_rate: U512,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub paused: bool,
+    /// Prefix of the pool's reserve counters and limits, empty for the default pool.
+    pub reserve_prefix: String,
+}
+
+impl Pool {
+    pub fn is_default(&self) -> bool {
+        self.reserve_prefix.is_empty()
+    }
+
+    /// Reserve asset of the CSPR side, e.g. `cspr` or `pool_1_cspr`.
+    pub fn cspr_asset(&self) -> String {
+        format!("{}{RESERVE_ASSET_CSPR}", self.reserve_prefix)
+    }
+
+    /// Reserve asset of the token side, e.g. `cowl` or `pool_1_cowl`.
+    pub fn token_asset(&self) -> String {
+        format!("{}{RESERVE_ASSET_COWL}", self.reserve_prefix)
+    }
+}
+
+#[cfg(feature = "contract-support")]
+impl Pool {
+    pub fn token_package_hash(&self) -> ContractPackageHash {
+        ContractPackageHash::from(
+            self.token_package
+                .into_hash()
+                .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
+        )
+    }
+
+    /// Token balance held by this package for the pool.
+    pub fn token_balance(&self) -> U256 {
+        get_cep18_balance_for_key(
+            self.token_package_hash(),
+            &get_cowl_swap_contract_package().into(),
+        )
+    }
+}
+
+tuple_typed! {
+    Pool as ((Key, u8, Vec<RateTier>), (U512, u64, u64), (bool, String)) {
+        token_package, token_decimals, rate_tiers, tax_rate, start_time, end_time, paused,
+        reserve_prefix
+    }
+}
+
+/// Pool of the configured token, whose package always follows the stored token package.
+#[cfg(feature = "contract-support")]
+pub fn get_default_pool() -> Pool {
+    let mut pool: Pool = get_dictionary_value_from_key(DICT_SWAP_POOLS, DEFAULT_POOL_ITEM_KEY)
+        .unwrap_or_revert_with(SwapError::MissingPool);
+    pool.token_package = get_cowl_cep18_contract_package().into();
+    pool
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_pool(token_package: &Key) -> Option<Pool> {
+    if *token_package == Key::from(get_cowl_cep18_contract_package()) {
+        return Some(get_default_pool());
+    }
+    get_dictionary_value_from_key(DICT_SWAP_POOLS, &key_to_dictionary_item_key(token_package))
+}
+
+/// Pool selected by the optional `pool` argument, the default pool when it is omitted.
+#[cfg(feature = "contract-support")]
+pub fn get_pool_arg() -> Pool {
+    match get_optional_named_arg_with_user_errors::<Key>(ARG_POOL, SwapError::InvalidPool) {
+        Some(token_package) => {
+            get_pool(&token_package).unwrap_or_revert_with(SwapError::MissingPool)
+        }
+        None => get_default_pool(),
+    }
+}
+
+/// Item key of the pool in the pool dictionaries.
+#[cfg(feature = "contract-support")]
+pub fn pool_item_key(pool: &Pool) -> String {
+    if pool.is_default() {
+        String::from(DEFAULT_POOL_ITEM_KEY)
+    } else {
+        key_to_dictionary_item_key(&pool.token_package)
+    }
+}
+
+/// Item key of an account in the per-pool dictionaries, prefixed with the pool's reserve prefix.
+#[cfg(feature = "contract-support")]
+pub fn account_item_key(pool: &Pool, account: &Key) -> String {
+    format!(
+        "{}{}",
+        pool.reserve_prefix,
+        key_to_dictionary_item_key(account)
+    )
+}
+
+/// Stores an existing pool, mirroring the default pool's window and decimals to named keys.
+#[cfg(feature = "contract-support")]
+pub fn save_pool(pool: &Pool) {
+    set_dictionary_value_for_key(DICT_SWAP_POOLS, &pool_item_key(pool), pool);
+    if pool.is_default() {
+        set_stored_value(ARG_START_TIME, pool.start_time);
+        set_stored_value(ARG_END_TIME, pool.end_time);
+        set_stored_value(ARG_TOKEN_DECIMALS, pool.token_decimals);
+    }
+}
+
+/// Token packages of the pools added next to the default one, in creation order.
+#[cfg(feature = "contract-support")]
+pub fn get_pool_tokens() -> Vec<Key> {
+    get_stored_value_with_user_errors(
+        ARG_POOL_TOKENS,
+        SwapError::MissingPoolTokens,
+        SwapError::InvalidPoolTokens,
+    )
+}
+
+/// Registers a new pool, its reserves are prefixed with its position in the pool list.
+#[cfg(feature = "contract-support")]
+pub fn insert_pool(mut pool: Pool) -> Pool {
+    if get_pool(&pool.token_package).is_some() {
+        revert(SwapError::PoolAlreadyExists);
+    }
+
+    let mut pool_tokens = get_pool_tokens();
+    pool_tokens.push(pool.token_package);
+    pool.reserve_prefix = format!("pool_{}_", pool_tokens.len());
+
+    save_pool(&pool);
+    set_stored_value(ARG_POOL_TOKENS, pool_tokens);
+    pool
+}
+
+/// Default pool followed by the added pools.
+#[cfg(feature = "contract-support")]
+pub fn all_pools() -> Vec<Pool> {
+    let mut pools = Vec::from([get_default_pool()]);
+    for token_package in get_pool_tokens() {
+        pools.push(get_pool(&token_package).unwrap_or_revert_with(SwapError::MissingPool));
+    }
+    pools
+}
+
+/// Default pool built from the named key window and decimals and the built-in tiers and tax.
+#[cfg(feature = "contract-support")]
+pub fn default_pool_from_named_keys() -> Pool {
+    Pool {
+        token_package: get_cowl_cep18_contract_package().into(),
+        token_decimals: get_token_decimals(),
+        rate_tiers: RATE_TIERS.to_vec(),
+        tax_rate: TAX_RATE,
+        start_time: get_stored_value_with_user_errors(
+            ARG_START_TIME,
+            SwapError::MissingStartTime,
+            SwapError::InvalidStartTime,
+        ),
+        end_time: get_stored_value_with_user_errors(
+            ARG_END_TIME,
+            SwapError::MissingEndTime,
+            SwapError::InvalidEndTime,
+        ),
+        paused: false,
+        reserve_prefix: String::new(),
+    }
+}
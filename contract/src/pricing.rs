@@ -0,0 +1,258 @@
+//! Pricing of swaps against CSPR, with the rate tiers of a pool, a constant-product curve over its
+//! reserves, an oracle reference price or a bonding curve.
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::DICT_POOL_PRICING_MODES,
+    curve::get_bonding_curve,
+    oracle::get_fresh_oracle_price,
+    pools::pool_item_key,
+    reserves::{expected_cowl_reserve, expected_cspr_reserve},
+    utils::{get_dictionary_value_from_key, set_dictionary_value_for_key, u256_to_u512},
+};
+use crate::{
+    constants::{MAX_BASIS_POINTS, MIN_SWAP_AMOUNT},
+    curve::BondingCurve,
+    enums::PricingMode,
+    error::SwapError,
+    oracle::ORACLE_PRICE_PRECISION,
+    pools::Pool,
+    rate::{cspr_to_token_amount, get_swap_rate, token_to_cspr_amount, validate_rate},
+};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::U512;
+
+/// Quote of a swap against CSPR, amounts in the smallest unit of each side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Tier rate the swap is priced at, zero in the other pricing modes.
+    pub base_rate: U512,
+    pub output_amount: U512,
+    /// Part of the output kept by the pool.
+    pub fee_amount: U512,
+}
+
+/// Output of an x*y=k swap of `input_amount` against the given reserves, rounded down so the
+/// product of the reserves never decreases.
+pub fn constant_product_amount(
+    input_amount: U512,
+    input_reserve: U512,
+    output_reserve: U512,
+) -> Result<U512, SwapError> {
+    if input_reserve.is_zero() || output_reserve.is_zero() {
+        return Err(SwapError::InsufficientLiquidity);
+    }
+    output_reserve
+        .checked_mul(input_amount)
+        .zip(input_reserve.checked_add(input_amount))
+        .and_then(|(numerator, denominator)| numerator.checked_div(denominator))
+        .ok_or(SwapError::Overflow)
+}
+
+fn with_fee(base_rate: U512, gross_amount: U512, fee_rate: U512) -> Result<SwapQuote, SwapError> {
+    let fee_amount = gross_amount
+        .checked_mul(fee_rate)
+        .and_then(|value| value.checked_div(U512::from(100)))
+        .ok_or(SwapError::InvalidRate)?;
+    let output_amount = gross_amount
+        .checked_sub(fee_amount)
+        .ok_or(SwapError::InvalidAmount)?;
+    Ok(SwapQuote {
+        base_rate,
+        output_amount,
+        fee_amount,
+    })
+}
+
+/// Prices `cspr_amount` motes in tokens of the pool.
+///
+/// Tiers keep the historical behaviour of an untaxed purchase, the curve keeps the pool tax from
+/// the tokens paid out. The oracle mode is priced with the tiers here, see
+/// [`price_cspr_to_token_at_oracle`] for a fresh price and [`price_cspr_to_token_on_curve`] for
+/// the bonding curve.
+pub fn price_cspr_to_token(
+    pool: &Pool,
+    pricing_mode: PricingMode,
+    cspr_amount: U512,
+    cspr_reserve: U512,
+    token_reserve: U512,
+) -> Result<SwapQuote, SwapError> {
+    match pricing_mode {
+        PricingMode::Tiers | PricingMode::Oracle => {
+            let base_rate = get_swap_rate(cspr_amount, &pool.rate_tiers)?;
+            let output_amount = cspr_to_token_amount(cspr_amount, base_rate, pool.token_decimals)
+                .ok_or(SwapError::Overflow)?;
+            with_fee(base_rate, output_amount, U512::zero())
+        }
+        PricingMode::ConstantProduct => {
+            if cspr_amount < MIN_SWAP_AMOUNT {
+                return Err(SwapError::BelowMinimumSwap);
+            }
+            let gross_amount = constant_product_amount(cspr_amount, cspr_reserve, token_reserve)?;
+            with_fee(U512::zero(), gross_amount, pool.tax_rate)
+        }
+        PricingMode::BondingCurve => Err(SwapError::MissingBondingCurve),
+    }
+}
+
+/// Prices `token_amount` tokens of the pool in motes, the pool tax being kept from the CSPR paid
+/// out in both modes.
+pub fn price_token_to_cspr(
+    pool: &Pool,
+    pricing_mode: PricingMode,
+    token_amount: U512,
+    cspr_reserve: U512,
+    token_reserve: U512,
+) -> Result<SwapQuote, SwapError> {
+    match pricing_mode {
+        PricingMode::Tiers | PricingMode::Oracle => {
+            let base_rate = pool.rate_tiers.first().ok_or(SwapError::InvalidRate)?.rate;
+            validate_rate(base_rate, &pool.rate_tiers)?;
+            let gross_amount = token_to_cspr_amount(token_amount, base_rate, pool.token_decimals)
+                .ok_or(SwapError::InvalidAmount)?;
+            with_fee(base_rate, gross_amount, pool.tax_rate)
+        }
+        PricingMode::ConstantProduct => {
+            let gross_amount = constant_product_amount(token_amount, token_reserve, cspr_reserve)?;
+            with_fee(U512::zero(), gross_amount, pool.tax_rate)
+        }
+        PricingMode::BondingCurve => Err(SwapError::MissingBondingCurve),
+    }
+}
+
+fn without_spread(amount: U512, spread: u32) -> Result<U512, SwapError> {
+    amount
+        .checked_mul(U512::from(MAX_BASIS_POINTS - spread))
+        .map(|value| value / MAX_BASIS_POINTS)
+        .ok_or(SwapError::Overflow)
+}
+
+/// Prices `cspr_amount` motes at an oracle `price`, the spread being kept from the tokens paid
+/// out. Like the tiers, the purchase is untaxed.
+pub fn price_cspr_to_token_at_oracle(
+    pool: &Pool,
+    cspr_amount: U512,
+    price: U512,
+    spread: u32,
+) -> Result<SwapQuote, SwapError> {
+    if cspr_amount < MIN_SWAP_AMOUNT {
+        return Err(SwapError::BelowMinimumSwap);
+    }
+    let gross_amount = cspr_to_token_amount(cspr_amount, price, pool.token_decimals)
+        .and_then(|value| value.checked_div(ORACLE_PRICE_PRECISION))
+        .ok_or(SwapError::Overflow)?;
+    with_fee(
+        U512::zero(),
+        without_spread(gross_amount, spread)?,
+        U512::zero(),
+    )
+}
+
+/// Prices `token_amount` tokens at an oracle `price`, the spread and then the pool tax being kept
+/// from the CSPR paid out.
+pub fn price_token_to_cspr_at_oracle(
+    pool: &Pool,
+    token_amount: U512,
+    price: U512,
+    spread: u32,
+) -> Result<SwapQuote, SwapError> {
+    let gross_amount = token_amount
+        .checked_mul(ORACLE_PRICE_PRECISION)
+        .and_then(|value| token_to_cspr_amount(value, price, pool.token_decimals))
+        .ok_or(SwapError::InvalidAmount)?;
+    with_fee(
+        U512::zero(),
+        without_spread(gross_amount, spread)?,
+        pool.tax_rate,
+    )
+}
+
+/// Prices `cspr_amount` motes along the bonding curve from the tokens already sold. Like the
+/// tiers, the purchase is untaxed.
+pub fn price_cspr_to_token_on_curve(
+    pool: &Pool,
+    curve: &BondingCurve,
+    cspr_amount: U512,
+) -> Result<SwapQuote, SwapError> {
+    if cspr_amount < MIN_SWAP_AMOUNT {
+        return Err(SwapError::BelowMinimumSwap);
+    }
+    let output_amount = curve.buy(cspr_amount, pool.token_decimals)?;
+    with_fee(U512::zero(), output_amount, U512::zero())
+}
+
+/// Prices `token_amount` tokens returned down the bonding curve, the pool tax being kept from the
+/// CSPR paid out.
+pub fn price_token_to_cspr_on_curve(
+    pool: &Pool,
+    curve: &BondingCurve,
+    token_amount: U512,
+) -> Result<SwapQuote, SwapError> {
+    let gross_amount = curve.sell(token_amount, pool.token_decimals)?;
+    with_fee(U512::zero(), gross_amount, pool.tax_rate)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_pricing_mode(pool: &Pool) -> PricingMode {
+    get_dictionary_value_from_key::<u8>(DICT_POOL_PRICING_MODES, &pool_item_key(pool))
+        .map(|value| PricingMode::try_from(value).unwrap_or_revert())
+        .unwrap_or(PricingMode::Tiers)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_pricing_mode(pool: &Pool, pricing_mode: PricingMode) {
+    set_dictionary_value_for_key(
+        DICT_POOL_PRICING_MODES,
+        &pool_item_key(pool),
+        &(pricing_mode as u8),
+    )
+}
+
+/// Accounted reserves of the pool, the token side in U512 for pricing.
+#[cfg(feature = "contract-support")]
+fn pricing_reserves(pool: &Pool) -> (U512, U512) {
+    (
+        expected_cspr_reserve(pool),
+        u256_to_u512(expected_cowl_reserve(pool)),
+    )
+}
+
+/// Quotes a CSPR → token swap with the pricing mode and current reserves of the pool.
+#[cfg(feature = "contract-support")]
+pub fn get_cspr_to_token_quote(pool: &Pool, cspr_amount: U512) -> Result<SwapQuote, SwapError> {
+    let pricing_mode = get_pricing_mode(pool);
+    if pricing_mode == PricingMode::Oracle {
+        if let Some((price, spread)) = get_fresh_oracle_price(pool) {
+            return price_cspr_to_token_at_oracle(pool, cspr_amount, price, spread);
+        }
+    }
+    if pricing_mode == PricingMode::BondingCurve {
+        let curve = get_bonding_curve(pool).ok_or(SwapError::MissingBondingCurve)?;
+        return price_cspr_to_token_on_curve(pool, &curve, cspr_amount);
+    }
+    let (cspr_reserve, token_reserve) = pricing_reserves(pool);
+    price_cspr_to_token(pool, pricing_mode, cspr_amount, cspr_reserve, token_reserve)
+}
+
+/// Quotes a token → CSPR swap with the pricing mode and current reserves of the pool.
+#[cfg(feature = "contract-support")]
+pub fn get_token_to_cspr_quote(pool: &Pool, token_amount: U512) -> Result<SwapQuote, SwapError> {
+    let pricing_mode = get_pricing_mode(pool);
+    if pricing_mode == PricingMode::Oracle {
+        if let Some((price, spread)) = get_fresh_oracle_price(pool) {
+            return price_token_to_cspr_at_oracle(pool, token_amount, price, spread);
+        }
+    }
+    if pricing_mode == PricingMode::BondingCurve {
+        let curve = get_bonding_curve(pool).ok_or(SwapError::MissingBondingCurve)?;
+        return price_token_to_cspr_on_curve(pool, &curve, token_amount);
+    }
+    let (cspr_reserve, token_reserve) = pricing_reserves(pool);
+    price_token_to_cspr(
+        pool,
+        pricing_mode,
+        token_amount,
+        cspr_reserve,
+        token_reserve,
+    )
+}
@@ -1,25 +1,55 @@
+#[cfg(feature = "contract-support")]
+use crate::pools::Pool;
 use crate::{
-    constants::{MIN_SWAP_AMOUNT, RATE_TIERS},
+    constants::{CSPR_DECIMALS, MAX_TAX_RATE, MIN_SWAP_AMOUNT},
     error::SwapError,
+    utils::tuple_typed,
 };
 use casper_types::U512;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct RateTier {
     pub cspr_amount: U512,
     pub rate: U512,
 }
 
-pub fn validate_rate(rate: U512) -> Result<(), SwapError> {
+tuple_typed! {
+    RateTier as (U512, U512) {
+        cspr_amount, rate
+    }
+}
+
+pub fn validate_rate(rate: U512, rate_tiers: &[RateTier]) -> Result<(), SwapError> {
     if rate.is_zero() {
         return Err(SwapError::InvalidRate);
     }
-    if !RATE_TIERS.iter().any(|tier| tier.rate == rate) {
+    if !rate_tiers.iter().any(|tier| tier.rate == rate) {
         return Err(SwapError::InvalidRate);
     }
     Ok(())
 }
 
+/// Checks a pool's tiers: at least one, no zero rate and strictly increasing thresholds.
+pub fn validate_rate_tiers(rate_tiers: &[RateTier]) -> Result<(), SwapError> {
+    if rate_tiers.is_empty() || rate_tiers.iter().any(|tier| tier.rate.is_zero()) {
+        return Err(SwapError::InvalidRateTiers);
+    }
+    if rate_tiers
+        .windows(2)
+        .any(|tiers| tiers[0].cspr_amount >= tiers[1].cspr_amount)
+    {
+        return Err(SwapError::InvalidRateTiers);
+    }
+    Ok(())
+}
+
+pub fn validate_tax_rate(tax_rate: U512) -> Result<(), SwapError> {
+    if tax_rate > MAX_TAX_RATE {
+        return Err(SwapError::InvalidTaxRate);
+    }
+    Ok(())
+}
+
 pub fn validate_amount<T>(amount: T) -> Result<(), SwapError>
 where
     T: PartialEq + From<u8> + Copy,
@@ -30,47 +60,55 @@ where
     Ok(())
 }
 
-/// Get the swap rate based on the CSPR amount.
-pub fn get_swap_rate(cspr_amount: U512) -> Result<U512, SwapError> {
+/// Get the swap rate of `rate_tiers` based on the CSPR amount.
+pub fn get_swap_rate(cspr_amount: U512, rate_tiers: &[RateTier]) -> Result<U512, SwapError> {
     if cspr_amount < MIN_SWAP_AMOUNT {
         return Err(SwapError::BelowMinimumSwap);
     }
 
     // Find the appropriate rate tier.
-    let rate = RATE_TIERS
+    let rate = rate_tiers
         .iter()
         .rev()
         .find(|tier| cspr_amount >= tier.cspr_amount)
-        .map(|tier| tier.rate)
-        .unwrap_or_else(|| RATE_TIERS.first().unwrap().rate); // Default to the base rate if no match.
+        .or_else(|| rate_tiers.first()) // Default to the base rate if no match.
+        .ok_or(SwapError::InvalidRate)?
+        .rate;
 
-    validate_rate(rate)?;
+    validate_rate(rate, rate_tiers)?;
     Ok(rate)
 }
 
+/// Smallest token units bought with `cspr_amount` motes at `rate` whole tokens per CSPR.
+pub fn cspr_to_token_amount(cspr_amount: U512, rate: U512, token_decimals: u8) -> Option<U512> {
+    cspr_amount
+        .checked_mul(rate)?
+        .checked_mul(U512::exp10(token_decimals.into()))?
+        .checked_div(U512::exp10(CSPR_DECIMALS.into()))
+}
+
+/// Motes paid for `token_amount` smallest token units at `rate` whole tokens per CSPR.
+pub fn token_to_cspr_amount(token_amount: U512, rate: U512, token_decimals: u8) -> Option<U512> {
+    token_amount
+        .checked_mul(U512::exp10(CSPR_DECIMALS.into()))?
+        .checked_div(rate.checked_mul(U512::exp10(token_decimals.into()))?)
+}
+
 #[cfg(feature = "contract-support")]
-pub fn verify_swap_active() -> Result<(), SwapError> {
-    use crate::constants::{ARG_END_TIME, ARG_START_TIME};
-    use crate::utils::{get_current_time_in_seconds, get_stored_value_with_user_errors};
+pub fn verify_swap_active(pool: &Pool) -> Result<(), SwapError> {
+    use crate::utils::get_current_time_in_seconds;
 
-    let current_time = get_current_time_in_seconds();
+    if pool.paused {
+        return Err(SwapError::PoolPaused);
+    }
 
-    let start_time: u64 = get_stored_value_with_user_errors(
-        ARG_START_TIME,
-        SwapError::MissingStartTime,
-        SwapError::InvalidStartTime,
-    );
-    let end_time: u64 = get_stored_value_with_user_errors(
-        ARG_END_TIME,
-        SwapError::MissingEndTime,
-        SwapError::InvalidEndTime,
-    );
+    let current_time = get_current_time_in_seconds();
 
     // Check if the current time falls within the swap window
-    if current_time < start_time {
+    if current_time < pool.start_time {
         return Err(SwapError::SwapNotActive);
     }
-    if current_time > end_time {
+    if current_time > pool.end_time {
         return Err(SwapError::SwapExpired);
     }
 
@@ -0,0 +1,57 @@
+//! COWL bonus paid to the referrer of a CSPR → token purchase.
+use casper_types::U256;
+
+use crate::{constants::MAX_BASIS_POINTS, error::SwapError};
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{ARG_REFERRAL_BONUS, DICT_REFERRALS},
+    pools::{account_item_key, Pool},
+    utils::{
+        get_dictionary_value_from_key, get_stored_value_with_user_errors,
+        set_dictionary_value_for_key,
+    },
+};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+#[cfg(feature = "contract-support")]
+use casper_types::Key;
+
+pub fn validate_referral_bonus(referral_bonus: u32) -> Result<(), SwapError> {
+    if referral_bonus > MAX_BASIS_POINTS {
+        return Err(SwapError::InvalidReferralBonus);
+    }
+    Ok(())
+}
+
+/// Bonus earned by the referrer of a purchase of `token_amount`, `referral_bonus` being in basis
+/// points.
+pub fn referral_reward(token_amount: U256, referral_bonus: u32) -> Result<U256, SwapError> {
+    token_amount
+        .checked_mul(U256::from(referral_bonus))
+        .map(|value| value / MAX_BASIS_POINTS)
+        .ok_or(SwapError::Overflow)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_referral_bonus() -> u32 {
+    get_stored_value_with_user_errors(
+        ARG_REFERRAL_BONUS,
+        SwapError::MissingReferralBonus,
+        SwapError::InvalidReferralBonus,
+    )
+}
+
+/// Tokens of the pool earned by `referrer` so far.
+#[cfg(feature = "contract-support")]
+pub fn get_referral_total(pool: &Pool, referrer: &Key) -> U256 {
+    get_dictionary_value_from_key(DICT_REFERRALS, &account_item_key(pool, referrer))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn add_referral_reward(pool: &Pool, referrer: &Key, reward: U256) {
+    let total = get_referral_total(pool, referrer)
+        .checked_add(reward)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_dictionary_value_for_key(DICT_REFERRALS, &account_item_key(pool, referrer), &total)
+}
@@ -0,0 +1,276 @@
+//! Internal reserve accounting, tracked separately from the raw purse and CEP-18 balances.
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{
+        ARG_MIN_RESERVE_COWL, ARG_MIN_RESERVE_CSPR, ARG_WARNING_RESERVE_COWL,
+        ARG_WARNING_RESERVE_CSPR, DICT_RESERVES,
+    },
+    error::SwapError,
+    events::{record_event_dictionary, CowlReserveLow, CsprReserveLow, Event, EventHeader},
+    pools::Pool,
+    utils::{
+        get_dictionary_value_from_key, get_stored_value_with_user_errors,
+        set_dictionary_value_for_key, set_stored_value,
+    },
+};
+#[cfg(feature = "contract-support")]
+use alloc::{collections::BTreeMap, string::ToString};
+use alloc::{format, string::String};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+#[cfg(feature = "contract-support")]
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, U256, U512,
+};
+
+pub const RESERVE_ASSET_CSPR: &str = "cspr";
+pub const RESERVE_ASSET_COWL: &str = "cowl";
+/// Tokens bought but still held by the contract until their buyers claim them.
+pub const RESERVE_LOCKED: &str = "locked";
+/// CSPR of the purse shared by all pools, counted apart from theirs, e.g. `package_cspr_swept`.
+pub const RESERVE_ASSET_PACKAGE_CSPR: &str = "package_cspr";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReserveCounter {
+    Deposited,
+    SwappedIn,
+    SwappedOut,
+    Fees,
+    Withdrawn,
+    Swept,
+}
+
+impl ReserveCounter {
+    pub const ALL: [ReserveCounter; 6] = [
+        ReserveCounter::Deposited,
+        ReserveCounter::SwappedIn,
+        ReserveCounter::SwappedOut,
+        ReserveCounter::Fees,
+        ReserveCounter::Withdrawn,
+        ReserveCounter::Swept,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReserveCounter::Deposited => "deposited",
+            ReserveCounter::SwappedIn => "swapped_in",
+            ReserveCounter::SwappedOut => "swapped_out",
+            ReserveCounter::Fees => "fees",
+            ReserveCounter::Withdrawn => "withdrawn",
+            ReserveCounter::Swept => "swept",
+        }
+    }
+}
+
+/// Dictionary item key of a reserve counter, e.g. `cspr_deposited`.
+pub fn reserve_item_key(asset: &str, counter: ReserveCounter) -> String {
+    format!("{asset}_{}", counter.name())
+}
+
+#[cfg(feature = "contract-support")]
+fn get_reserve<T>(asset: &str, counter: ReserveCounter) -> T
+where
+    T: CLTyped + FromBytes + Default,
+{
+    get_dictionary_value_from_key(DICT_RESERVES, &reserve_item_key(asset, counter))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+fn set_reserve<T>(asset: &str, counter: ReserveCounter, value: &T)
+where
+    T: CLTyped + ToBytes + Clone,
+{
+    set_dictionary_value_for_key(DICT_RESERVES, &reserve_item_key(asset, counter), value)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_cspr_reserve(pool: &Pool, counter: ReserveCounter) -> U512 {
+    get_reserve(&pool.cspr_asset(), counter)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_cowl_reserve(pool: &Pool, counter: ReserveCounter) -> U256 {
+    get_reserve(&pool.token_asset(), counter)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn add_cspr_reserve(pool: &Pool, counter: ReserveCounter, amount: U512) {
+    let value = get_cspr_reserve(pool, counter)
+        .checked_add(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_reserve(&pool.cspr_asset(), counter, &value);
+}
+
+#[cfg(feature = "contract-support")]
+pub fn add_cowl_reserve(pool: &Pool, counter: ReserveCounter, amount: U256) {
+    let value = get_cowl_reserve(pool, counter)
+        .checked_add(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_reserve(&pool.token_asset(), counter, &value);
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_package_cspr_reserve(counter: ReserveCounter) -> U512 {
+    get_reserve(RESERVE_ASSET_PACKAGE_CSPR, counter)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn add_package_cspr_reserve(counter: ReserveCounter, amount: U512) {
+    let value = get_package_cspr_reserve(counter)
+        .checked_add(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_reserve(RESERVE_ASSET_PACKAGE_CSPR, counter, &value);
+}
+
+/// Tokens of the pool held for buyers. They are already counted as swapped out, so they are not
+/// part of the expected reserve while still being in the contract's balance.
+#[cfg(feature = "contract-support")]
+pub fn get_locked_cowl(pool: &Pool) -> U256 {
+    get_dictionary_value_from_key(DICT_RESERVES, &locked_item_key(pool)).unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+fn locked_item_key(pool: &Pool) -> String {
+    format!("{}_{RESERVE_LOCKED}", pool.token_asset())
+}
+
+#[cfg(feature = "contract-support")]
+pub fn lock_cowl(pool: &Pool, amount: U256) {
+    let value = get_locked_cowl(pool)
+        .checked_add(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_dictionary_value_for_key(DICT_RESERVES, &locked_item_key(pool), &value);
+}
+
+#[cfg(feature = "contract-support")]
+pub fn unlock_cowl(pool: &Pool, amount: U256) {
+    let value = get_locked_cowl(pool)
+        .checked_sub(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    set_dictionary_value_for_key(DICT_RESERVES, &locked_item_key(pool), &value);
+}
+
+/// Reads a reserve limit of the pool. The default pool keeps its limits in named keys, the other
+/// pools in the reserves dictionary under their prefix, unset limits being zero.
+#[cfg(feature = "contract-support")]
+pub fn get_reserve_limit<T>(pool: &Pool, name: &str) -> T
+where
+    T: CLTyped + FromBytes + Default,
+{
+    if pool.is_default() {
+        get_stored_value_with_user_errors(
+            name,
+            SwapError::MissingReserveLimit,
+            SwapError::InvalidReserveLimit,
+        )
+    } else {
+        get_dictionary_value_from_key(DICT_RESERVES, &format!("{}{name}", pool.reserve_prefix))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn set_reserve_limit<T>(pool: &Pool, name: &str, value: T)
+where
+    T: CLTyped + ToBytes + Clone,
+{
+    if pool.is_default() {
+        set_stored_value(name, value);
+    } else {
+        set_dictionary_value_for_key(
+            DICT_RESERVES,
+            &format!("{}{name}", pool.reserve_prefix),
+            &value,
+        );
+    }
+}
+
+/// CSPR the contract should hold according to its own accounting.
+///
+/// Fees are informational only: the tax is already retained because `swapped_out` is recorded net.
+#[cfg(feature = "contract-support")]
+pub fn expected_cspr_reserve(pool: &Pool) -> U512 {
+    get_cspr_reserve(pool, ReserveCounter::Deposited)
+        .saturating_add(get_cspr_reserve(pool, ReserveCounter::SwappedIn))
+        .saturating_sub(
+            get_cspr_reserve(pool, ReserveCounter::SwappedOut)
+                .saturating_add(get_cspr_reserve(pool, ReserveCounter::Withdrawn)),
+        )
+}
+
+/// COWL the contract should hold according to its own accounting.
+#[cfg(feature = "contract-support")]
+pub fn expected_cowl_reserve(pool: &Pool) -> U256 {
+    get_cowl_reserve(pool, ReserveCounter::Deposited)
+        .saturating_add(get_cowl_reserve(pool, ReserveCounter::SwappedIn))
+        .saturating_sub(
+            get_cowl_reserve(pool, ReserveCounter::SwappedOut)
+                .saturating_add(get_cowl_reserve(pool, ReserveCounter::Withdrawn)),
+        )
+}
+
+#[cfg(feature = "contract-support")]
+pub fn cspr_reserves_breakdown(pool: &Pool) -> BTreeMap<String, U512> {
+    ReserveCounter::ALL
+        .iter()
+        .map(|counter| (counter.name().to_string(), get_cspr_reserve(pool, *counter)))
+        .collect()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn cowl_reserves_breakdown(pool: &Pool) -> BTreeMap<String, U256> {
+    ReserveCounter::ALL
+        .iter()
+        .map(|counter| (counter.name().to_string(), get_cowl_reserve(pool, *counter)))
+        .collect()
+}
+
+/// Checks that paying out `amount` CSPR keeps the accounted reserve at or above the stored floor.
+#[cfg(feature = "contract-support")]
+pub fn verify_cspr_liquidity(pool: &Pool, reserve: U512, amount: U512) -> Result<(), SwapError> {
+    let min_reserve: U512 = get_reserve_limit(pool, ARG_MIN_RESERVE_CSPR);
+    match reserve.checked_sub(amount) {
+        Some(remaining) if remaining >= min_reserve => Ok(()),
+        _ => Err(SwapError::InsufficientLiquidity),
+    }
+}
+
+/// Checks that paying out `amount` COWL keeps the accounted reserve at or above the stored floor.
+#[cfg(feature = "contract-support")]
+pub fn verify_cowl_liquidity(pool: &Pool, reserve: U256, amount: U256) -> Result<(), SwapError> {
+    let min_reserve: U256 = get_reserve_limit(pool, ARG_MIN_RESERVE_COWL);
+    match reserve.checked_sub(amount) {
+        Some(remaining) if remaining >= min_reserve => Ok(()),
+        _ => Err(SwapError::InsufficientLiquidity),
+    }
+}
+
+/// Emits `CsprReserveLow` when the reserve drops below the warning threshold.
+#[cfg(feature = "contract-support")]
+pub fn record_cspr_reserve_warning(pool: &Pool, reserve_before: U512, reserve_after: U512) {
+    let threshold: U512 = get_reserve_limit(pool, ARG_WARNING_RESERVE_CSPR);
+    if reserve_before >= threshold && reserve_after < threshold {
+        record_event_dictionary(Event::CsprReserveLow(CsprReserveLow {
+            header: EventHeader::next(),
+            pool: pool.token_package,
+            reserve: reserve_after,
+            threshold,
+        }));
+    }
+}
+
+/// Emits `CowlReserveLow` when the reserve drops below the warning threshold.
+#[cfg(feature = "contract-support")]
+pub fn record_cowl_reserve_warning(pool: &Pool, reserve_before: U256, reserve_after: U256) {
+    let threshold: U256 = get_reserve_limit(pool, ARG_WARNING_RESERVE_COWL);
+    if reserve_before >= threshold && reserve_after < threshold {
+        record_event_dictionary(Event::CowlReserveLow(CowlReserveLow {
+            header: EventHeader::next(),
+            pool: pool.token_package,
+            reserve: reserve_after,
+            threshold,
+        }));
+    }
+}
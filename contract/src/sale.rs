@@ -0,0 +1,164 @@
+//! Soft cap of a pool's fundraising sale.
+use casper_types::U512;
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{DICT_CONTRIBUTIONS, DICT_SALES},
+    events::{record_event_dictionary, Event, EventHeader, SaleStateUpdate},
+    pools::{account_item_key, all_pools, pool_item_key, Pool},
+    utils::{
+        get_contract_cspr_balance, get_current_time_in_seconds, get_dictionary_value_from_key,
+        set_dictionary_value_for_key,
+    },
+};
+use crate::{enums::SaleState, error::SwapError, utils::tuple_typed};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+#[cfg(feature = "contract-support")]
+use casper_types::{Key, U256};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Sale {
+    pub state: SaleState,
+    pub soft_cap: U512,
+    pub raised: U512,
+    pub refunded: U512,
+}
+
+impl Sale {
+    /// Sets the soft cap of a sale that has not ended, returning whether its state changed.
+    pub fn set_soft_cap(&mut self, soft_cap: U512) -> Result<bool, SwapError> {
+        if soft_cap.is_zero() {
+            return Err(SwapError::InvalidSoftCap);
+        }
+        if !matches!(self.state, SaleState::Open | SaleState::Raising) {
+            return Err(SwapError::InvalidSaleState);
+        }
+        let previous_state = self.state;
+        self.soft_cap = soft_cap;
+        self.state = if self.raised >= soft_cap {
+            SaleState::Succeeded
+        } else {
+            SaleState::Raising
+        };
+        Ok(self.state != previous_state)
+    }
+
+    /// Records `cspr_amount` raised, returning whether it met the soft cap.
+    pub fn contribute(&mut self, cspr_amount: U512) -> Result<bool, SwapError> {
+        if self.state != SaleState::Raising {
+            return Err(SwapError::InvalidSaleState);
+        }
+        self.raised = self
+            .raised
+            .checked_add(cspr_amount)
+            .ok_or(SwapError::Overflow)?;
+        if self.raised < self.soft_cap {
+            return Ok(false);
+        }
+        self.state = SaleState::Succeeded;
+        Ok(true)
+    }
+
+    /// Fails a sale still raising after `end_time`, returning whether its state changed.
+    pub fn settle(&mut self, current_time: u64, end_time: u64) -> bool {
+        if self.state != SaleState::Raising || current_time <= end_time {
+            return false;
+        }
+        self.state = SaleState::Failed;
+        true
+    }
+
+    /// Raised CSPR the contract must keep for refunds.
+    pub fn held(&self) -> U512 {
+        match self.state {
+            SaleState::Raising | SaleState::Failed => self.raised.saturating_sub(self.refunded),
+            SaleState::Open | SaleState::Succeeded => U512::zero(),
+        }
+    }
+}
+
+tuple_typed! {
+    Sale as ((u8, U512), (U512, U512)) {
+        state, soft_cap, raised, refunded
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_sale(pool: &Pool) -> Sale {
+    get_dictionary_value_from_key(DICT_SALES, &pool_item_key(pool)).unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_sale(pool: &Pool, sale: &Sale) {
+    set_dictionary_value_for_key(DICT_SALES, &pool_item_key(pool), sale)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn record_sale_state(pool: &Pool, sale: &Sale) {
+    record_event_dictionary(Event::SaleStateUpdate(SaleStateUpdate {
+        header: EventHeader::next(),
+        pool: pool.token_package,
+        state: sale.state as u8,
+        soft_cap: sale.soft_cap,
+        raised: sale.raised,
+    }));
+}
+
+/// Sale of the pool, failed and saved first if it ended below its soft cap.
+#[cfg(feature = "contract-support")]
+pub fn get_settled_sale(pool: &Pool) -> Sale {
+    let mut sale = get_sale(pool);
+    if sale.settle(get_current_time_in_seconds(), pool.end_time) {
+        save_sale(pool, &sale);
+        record_sale_state(pool, &sale);
+    }
+    sale
+}
+
+/// Whether paying `cspr_amount` out of the purse leaves the CSPR held for refunds by every pool.
+#[cfg(feature = "contract-support")]
+pub fn keeps_raised_cspr(cspr_amount: U512) -> bool {
+    let held = all_pools()
+        .iter()
+        .map(|pool| get_sale(pool).held())
+        .fold(U512::zero(), |total, held| total.saturating_add(held));
+    match get_contract_cspr_balance().checked_sub(cspr_amount) {
+        Some(remaining) => remaining >= held,
+        None => false,
+    }
+}
+
+/// CSPR paid by `buyer` while the pool raised, the tokens credited and those from the curve.
+#[cfg(feature = "contract-support")]
+pub fn get_contribution(pool: &Pool, buyer: &Key) -> (U512, U256, U256) {
+    get_dictionary_value_from_key(DICT_CONTRIBUTIONS, &account_item_key(pool, buyer))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_contribution(pool: &Pool, buyer: &Key, contribution: (U512, U256, U256)) {
+    set_dictionary_value_for_key(
+        DICT_CONTRIBUTIONS,
+        &account_item_key(pool, buyer),
+        &contribution,
+    )
+}
+
+#[cfg(feature = "contract-support")]
+pub fn add_contribution(
+    pool: &Pool,
+    buyer: &Key,
+    cspr_amount: U512,
+    token_amount: U256,
+    curve_amount: U256,
+) {
+    let (cspr_total, token_total, curve_total) = get_contribution(pool, buyer);
+    let contribution = cspr_total
+        .checked_add(cspr_amount)
+        .zip(token_total.checked_add(token_amount))
+        .zip(curve_total.checked_add(curve_amount))
+        .map(|((cspr_total, token_total), curve_total)| (cspr_total, token_total, curve_total))
+        .unwrap_or_revert_with(SwapError::Overflow);
+    save_contribution(pool, buyer, contribution);
+}
@@ -21,6 +21,8 @@ use crate::{
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum SecurityBadge {
     Admin = 0,
+    /// Pushes oracle prices, see [`crate::oracle`].
+    OracleUpdater = 1,
     None = 99,
 }
 
@@ -45,6 +47,7 @@ impl FromBytes for SecurityBadge {
         Ok((
             match bytes[0] {
                 0 => SecurityBadge::Admin,
+                1 => SecurityBadge::OracleUpdater,
                 99 => SecurityBadge::None,
                 _ => return Err(bytesrepr::Error::LeftOverBytes),
             },
@@ -0,0 +1,84 @@
+//! Calls shared by the session wasms.
+#[cfg(feature = "contract-support")]
+use casper_contract::{
+    contract_api::runtime::{call_versioned_contract, get_caller},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+#[cfg(feature = "contract-support")]
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U256};
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{
+        ARG_AMOUNT, ARG_OWNER, ARG_POOL, ARG_SPENDER, ENTRY_POINT_ALLOWANCE, ENTRY_POINT_APPROVE,
+    },
+    error::SwapError,
+};
+
+/// Sells `amount` tokens of the pool of `token_contract_package_key` through `entry_point` of the
+/// swap package, approving the amount first when the caller has no allowance for it yet.
+#[cfg(feature = "contract-support")]
+pub fn sell_token(
+    entry_point: &str,
+    token_contract_package_key: Key,
+    cowl_swap_contract_package_key: Key,
+    amount: U256,
+) {
+    let token_contract_package = ContractPackageHash::from(
+        token_contract_package_key
+            .into_hash()
+            .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
+    );
+
+    let caller = Key::from(get_caller());
+
+    let current_allowance = call_versioned_contract::<U256>(
+        token_contract_package,
+        None,
+        ENTRY_POINT_ALLOWANCE,
+        runtime_args! {
+            ARG_OWNER => caller,
+            ARG_SPENDER => cowl_swap_contract_package_key,
+        },
+    );
+
+    if current_allowance.is_zero() {
+        call_versioned_contract::<()>(
+            token_contract_package,
+            None,
+            ENTRY_POINT_APPROVE,
+            runtime_args! {
+                ARG_SPENDER => cowl_swap_contract_package_key,
+                ARG_AMOUNT => amount
+            },
+        );
+    }
+
+    let current_allowance = call_versioned_contract::<U256>(
+        token_contract_package,
+        None,
+        ENTRY_POINT_ALLOWANCE,
+        runtime_args! {
+            ARG_OWNER => caller,
+            ARG_SPENDER => cowl_swap_contract_package_key,
+        },
+    );
+
+    if current_allowance >= amount {
+        let cowl_swap_contract_package_key_hash = ContractPackageHash::from(
+            cowl_swap_contract_package_key
+                .into_hash()
+                .unwrap_or_revert_with(SwapError::InvalidPackageHash),
+        );
+
+        call_versioned_contract::<()>(
+            cowl_swap_contract_package_key_hash,
+            None,
+            entry_point,
+            runtime_args! {
+                ARG_AMOUNT => amount,
+                ARG_POOL => token_contract_package_key
+            },
+        );
+    }
+}
@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+extern crate alloc;
+
+use casper_contract::contract_api::runtime::{get_named_arg, revert};
+use casper_types::{Key, U256};
+use cowl_swap::{
+    constants::{
+        ARG_AMOUNT, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_TOKEN_CONTRACT_PACKAGE,
+        ENTRY_POINT_TOKEN_TO_CSPR,
+    },
+    error::SwapError,
+    sessions::sell_token,
+};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U256 = get_named_arg(ARG_AMOUNT);
+    if amount == U256::zero() {
+        revert(SwapError::InvalidAmount);
+    }
+
+    let token_contract_package_key: Key = get_named_arg(ARG_TOKEN_CONTRACT_PACKAGE);
+    let cowl_swap_contract_package_key: Key = get_named_arg(ARG_COWL_SWAP_CONTRACT_PACKAGE);
+
+    sell_token(
+        ENTRY_POINT_TOKEN_TO_CSPR,
+        token_contract_package_key,
+        cowl_swap_contract_package_key,
+        amount,
+    );
+}
@@ -0,0 +1,80 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+extern crate alloc;
+
+use casper_contract::{
+    contract_api::runtime::{call_versioned_contract, get_caller, get_named_arg, revert},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, U256};
+use cowl_swap::{
+    constants::{
+        ARG_AMOUNT, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_INPUT_TOKEN_PACKAGE,
+        ARG_OUTPUT_TOKEN_PACKAGE, ARG_OWNER, ARG_SPENDER, ENTRY_POINT_ALLOWANCE,
+        ENTRY_POINT_APPROVE, ENTRY_POINT_TOKEN_TO_TOKEN,
+    },
+    error::SwapError,
+};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U256 = get_named_arg(ARG_AMOUNT);
+    if amount == U256::zero() {
+        revert(SwapError::InvalidAmount);
+    }
+
+    let input_token_package_key: Key = get_named_arg(ARG_INPUT_TOKEN_PACKAGE);
+    let output_token_package_key: Key = get_named_arg(ARG_OUTPUT_TOKEN_PACKAGE);
+
+    let input_token_package = ContractPackageHash::from(
+        input_token_package_key
+            .into_hash()
+            .unwrap_or_revert_with(SwapError::InvalidTokenContractPackage),
+    );
+
+    let cowl_swap_contract_package_key: Key = get_named_arg(ARG_COWL_SWAP_CONTRACT_PACKAGE);
+
+    let caller = Key::from(get_caller());
+
+    let current_allowance = call_versioned_contract::<U256>(
+        input_token_package,
+        None,
+        ENTRY_POINT_ALLOWANCE,
+        runtime_args! {
+            ARG_OWNER => caller,
+            ARG_SPENDER => cowl_swap_contract_package_key,
+        },
+    );
+
+    if current_allowance < amount {
+        call_versioned_contract::<()>(
+            input_token_package,
+            None,
+            ENTRY_POINT_APPROVE,
+            runtime_args! {
+                ARG_SPENDER => cowl_swap_contract_package_key,
+                ARG_AMOUNT => amount
+            },
+        );
+    }
+
+    let cowl_swap_contract_package_key_hash = ContractPackageHash::from(
+        cowl_swap_contract_package_key
+            .into_hash()
+            .unwrap_or_revert_with(SwapError::InvalidPackageHash),
+    );
+
+    call_versioned_contract::<U256>(
+        cowl_swap_contract_package_key_hash,
+        None,
+        ENTRY_POINT_TOKEN_TO_TOKEN,
+        runtime_args! {
+            ARG_INPUT_TOKEN_PACKAGE => input_token_package_key,
+            ARG_OUTPUT_TOKEN_PACKAGE => output_token_package_key,
+            ARG_AMOUNT => amount
+        },
+    );
+}
@@ -11,7 +11,7 @@ use casper_types::{
     account::AccountHash,
     api_error::result_from,
     bytesrepr::{self, FromBytes, ToBytes},
-    CLTyped, ContractHash, ContractPackageHash, Key, URef, U256,
+    CLTyped, ContractHash, ContractPackageHash, Key, URef, U256, U512,
 };
 
 #[cfg(feature = "contract-support")]
@@ -51,6 +51,24 @@ pub fn get_verified_caller() -> (Key, Option<Key>) {
     }
 }
 
+/// Contract hash and package of the contract currently executing.
+#[cfg(feature = "contract-support")]
+pub fn get_executing_contract() -> (ContractHash, ContractPackageHash) {
+    use casper_contract::{
+        contract_api::runtime::{get_call_stack, revert},
+        unwrap_or_revert::UnwrapOrRevert,
+    };
+    use casper_types::system::CallStackElement;
+
+    match get_call_stack().last().unwrap_or_revert() {
+        CallStackElement::StoredContract {
+            contract_hash,
+            contract_package_hash,
+        } => (*contract_hash, *contract_package_hash),
+        _ => revert(SwapError::InvalidContractHash),
+    }
+}
+
 #[cfg(feature = "contract-support")]
 pub fn get_stored_value<T>(name: &str) -> T
 where
@@ -339,6 +357,17 @@ pub fn get_cowl_cep18_contract_package() -> ContractPackageHash {
     )
 }
 
+#[cfg(feature = "contract-support")]
+pub fn get_token_decimals() -> u8 {
+    use crate::constants::ARG_TOKEN_DECIMALS;
+
+    get_stored_value_with_user_errors(
+        ARG_TOKEN_DECIMALS,
+        SwapError::MissingTokenDecimals,
+        SwapError::InvalidTokenDecimals,
+    )
+}
+
 #[cfg(feature = "contract-support")]
 pub fn get_cowl_swap_contract_package() -> ContractPackageHash {
     use casper_contract::unwrap_or_revert::UnwrapOrRevert;
@@ -360,11 +389,15 @@ pub fn get_cowl_swap_contract_package() -> ContractPackageHash {
 
 #[cfg(feature = "contract-support")]
 pub fn get_cowl_cep18_balance_for_key(owner: &Key) -> U256 {
+    get_cep18_balance_for_key(get_cowl_cep18_contract_package(), owner)
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_cep18_balance_for_key(contract_package_hash: ContractPackageHash, owner: &Key) -> U256 {
     use casper_contract::contract_api::runtime::call_versioned_contract;
     use casper_types::{runtime_args, RuntimeArgs};
 
     use crate::constants::{ARG_ADDRESS, ENTRY_POINT_BALANCE_OF};
-    let contract_package_hash = get_cowl_cep18_contract_package();
 
     call_versioned_contract(
         contract_package_hash,
@@ -381,3 +414,136 @@ pub fn get_current_time_in_seconds() -> u64 {
     let current_time_in_ms: u64 = get_blocktime().into();
     current_time_in_ms.checked_div(1000).unwrap_or_revert()
 }
+
+#[cfg(feature = "contract-support")]
+pub fn key_to_dictionary_item_key(key: &Key) -> String {
+    use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+
+    hex::encode(key.to_bytes().unwrap_or_revert())
+}
+
+/// Writes `value` into the URef stored under `name`, creating it on first use so repeated
+/// writes do not leave orphaned URefs behind.
+#[cfg(feature = "contract-support")]
+pub fn set_stored_value<T: CLTyped + ToBytes>(name: &str, value: T) {
+    use casper_contract::{
+        contract_api::{
+            runtime::{get_key, put_key},
+            storage::{new_uref, write},
+        },
+        unwrap_or_revert::UnwrapOrRevert,
+    };
+
+    match get_key(name) {
+        Some(key) => write(
+            key.into_uref()
+                .unwrap_or_revert_with(SwapError::UnexpectedKeyVariant),
+            value,
+        ),
+        None => put_key(name, new_uref(value).into()),
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_contract_cspr_balance() -> U512 {
+    use casper_contract::{
+        contract_api::{runtime::get_key, system::get_purse_balance},
+        unwrap_or_revert::UnwrapOrRevert,
+    };
+    use casper_types::system::handle_payment::ARG_PURSE;
+
+    let contract_purse = get_key(ARG_PURSE).unwrap_or_revert_with(SwapError::MissingPurse);
+    get_purse_balance(
+        contract_purse
+            .as_uref()
+            .unwrap_or_revert_with(SwapError::MissingPurse)
+            .into_read(),
+    )
+    .unwrap_or_revert()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_contract_cowl_balance() -> U256 {
+    let cowl_swap_contract_package_key: Key = get_cowl_swap_contract_package().into();
+    get_cowl_cep18_balance_for_key(&cowl_swap_contract_package_key)
+}
+
+/// Refreshes the cached CSPR balance snapshot kept for off-chain readers.
+#[cfg(feature = "contract-support")]
+pub fn cache_cspr_balance() -> U512 {
+    use crate::constants::ARG_BALANCE_CSPR;
+
+    let balance = get_contract_cspr_balance();
+    set_stored_value(ARG_BALANCE_CSPR, balance);
+    balance
+}
+
+/// Refreshes the cached COWL balance snapshot kept for off-chain readers.
+#[cfg(feature = "contract-support")]
+pub fn cache_cowl_balance() -> U256 {
+    use crate::constants::ARG_BALANCE_COWL;
+
+    let balance = get_contract_cowl_balance();
+    set_stored_value(ARG_BALANCE_COWL, balance);
+    balance
+}
+
+/// Widens a token amount to U512, which always fits.
+#[cfg(feature = "contract-support")]
+pub fn u256_to_u512(value: U256) -> U512 {
+    let mut bytes = [0u8; 64];
+    value.to_little_endian(&mut bytes[..32]);
+    U512::from_little_endian(&bytes)
+}
+
+/// Narrows a U512 amount to a token amount, failing with `Overflow` when it does not fit.
+#[cfg(feature = "contract-support")]
+pub fn u512_to_u256(value: U512) -> Result<U256, SwapError> {
+    let mut bytes = [0u8; 64];
+    value.to_little_endian(&mut bytes);
+    if bytes[32..].iter().any(|byte| *byte != 0) {
+        return Err(SwapError::Overflow);
+    }
+    Ok(U256::from_little_endian(&bytes[..32]))
+}
+
+/// Implements `CLTyped`, `ToBytes` and `FromBytes` for a struct typed as `$tuple`, its fields
+/// being serialized in the order given, which yields the same bytes as the tuple.
+macro_rules! tuple_typed {
+    ($name:ident as $tuple:ty { $first:ident $(, $field:ident)* $(,)? }) => {
+        impl ::casper_types::CLTyped for $name {
+            fn cl_type() -> ::casper_types::CLType {
+                <$tuple as ::casper_types::CLTyped>::cl_type()
+            }
+        }
+
+        impl ::casper_types::bytesrepr::ToBytes for $name {
+            fn to_bytes(
+                &self,
+            ) -> ::core::result::Result<::alloc::vec::Vec<u8>, ::casper_types::bytesrepr::Error>
+            {
+                let mut result = ::casper_types::bytesrepr::allocate_buffer(self)?;
+                result.extend(::casper_types::bytesrepr::ToBytes::to_bytes(&self.$first)?);
+                $(result.extend(::casper_types::bytesrepr::ToBytes::to_bytes(&self.$field)?);)*
+                Ok(result)
+            }
+
+            fn serialized_length(&self) -> usize {
+                ::casper_types::bytesrepr::ToBytes::serialized_length(&self.$first)
+                    $(+ ::casper_types::bytesrepr::ToBytes::serialized_length(&self.$field))*
+            }
+        }
+
+        impl ::casper_types::bytesrepr::FromBytes for $name {
+            fn from_bytes(
+                bytes: &[u8],
+            ) -> ::core::result::Result<(Self, &[u8]), ::casper_types::bytesrepr::Error> {
+                let ($first, remainder) = ::casper_types::bytesrepr::FromBytes::from_bytes(bytes)?;
+                $(let ($field, remainder) =
+                    ::casper_types::bytesrepr::FromBytes::from_bytes(remainder)?;)*
+                Ok((Self { $first $(, $field)* }, remainder))
+            }
+        }
+    };
+}
+pub(crate) use tuple_typed;
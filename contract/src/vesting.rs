@@ -0,0 +1,264 @@
+//! Vesting of tokens bought from a pool, released to buyers through `claim`.
+use alloc::vec::Vec;
+use casper_types::U256;
+
+#[cfg(feature = "contract-support")]
+use crate::{
+    constants::{
+        DICT_CLAIM_START_TIMES, DICT_VESTING_ALLOCATIONS, DICT_VESTING_PHASES,
+        DICT_VESTING_SCHEDULES,
+    },
+    pools::{account_item_key, pool_item_key, Pool},
+    utils::{get_dictionary_value_from_key, set_dictionary_value_for_key},
+};
+use crate::{error::SwapError, utils::tuple_typed};
+#[cfg(feature = "contract-support")]
+use alloc::{format, string::String};
+#[cfg(feature = "contract-support")]
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+#[cfg(feature = "contract-support")]
+use casper_types::Key;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VestingSchedule {
+    pub start_time: u64,
+    pub cliff_duration: u64,
+    pub vesting_duration: u64,
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<(), SwapError> {
+        if self.cliff_duration > self.vesting_duration
+            || self.start_time.checked_add(self.vesting_duration).is_none()
+        {
+            return Err(SwapError::InvalidVestingSchedule);
+        }
+        Ok(())
+    }
+
+    /// Part of `total` released at `current_time`.
+    pub fn vested_amount(&self, total: U256, current_time: u64) -> Result<U256, SwapError> {
+        let cliff_end = self
+            .start_time
+            .checked_add(self.cliff_duration)
+            .ok_or(SwapError::Overflow)?;
+        if current_time < cliff_end {
+            return Ok(U256::zero());
+        }
+        let elapsed = current_time - self.start_time;
+        if elapsed >= self.vesting_duration {
+            return Ok(total);
+        }
+        total
+            .checked_mul(U256::from(elapsed))
+            .map(|value| value / self.vesting_duration)
+            .ok_or(SwapError::Overflow)
+    }
+}
+
+tuple_typed! {
+    VestingSchedule as (u64, u64, u64) {
+        start_time, cliff_duration, vesting_duration
+    }
+}
+
+/// Phase of a pool sale, whose purchases vest with `schedule`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VestingPhase {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub schedule: VestingSchedule,
+}
+
+impl VestingPhase {
+    pub fn validate(&self) -> Result<(), SwapError> {
+        if self.start_time >= self.end_time {
+            return Err(SwapError::InvalidVestingSchedule);
+        }
+        self.schedule.validate()
+    }
+
+    pub fn contains(&self, time: u64) -> bool {
+        self.start_time <= time && time < self.end_time
+    }
+}
+
+/// Phase `time` falls in.
+pub fn vesting_phase_at(phases: &[VestingPhase], time: u64) -> Option<&VestingPhase> {
+    phases.iter().find(|phase| phase.contains(time))
+}
+
+/// Adds `phase` to the phases of a pool, kept in order and not overlapping.
+pub fn set_vesting_phase(
+    phases: &mut Vec<VestingPhase>,
+    phase: VestingPhase,
+) -> Result<(), SwapError> {
+    phase.validate()?;
+    phases.retain(|existing| existing.start_time != phase.start_time);
+    if phases.iter().any(|existing| {
+        existing.start_time < phase.end_time && phase.start_time < existing.end_time
+    }) {
+        return Err(SwapError::InvalidVestingSchedule);
+    }
+    let index = phases
+        .iter()
+        .position(|existing| existing.start_time > phase.start_time)
+        .unwrap_or(phases.len());
+    phases.insert(index, phase);
+    Ok(())
+}
+
+tuple_typed! {
+    VestingPhase as ((u64, u64), (u64, u64, u64)) {
+        start_time, end_time, schedule
+    }
+}
+
+/// Tokens bought by a buyer of a pool while it vests and the part already claimed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VestingAllocation {
+    pub total: U256,
+    pub claimed: U256,
+}
+
+impl VestingAllocation {
+    /// Tokens the buyer can claim at `current_time`, all of them without a schedule.
+    pub fn releasable(
+        &self,
+        schedule: Option<&VestingSchedule>,
+        current_time: u64,
+    ) -> Result<U256, SwapError> {
+        let vested = match schedule {
+            Some(schedule) => schedule.vested_amount(self.total, current_time)?,
+            None => self.total,
+        };
+        Ok(vested.saturating_sub(self.claimed))
+    }
+
+    /// Tokens the buyer can claim at `current_time`, nothing before the claim start time.
+    pub fn claimable(
+        &self,
+        schedule: Option<&VestingSchedule>,
+        claim_start_time: Option<u64>,
+        current_time: u64,
+    ) -> Result<U256, SwapError> {
+        if matches!(claim_start_time, Some(start_time) if current_time < start_time) {
+            return Ok(U256::zero());
+        }
+        self.releasable(schedule, current_time)
+    }
+}
+
+tuple_typed! {
+    VestingAllocation as (U256, U256) {
+        total, claimed
+    }
+}
+
+/// Schedule purchases of the pool vest with, `None` when they are transferred right away.
+#[cfg(feature = "contract-support")]
+pub fn get_vesting_schedule(pool: &Pool) -> Option<VestingSchedule> {
+    get_dictionary_value_from_key::<Option<VestingSchedule>>(
+        DICT_VESTING_SCHEDULES,
+        &pool_item_key(pool),
+    )
+    .flatten()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_vesting_schedule(pool: &Pool, schedule: Option<VestingSchedule>) {
+    set_dictionary_value_for_key(DICT_VESTING_SCHEDULES, &pool_item_key(pool), &schedule)
+}
+
+/// Time from which the allocations of the pool can be claimed, `None` when they are claimed as
+/// they vest.
+#[cfg(feature = "contract-support")]
+pub fn get_claim_start_time(pool: &Pool) -> Option<u64> {
+    get_dictionary_value_from_key(DICT_CLAIM_START_TIMES, &pool_item_key(pool))
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_claim_start_time(pool: &Pool, claim_start_time: u64) {
+    set_dictionary_value_for_key(
+        DICT_CLAIM_START_TIMES,
+        &pool_item_key(pool),
+        &claim_start_time,
+    )
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_vesting_phases(pool: &Pool) -> Vec<VestingPhase> {
+    get_dictionary_value_from_key(DICT_VESTING_PHASES, &pool_item_key(pool)).unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_vesting_phases(pool: &Pool, phases: Vec<VestingPhase>) {
+    set_dictionary_value_for_key(DICT_VESTING_PHASES, &pool_item_key(pool), &phases)
+}
+
+/// Schedule of the allocations of `phase`, the pool's own schedule without a phase.
+#[cfg(feature = "contract-support")]
+pub fn get_phase_schedule(pool: &Pool, phase: Option<u64>) -> Option<VestingSchedule> {
+    match phase {
+        Some(start_time) => get_vesting_phases(pool)
+            .into_iter()
+            .find(|phase| phase.start_time == start_time)
+            .map(|phase| phase.schedule),
+        None => get_vesting_schedule(pool),
+    }
+}
+
+/// Allocations of a phase are kept next to the pool-wide ones, suffixed with the phase start.
+#[cfg(feature = "contract-support")]
+fn allocation_item_key(pool: &Pool, beneficiary: &Key, phase: Option<u64>) -> String {
+    let item_key = account_item_key(pool, beneficiary);
+    match phase {
+        Some(start_time) => format!("{item_key}_{start_time}"),
+        None => item_key,
+    }
+}
+
+#[cfg(feature = "contract-support")]
+pub fn get_vesting_allocation(
+    pool: &Pool,
+    beneficiary: &Key,
+    phase: Option<u64>,
+) -> VestingAllocation {
+    get_dictionary_value_from_key(
+        DICT_VESTING_ALLOCATIONS,
+        &allocation_item_key(pool, beneficiary, phase),
+    )
+    .unwrap_or_default()
+}
+
+#[cfg(feature = "contract-support")]
+pub fn save_vesting_allocation(
+    pool: &Pool,
+    beneficiary: &Key,
+    phase: Option<u64>,
+    allocation: &VestingAllocation,
+) {
+    set_dictionary_value_for_key(
+        DICT_VESTING_ALLOCATIONS,
+        &allocation_item_key(pool, beneficiary, phase),
+        allocation,
+    )
+}
+
+/// Credits `amount` tokens bought by `beneficiary` to its allocation in `phase` and returns its
+/// new total.
+#[cfg(feature = "contract-support")]
+pub fn add_vesting_allocation(
+    pool: &Pool,
+    beneficiary: &Key,
+    phase: Option<u64>,
+    amount: U256,
+) -> U256 {
+    let mut allocation = get_vesting_allocation(pool, beneficiary, phase);
+    allocation.total = allocation
+        .total
+        .checked_add(amount)
+        .unwrap_or_revert_with(SwapError::Overflow);
+    save_vesting_allocation(pool, beneficiary, phase, &allocation);
+    allocation.total
+}
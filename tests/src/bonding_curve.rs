@@ -0,0 +1,320 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{CLValue, Key, U256, U512};
+use cowl_swap::{
+    constants::{ARG_BALANCE_COWL, DEFAULT_TOKEN_DECIMALS, TAX_RATE},
+    curve::{validate_curve_steps, BondingCurve, CurveStep, CURVE_RATE_PRECISION},
+    enums::PricingMode,
+    error::SwapError,
+    events::BondingCurveUpdate,
+    pricing::{price_cspr_to_token_on_curve, price_token_to_cspr_on_curve},
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl, cowl_swap_set_bonding_curve,
+        cowl_swap_set_pricing_mode, setup, TestContext,
+    },
+    pools::{get_default_curve, test_pool},
+};
+
+const TOKEN: u64 = 1_000_000_000;
+const CSPR: u64 = 1_000_000_000;
+
+/// 100 tokens at 5 per CSPR, 100 more at 4 and a last 100 at 2.
+fn test_steps() -> Vec<CurveStep> {
+    vec![
+        CurveStep {
+            sold_until: U512::from(100 * TOKEN),
+            rate: CURVE_RATE_PRECISION * 5,
+        },
+        CurveStep {
+            sold_until: U512::from(200 * TOKEN),
+            rate: CURVE_RATE_PRECISION * 4,
+        },
+        CurveStep {
+            sold_until: U512::from(300 * TOKEN),
+            rate: CURVE_RATE_PRECISION * 2,
+        },
+    ]
+}
+
+fn test_curve(sold: U512) -> BondingCurve {
+    BondingCurve {
+        steps: test_steps(),
+        sold,
+    }
+}
+
+#[test]
+fn should_type_bonding_curve_as_tuples() {
+    let curve = test_curve(U512::from(40 * TOKEN));
+
+    let (steps, sold) = CLValue::from_t(curve.clone())
+        .expect("should encode curve")
+        .into_t::<(Vec<(U512, U512)>, U512)>()
+        .expect("should decode as tuples");
+    assert_eq!(
+        steps,
+        curve
+            .steps
+            .iter()
+            .map(|step| (step.sold_until, step.rate))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(sold, curve.sold);
+}
+
+#[test]
+fn should_integrate_purchase_across_steps() {
+    let curve = test_curve(U512::zero());
+
+    assert_eq!(
+        curve
+            .buy(U512::from(10 * CSPR), DEFAULT_TOKEN_DECIMALS)
+            .unwrap(),
+        U512::from(50 * TOKEN)
+    );
+
+    // 20 CSPR fill the first step, the other 10 are priced at the second rate
+    let tokens = curve
+        .buy(U512::from(30 * CSPR), DEFAULT_TOKEN_DECIMALS)
+        .unwrap();
+    assert_eq!(tokens, U512::from(140 * TOKEN));
+
+    // Splitting the order does not change what it buys
+    let first = curve
+        .buy(U512::from(20 * CSPR), DEFAULT_TOKEN_DECIMALS)
+        .unwrap();
+    let second = test_curve(first)
+        .buy(U512::from(10 * CSPR), DEFAULT_TOKEN_DECIMALS)
+        .unwrap();
+    assert_eq!(first + second, tokens);
+}
+
+#[test]
+fn should_price_at_curve_boundaries() {
+    let curve = test_curve(U512::zero());
+    assert_eq!(curve.current_rate(), Some(CURVE_RATE_PRECISION * 5));
+
+    // Exactly the first step, the next token is priced at the second rate
+    let tokens = curve
+        .buy(U512::from(20 * CSPR), DEFAULT_TOKEN_DECIMALS)
+        .unwrap();
+    assert_eq!(tokens, U512::from(100 * TOKEN));
+    assert_eq!(
+        test_curve(tokens).current_rate(),
+        Some(CURVE_RATE_PRECISION * 4)
+    );
+
+    // The whole curve costs 20 + 25 + 50 CSPR
+    let tokens = curve
+        .buy(U512::from(95 * CSPR), DEFAULT_TOKEN_DECIMALS)
+        .unwrap();
+    assert_eq!(tokens, U512::from(300 * TOKEN));
+    assert_eq!(test_curve(tokens).current_rate(), None);
+
+    let error = curve
+        .buy(U512::from(95 * CSPR + 1), DEFAULT_TOKEN_DECIMALS)
+        .unwrap_err();
+    assert!(matches!(error, SwapError::BondingCurveSoldOut));
+
+    let error = price_cspr_to_token_on_curve(
+        &test_pool(Key::Hash([1u8; 32])),
+        &curve,
+        U512::from(10 * CSPR - 1),
+    )
+    .unwrap_err();
+    assert!(matches!(error, SwapError::BelowMinimumSwap));
+}
+
+#[test]
+fn should_sell_back_down_the_curve() {
+    let curve = test_curve(U512::from(140 * TOKEN));
+
+    // 40 tokens at 4 per CSPR, then 100 at 5
+    assert_eq!(
+        curve
+            .sell(U512::from(140 * TOKEN), DEFAULT_TOKEN_DECIMALS)
+            .unwrap(),
+        U512::from(30 * CSPR)
+    );
+
+    let quote = price_token_to_cspr_on_curve(
+        &test_pool(Key::Hash([1u8; 32])),
+        &curve,
+        U512::from(40 * TOKEN),
+    )
+    .unwrap();
+    assert_eq!(quote.fee_amount, U512::from(10 * CSPR) * TAX_RATE / 100);
+    assert_eq!(
+        quote.output_amount,
+        U512::from(10 * CSPR) - quote.fee_amount
+    );
+
+    let error = curve
+        .sell(U512::from(140 * TOKEN + 1), DEFAULT_TOKEN_DECIMALS)
+        .unwrap_err();
+    assert!(matches!(error, SwapError::InsufficientLiquidity));
+}
+
+#[test]
+fn should_not_accept_invalid_curve_steps() {
+    assert!(validate_curve_steps(&test_steps()).is_ok());
+
+    let error = validate_curve_steps(&[]).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidBondingCurve));
+
+    let mut steps = test_steps();
+    steps.swap(0, 1);
+    let error = validate_curve_steps(&steps).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidBondingCurve));
+
+    // The rate cannot rise, the price would drop as tokens are sold
+    let mut steps = test_steps();
+    steps[2].rate = CURVE_RATE_PRECISION * 6;
+    let error = validate_curve_steps(&steps).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidBondingCurve));
+}
+
+#[test]
+fn should_cspr_to_cowl_on_bonding_curve() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    // 50 tokens at 5 per CSPR, then 50 at 2
+    let steps = vec![
+        CurveStep {
+            sold_until: U512::from(50 * TOKEN),
+            rate: CURVE_RATE_PRECISION * 5,
+        },
+        CurveStep {
+            sold_until: U512::from(100 * TOKEN),
+            rate: CURVE_RATE_PRECISION * 2,
+        },
+    ];
+
+    cowl_swap_set_bonding_curve(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        &steps,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set the bonding curve for non admin account",
+    );
+
+    cowl_swap_set_bonding_curve(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        &[],
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidBondingCurve as u16,
+        "should not allow a curve without steps",
+    );
+
+    cowl_swap_set_bonding_curve(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        &steps,
+    )
+    .expect_success()
+    .commit();
+
+    let event: BondingCurveUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.pool, pool);
+    assert_eq!(
+        event.curve_steps,
+        steps
+            .iter()
+            .map(|step| (step.sold_until, step.rate))
+            .collect::<Vec<_>>()
+    );
+    assert!(event.sold.is_zero());
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100 * TOKEN);
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        PricingMode::BondingCurve as u8,
+    )
+    .expect_success()
+    .commit();
+
+    // 10 CSPR buy the first 50 tokens, the other 5 buy 10 at the second rate
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        U512::from(15 * CSPR),
+    )
+    .expect_success()
+    .commit();
+
+    let curve = get_default_curve(&builder, cowl_swap_contract_hash);
+    assert_eq!(curve.sold, U512::from(60 * TOKEN));
+    assert_eq!(curve.current_rate(), Some(CURVE_RATE_PRECISION * 2));
+
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    let actual_balance_cowl = builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.");
+
+    assert_eq!(
+        actual_balance_cowl,
+        cowl_pool_amount - U256::from(60 * TOKEN)
+    );
+}
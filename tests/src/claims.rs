@@ -0,0 +1,300 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{Key, U256, U512};
+use cowl_swap::{
+    constants::RATE_TIERS,
+    error::SwapError,
+    events::{ClaimStartTimeUpdate, TokensClaimed, TokensVested},
+    vesting::{VestingAllocation, VestingSchedule},
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::installer_request_builders::{
+    cowl_swap_claim, cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl,
+    cowl_swap_set_claim_start_time, cowl_swap_set_vesting_schedule, setup, TestContext,
+};
+
+#[test]
+fn should_release_claimable_tokens_at_claim_start() {
+    let allocation = VestingAllocation {
+        total: U256::from(1_000u64),
+        claimed: U256::from(400u64),
+    };
+    assert!(allocation
+        .claimable(None, Some(500), 499)
+        .unwrap()
+        .is_zero());
+    assert_eq!(
+        allocation.claimable(None, Some(500), 500).unwrap(),
+        U256::from(600u64)
+    );
+
+    // The claim start holds the allocation on top of its schedule
+    let schedule = VestingSchedule {
+        start_time: 0,
+        cliff_duration: 0,
+        vesting_duration: 1_000,
+    };
+    assert!(allocation
+        .claimable(Some(&schedule), Some(500), 499)
+        .unwrap()
+        .is_zero());
+    assert_eq!(
+        allocation
+            .claimable(Some(&schedule), Some(500), 500)
+            .unwrap(),
+        U256::from(100u64)
+    );
+}
+
+#[test]
+fn should_claim_purchases_partially_after_claim_start() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_claim_start_time(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        Some(0),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set the claim start time for non admin account",
+    );
+
+    cowl_swap_set_claim_start_time(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(1),
+    )
+    .expect_success()
+    .commit();
+
+    let event: ClaimStartTimeUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.claim_start_time, 1);
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+
+    let event: TokensVested = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(event.amount, cowl_amount);
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::NothingToClaim as u16,
+        "should not allow to claim before the claim start time",
+    );
+
+    // Moving the claim start time to a TGE already reached
+    cowl_swap_set_claim_start_time(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(0),
+    )
+    .expect_success()
+    .commit();
+
+    let partial_amount = U256::from(10_000_000_000u64);
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        Some(partial_amount),
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokensClaimed = get_event(&builder, &cowl_swap_contract_hash.into(), 5);
+    assert_eq!(event.beneficiary, Key::from(account_user_1));
+    assert_eq!(event.amount, partial_amount);
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        Some(cowl_amount),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientClaimable as u16,
+        "should not allow to claim more than the claimable balance",
+    );
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokensClaimed = get_event(&builder, &cowl_swap_contract_hash.into(), 6);
+    assert_eq!(event.amount, cowl_amount - partial_amount);
+}
+
+#[test]
+fn should_keep_vesting_schedule_with_claim_start_time() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(VestingSchedule {
+            start_time: 0,
+            cliff_duration: 100,
+            vesting_duration: 1_000,
+        }),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_claim_start_time(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(0),
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    // The claim start reached does not release tokens still before the cliff
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::NothingToClaim as u16,
+        "should keep the vesting schedule after setting the claim start time",
+    );
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        None,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokensClaimed = get_event(&builder, &cowl_swap_contract_hash.into(), 6);
+    assert_eq!(event.amount, cowl_amount);
+}
@@ -1,8 +1,39 @@
-use casper_types::U256;
-use cowl_swap::constants::ARG_BALANCE_COWL;
-use vesting_tests::constants::ACCOUNT_LIQUIDITY;
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_types::{ContractHash, Key, U256};
+use cowl_swap::{
+    constants::{ARG_BALANCE_COWL, DICT_COWL_DEPOSITS},
+    events::{DepositCowl, EventHeader},
+};
+use vesting_tests::{constants::ACCOUNT_LIQUIDITY, support::get_event};
 
-use crate::utility::installer_request_builders::{cowl_swap_deposit_cowl, setup, TestContext};
+use crate::utility::{
+    installer_request_builders::{cowl_swap_deposit_cowl, setup, TestContext},
+    keys::item_key,
+};
+
+fn get_cowl_deposit(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+    owner: Key,
+) -> U256 {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(DICT_COWL_DEPOSITS)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, &item_key(&owner))
+        .expect("should have cowl deposit")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.")
+}
 
 #[test]
 fn should_deposit_cowl() {
@@ -49,6 +80,89 @@ fn should_deposit_cowl() {
 
     assert_eq!(actual_balance, U256::one());
 
-    let named_keys = swap_contract.named_keys();
-    dbg!(named_keys);
+    assert_eq!(
+        get_cowl_deposit(&builder, cowl_swap_contract_hash, Key::from(liquidity)),
+        U256::one()
+    );
+
+    let actual_event: DepositCowl = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(actual_event.owner, Key::from(liquidity));
+    assert_eq!(actual_event.amount, U256::one());
+}
+
+#[test]
+fn should_accumulate_cowl_deposits_per_owner() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    for amount in [U256::one(), U256::from(2)] {
+        cowl_swap_deposit_cowl(
+            &mut builder,
+            &liquidity,
+            &cowl_cep18_token_package_hash,
+            &cowl_swap_contract_package,
+            amount,
+        )
+        .expect_success()
+        .commit();
+    }
+
+    assert_eq!(
+        get_cowl_deposit(&builder, cowl_swap_contract_hash, Key::from(liquidity)),
+        U256::from(3)
+    );
+
+    let actual_event: DepositCowl = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(actual_event.amount, U256::from(2));
+}
+
+#[test]
+fn should_emit_event_on_deposit_cowl() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    let deposit_cowl = cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::one(),
+    );
+
+    deposit_cowl.expect_success().commit();
+
+    // Expect DepositCowl event
+    let actual_event: DepositCowl = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    let expected_event = DepositCowl::new(
+        EventHeader::new(
+            0,
+            actual_event.header.block_time,
+            Key::from(liquidity),
+            None,
+        ),
+        Key::from(cowl_cep18_token_package_hash),
+        Key::from(liquidity),
+        U256::one(),
+    );
+    assert_eq!(actual_event, expected_event, "Expected DepositCowl event.");
 }
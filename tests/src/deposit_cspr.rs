@@ -79,7 +79,57 @@ fn should_deposit_cspr_when_installer() {
         actual_balance,
         U512::from_dec_str(MINIMUM_TRANSFER_AMOUNT).unwrap()
     );
+}
+
+#[test]
+fn should_update_balance_snapshot_in_place() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            ..
+        },
+    ) = setup();
+
+    let balance_key_on_install = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_CSPR)
+        .expect("balance uref should exist");
+
+    for _ in 0..2 {
+        let deposit_cspr = cowl_swap_deposit_cspr(
+            &mut builder,
+            &DEFAULT_ACCOUNT_ADDR,
+            &cowl_swap_contract_package,
+            U512::from_dec_str(MINIMUM_TRANSFER_AMOUNT).unwrap(),
+        );
+
+        deposit_cspr.expect_success().commit();
+    }
 
-    let named_keys = swap_contract.named_keys();
-    dbg!(named_keys);
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_CSPR)
+        .expect("balance uref should exist");
+
+    assert_eq!(balance_key, balance_key_on_install);
+
+    let actual_balance = builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U512>()
+        .expect("should be U512.");
+
+    assert_eq!(
+        actual_balance,
+        U512::from_dec_str(MINIMUM_TRANSFER_AMOUNT).unwrap() * 2
+    );
 }
@@ -0,0 +1,139 @@
+use casper_engine_test_support::{ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
+use casper_event_standard::{EventInstance, Schema, Schemas, EVENTS_SCHEMA};
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes},
+    runtime_args, CLType, CLTyped, Key, RuntimeArgs,
+};
+use cowl_swap::{
+    constants::{ARG_NAME, ARG_UPGRADE_FLAG},
+    events::{SetModalities, Upgrade},
+};
+use vesting_tests::support::get_event;
+
+use crate::utility::{
+    constants::{SWAP_CONTRACT_V1_0_0_WASM, SWAP_CONTRACT_WASM, SWAP_TEST_NAME},
+    installer_request_builders::{
+        cowl_swap_set_modalities, default_args, setup_with_wasm_and_args, TestContext,
+    },
+};
+
+/// Event layouts as emitted by contract 1.0.0, before event names were versioned.
+mod legacy {
+    use casper_event_standard::Event;
+
+    #[derive(Event, Debug, PartialEq, Eq)]
+    pub struct SetModalities {}
+}
+
+#[test]
+fn should_decode_legacy_and_versioned_events_after_upgrade() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup_with_wasm_and_args(SWAP_CONTRACT_V1_0_0_WASM, default_args());
+
+    let owner = *DEFAULT_ACCOUNT_ADDR;
+
+    cowl_swap_set_modalities(&mut builder, &cowl_swap_contract_hash, &owner, None)
+        .expect_success()
+        .commit();
+
+    let upgrade_request_contract = ExecuteRequestBuilder::standard(
+        owner,
+        SWAP_CONTRACT_WASM,
+        runtime_args! {
+            ARG_UPGRADE_FLAG => true,
+            ARG_NAME => SWAP_TEST_NAME,
+        },
+    )
+    .build();
+    builder
+        .exec(upgrade_request_contract)
+        .expect_success()
+        .commit();
+
+    // Event emitted by 1.0.0 keeps its unversioned name and layout
+    let legacy_event: legacy::SetModalities =
+        get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(legacy_event, legacy::SetModalities {});
+
+    let upgrade_event: Upgrade = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(upgrade_event.header.sequence_id, 0);
+    assert_eq!(upgrade_event.header.caller, Key::from(owner));
+    assert_eq!(
+        upgrade_event.old_contract_hash,
+        Key::from(cowl_swap_contract_hash)
+    );
+    assert_eq!(upgrade_event.old_contract_version, 1);
+    assert_eq!(upgrade_event.new_contract_version, 2);
+
+    let schemas = builder
+        .query(
+            None,
+            cowl_swap_contract_hash.into(),
+            &[EVENTS_SCHEMA.to_string()],
+        )
+        .expect("should have events schema")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<Schemas>()
+        .expect("should be schemas");
+
+    assert!(schemas.0.contains_key("SetModalities"));
+    assert!(schemas.0.contains_key("SetModalities_v2"));
+    assert!(schemas.0.contains_key("Upgrade_v2"));
+}
+
+#[test]
+fn should_reject_versioned_event_bytes_under_legacy_name() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup_with_wasm_and_args(SWAP_CONTRACT_WASM, default_args());
+
+    let owner = *DEFAULT_ACCOUNT_ADDR;
+
+    cowl_swap_set_modalities(&mut builder, &cowl_swap_contract_hash, &owner, None)
+        .expect_success()
+        .commit();
+
+    let event: SetModalities = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.old_events_mode, event.new_events_mode);
+
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(casper_event_standard::EVENTS_DICT)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    let bytes = builder
+        .query_dictionary_item(None, dictionary_seed_uref, "0")
+        .expect("should have event")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<Bytes>()
+        .expect("should be bytes");
+
+    assert!(legacy::SetModalities::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn should_describe_event_header_with_concrete_types() {
+    let mut expected_schema = Schema::new();
+    expected_schema.with_elem("header", <((u64, u64), Key, Option<Key>)>::cl_type());
+    expected_schema.with_elem("old_events_mode", CLType::U8);
+    expected_schema.with_elem("new_events_mode", CLType::U8);
+
+    assert_eq!(SetModalities::schema(), expected_schema);
+}
@@ -0,0 +1,373 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+};
+use casper_types::{runtime_args, CLValue, ContractHash, ContractPackageHash, Key, RuntimeArgs};
+use cowl_swap::{
+    constants::{
+        ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_DURATION, ARG_END_TIME, ARG_NAME, ARG_POOL_COUNT,
+        ARG_ROLLBACK_VERSION, ARG_START_TIME, ARG_UPGRADE_FLAG, DICT_POOLS,
+        ENTRY_POINT_CREATE_POOL, PREFIX_ACCESS_KEY_NAME, PREFIX_CONTRACT_NAME,
+        PREFIX_CONTRACT_VERSION,
+    },
+    error::SwapError,
+    events::{EventHeader, PoolCreated},
+    factory::PoolInfo,
+};
+use vesting_tests::{
+    constants::ACCOUNT_USER_1,
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    constants::SWAP_CONTRACT_WASM,
+    installer_request_builders::{
+        cowl_swap_factory_create_pool, cowl_swap_factory_create_pool_session,
+        cowl_swap_factory_install, cowl_swap_update_times, setup, TestContext,
+    },
+};
+
+const POOL_NAME: &str = "pool_a";
+
+fn get_pool(builder: &InMemoryWasmTestBuilder, factory: ContractHash, index: u64) -> PoolInfo {
+    let dictionary_seed_uref = *builder
+        .get_contract(factory)
+        .expect("should have factory contract")
+        .named_keys()
+        .get(DICT_POOLS)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, &index.to_string())
+        .expect("should have pool")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<PoolInfo>()
+        .expect("should convert pool info")
+}
+
+fn get_pool_count(builder: &InMemoryWasmTestBuilder, factory: ContractHash) -> u64 {
+    builder
+        .query(None, factory.into(), &[ARG_POOL_COUNT.to_string()])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<u64>()
+        .expect("should be u64.")
+}
+
+#[test]
+fn should_create_and_register_pool() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let factory = cowl_swap_factory_install(&mut builder);
+    assert_eq!(get_pool_count(&builder, factory), 0);
+
+    cowl_swap_factory_create_pool(
+        &mut builder,
+        &factory,
+        &DEFAULT_ACCOUNT_ADDR,
+        POOL_NAME,
+        &cowl_cep18_token_package_hash,
+    )
+    .expect_success()
+    .commit();
+
+    assert_eq!(get_pool_count(&builder, factory), 1);
+
+    let factory_named_keys = builder
+        .get_contract(factory)
+        .expect("should have factory contract")
+        .named_keys()
+        .clone();
+    let pool_contract_hash: ContractHash = factory_named_keys
+        .get(&format!("{PREFIX_CONTRACT_NAME}_{POOL_NAME}"))
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("factory should hold pool contract hash");
+
+    let pool_contract = builder
+        .get_contract(pool_contract_hash)
+        .expect("should have pool contract");
+    let pool_package: ContractPackageHash = pool_contract.contract_package_hash();
+    assert!(pool_contract
+        .named_keys()
+        .contains_key(ARG_COWL_CEP18_CONTRACT_PACKAGE));
+    assert!(pool_contract.named_keys().contains_key(ARG_END_TIME));
+
+    let creator = Key::from(*DEFAULT_ACCOUNT_ADDR);
+    let pool = get_pool(&builder, factory, 0);
+    assert_eq!(
+        pool,
+        PoolInfo {
+            name: POOL_NAME.to_string(),
+            package_hash: Key::from(pool_package),
+            contract_hash: Key::from(pool_contract_hash),
+            token_package: Key::from(cowl_cep18_token_package_hash),
+            start_time: 0,
+            end_time: 86400,
+            creator,
+            created_at: pool.created_at,
+        }
+    );
+
+    let actual_event: PoolCreated = get_event(&builder, &factory.into(), 0);
+    let expected_event = PoolCreated::new(
+        EventHeader::new(0, actual_event.header.block_time, creator, None),
+        0,
+        POOL_NAME.to_string(),
+        Key::from(pool_package),
+        Key::from(pool_contract_hash),
+        Key::from(cowl_cep18_token_package_hash),
+        0,
+        86400,
+        creator,
+    );
+    assert_eq!(actual_event, expected_event);
+
+    // The creator administers the pool
+    cowl_swap_update_times(&mut builder, &pool_contract_hash, 10_u64, 1000_u64)
+        .expect_success()
+        .commit();
+}
+
+#[test]
+fn should_not_create_pool_with_existing_name() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let factory = cowl_swap_factory_install(&mut builder);
+
+    cowl_swap_factory_create_pool(
+        &mut builder,
+        &factory,
+        &DEFAULT_ACCOUNT_ADDR,
+        POOL_NAME,
+        &cowl_cep18_token_package_hash,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_factory_create_pool(
+        &mut builder,
+        &factory,
+        &DEFAULT_ACCOUNT_ADDR,
+        POOL_NAME,
+        &cowl_cep18_token_package_hash,
+    )
+    .expect_failure();
+
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::SwapAlreadyInstalled as u16,
+        "should not create two pools with the same name",
+    );
+    assert_eq!(get_pool_count(&builder, factory), 1);
+}
+
+#[test]
+fn should_not_create_pool_with_empty_window() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let factory = cowl_swap_factory_install(&mut builder);
+
+    for (start_time, duration) in [(10_u64, 0_u64), (u64::MAX, 1_u64)] {
+        let create_pool_request = ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ACCOUNT_ADDR,
+            factory,
+            ENTRY_POINT_CREATE_POOL,
+            runtime_args! {
+                ARG_NAME => POOL_NAME,
+                ARG_COWL_CEP18_CONTRACT_PACKAGE => Key::from(cowl_cep18_token_package_hash),
+                ARG_START_TIME => start_time,
+                ARG_DURATION => duration,
+            },
+        )
+        .build();
+        builder.exec(create_pool_request).expect_failure();
+
+        assert_expected_error(
+            builder.get_error().expect("must have error"),
+            SwapError::InvalidTimeWindow as u16,
+            "should not create a pool whose window ends before it starts",
+        );
+    }
+    assert_eq!(get_pool_count(&builder, factory), 0);
+}
+
+#[test]
+fn should_not_create_pool_without_admin_rights() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let factory = cowl_swap_factory_install(&mut builder);
+    let user = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    cowl_swap_factory_create_pool(
+        &mut builder,
+        &factory,
+        &user,
+        POOL_NAME,
+        &cowl_cep18_token_package_hash,
+    )
+    .expect_failure();
+
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InsufficientRights as u16,
+        "should only let factory admins create pools",
+    );
+}
+
+fn get_account_pool_contract(builder: &InMemoryWasmTestBuilder) -> (ContractHash, u32) {
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let contract_hash = account
+        .named_keys()
+        .get(&format!("{PREFIX_CONTRACT_NAME}_{POOL_NAME}"))
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("account should hold pool contract hash");
+    let contract_version = builder
+        .query(
+            None,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            &[format!("{PREFIX_CONTRACT_VERSION}_{POOL_NAME}")],
+        )
+        .expect("should have pool contract version")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<u32>()
+        .expect("should be u32.");
+    (contract_hash, contract_version)
+}
+
+#[test]
+fn should_upgrade_and_rollback_pool_created_from_session() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let factory = cowl_swap_factory_install(&mut builder);
+
+    cowl_swap_factory_create_pool_session(
+        &mut builder,
+        &factory,
+        &DEFAULT_ACCOUNT_ADDR,
+        POOL_NAME,
+        &cowl_cep18_token_package_hash,
+    )
+    .expect_success()
+    .commit();
+
+    assert_eq!(get_pool_count(&builder, factory), 1);
+    assert!(builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .contains_key(&format!("{PREFIX_ACCESS_KEY_NAME}_{POOL_NAME}")));
+
+    let (pool_contract_hash, pool_contract_version) = get_account_pool_contract(&builder);
+    assert_eq!(
+        get_pool(&builder, factory, 0).contract_hash,
+        Key::from(pool_contract_hash)
+    );
+    assert_eq!(pool_contract_version, 1);
+
+    let upgrade_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        SWAP_CONTRACT_WASM,
+        runtime_args! {
+            ARG_NAME => POOL_NAME,
+            ARG_UPGRADE_FLAG => true,
+        },
+    )
+    .build();
+    builder.exec(upgrade_request).expect_success().commit();
+
+    let (upgraded_contract_hash, upgraded_contract_version) = get_account_pool_contract(&builder);
+    assert_ne!(upgraded_contract_hash, pool_contract_hash);
+    assert_eq!(upgraded_contract_version, 2);
+
+    let rollback_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        SWAP_CONTRACT_WASM,
+        runtime_args! {
+            ARG_NAME => POOL_NAME,
+            ARG_ROLLBACK_VERSION => 1_u32,
+        },
+    )
+    .build();
+    builder.exec(rollback_request).expect_success().commit();
+
+    assert_eq!(get_account_pool_contract(&builder), (pool_contract_hash, 1));
+
+    let pool_package = builder
+        .get_contract_package(
+            builder
+                .get_contract(pool_contract_hash)
+                .expect("should have pool contract")
+                .contract_package_hash(),
+        )
+        .expect("should have pool package");
+    assert!(pool_package.is_contract_enabled(&pool_contract_hash));
+    assert!(!pool_package.is_contract_enabled(&upgraded_contract_hash));
+}
+
+#[test]
+fn should_type_pool_info_as_nested_tuples() {
+    let pool = PoolInfo {
+        name: POOL_NAME.to_string(),
+        package_hash: Key::Hash([1; 32]),
+        contract_hash: Key::Hash([2; 32]),
+        token_package: Key::Hash([3; 32]),
+        start_time: 4,
+        end_time: 5,
+        creator: Key::Hash([6; 32]),
+        created_at: 7,
+    };
+
+    let value = CLValue::from_t(pool).expect("should encode pool info");
+    assert_eq!(
+        value
+            .into_t::<(String, (Key, Key, Key), ((u64, u64), Key, u64))>()
+            .expect("should decode as tuples"),
+        (
+            POOL_NAME.to_string(),
+            (Key::Hash([1; 32]), Key::Hash([2; 32]), Key::Hash([3; 32])),
+            ((4, 5), Key::Hash([6; 32]), 7)
+        )
+    );
+}
@@ -1,16 +1,21 @@
+use std::collections::BTreeMap;
+
 use crate::utility::{
     constants::{SWAP_CONTRACT_VERSION, SWAP_CONTRACT_WASM, SWAP_TEST_NAME},
-    installer_request_builders::{setup, TestContext},
+    installer_request_builders::{cowl_swap_upgrade, default_args, setup, TestContext},
 };
 use casper_engine_test_support::{ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
-use casper_types::{runtime_args, Key, RuntimeArgs};
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs};
 use cowl_swap::{
     constants::{
         ARG_CONTRACT_HASH, ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_EVENTS_MODE, ARG_INSTALLER,
-        ARG_NAME, ARG_PACKAGE_HASH, DICT_SECURITY_BADGES,
+        ARG_NAME, ARG_PACKAGE_HASH, DICT_SECURITY_BADGES, INSTALLED_SWAPS,
+        PREFIX_CONTRACT_PACKAGE_NAME,
     },
     enums::EventsMode,
+    error::SwapError,
 };
+use vesting_tests::support::assert_expected_error;
 
 #[test]
 fn should_install_contract() {
@@ -24,16 +29,12 @@ fn should_install_contract() {
         },
     ) = setup();
 
-    let cowl_cep18_token_contract = builder
+    builder
         .get_contract(cowl_cep18_token_contract_hash)
         .expect("should have cowl cep18 token contract");
-    let named_keys = cowl_cep18_token_contract.named_keys();
-    dbg!(named_keys);
-    let vesting_contract = builder
+    builder
         .get_contract(cowl_vesting_contract_hash)
         .expect("should have vesting contract");
-    let named_keys = vesting_contract.named_keys();
-    dbg!(named_keys);
     let swap_contract = builder
         .get_contract(cowl_swap_contract_hash)
         .expect("should have swap contract");
@@ -63,8 +64,6 @@ fn should_install_contract() {
         "{:?}",
         named_keys
     );
-
-    dbg!(named_keys);
 }
 
 #[test]
@@ -100,7 +99,6 @@ fn should_prevent_reinstall_contract() {
         .get_contract(cowl_swap_contract_hash)
         .expect("should have swap contract");
     let named_keys = swap_contract.named_keys();
-    dbg!(named_keys);
 
     let install_args = runtime_args!(
         ARG_NAME => SWAP_TEST_NAME,
@@ -114,16 +112,86 @@ fn should_prevent_reinstall_contract() {
         ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, SWAP_CONTRACT_WASM, install_args)
             .build();
 
-    builder
-        .exec(reinstall_request_contract)
-        .expect_success()
-        .commit();
+    builder.exec(reinstall_request_contract).expect_failure();
+
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::SwapAlreadyInstalled as u16,
+        "should refuse to install over an existing swap name",
+    );
 
     let swap_contract = builder
         .get_contract(cowl_swap_contract_hash)
         .expect("should have swap contract");
     let new_named_keys = swap_contract.named_keys();
-    dbg!(new_named_keys);
 
     assert_eq!(named_keys, new_named_keys)
 }
+
+#[test]
+fn should_index_several_swaps_installed_by_one_account() {
+    const SECOND_SWAP_NAME: &str = "second";
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let mut install_args = default_args();
+    let _ = install_args.insert(ARG_NAME, SECOND_SWAP_NAME);
+    let _ = install_args.insert(
+        ARG_COWL_CEP18_CONTRACT_PACKAGE,
+        Key::from(cowl_cep18_token_package_hash),
+    );
+    let install_request =
+        ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, SWAP_CONTRACT_WASM, install_args)
+            .build();
+    builder.exec(install_request).expect_success().commit();
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    let second_swap_package: ContractPackageHash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(&format!(
+            "{PREFIX_CONTRACT_PACKAGE_NAME}_{SECOND_SWAP_NAME}"
+        ))
+        .and_then(|key| key.into_hash())
+        .map(ContractPackageHash::new)
+        .expect("should have second package hash");
+    assert_ne!(second_swap_package, cowl_swap_contract_package);
+
+    let installed_swaps = builder
+        .query(
+            None,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            &[INSTALLED_SWAPS.to_string()],
+        )
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<BTreeMap<String, (Key, u32)>>()
+        .expect("should be installed swaps.");
+
+    assert_eq!(
+        installed_swaps,
+        BTreeMap::from([
+            (
+                SWAP_TEST_NAME.to_string(),
+                (Key::from(cowl_swap_contract_package), 2)
+            ),
+            (
+                SECOND_SWAP_NAME.to_string(),
+                (Key::from(second_swap_package), 1)
+            ),
+        ])
+    );
+}
@@ -33,3 +33,57 @@ mod cspr_to_cowl;
 
 #[cfg(test)]
 mod cowl_to_cspr;
+
+#[cfg(test)]
+mod reserves;
+
+#[cfg(test)]
+mod swap_history;
+
+#[cfg(test)]
+mod native_events;
+
+#[cfg(test)]
+mod event_schemas;
+
+#[cfg(test)]
+mod migrations;
+
+#[cfg(test)]
+mod rollback;
+
+#[cfg(test)]
+mod factory;
+
+#[cfg(test)]
+mod token;
+
+#[cfg(test)]
+mod token_pairs;
+
+#[cfg(test)]
+mod pools;
+
+#[cfg(test)]
+mod pricing;
+
+#[cfg(test)]
+mod liquidity;
+
+#[cfg(test)]
+mod oracle;
+
+#[cfg(test)]
+mod bonding_curve;
+
+#[cfg(test)]
+mod referrals;
+
+#[cfg(test)]
+mod vesting;
+
+#[cfg(test)]
+mod claims;
+
+#[cfg(test)]
+mod sale;
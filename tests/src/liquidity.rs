@@ -0,0 +1,381 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{CLValue, Key, U256, U512};
+use cowl_swap::{
+    constants::ARG_SHARES,
+    error::SwapError,
+    events::{AddLiquidity, CowlToCspr, RemoveLiquidity},
+    liquidity::{LiquidityPosition, LiquidityState},
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::installer_request_builders::{
+    cowl_swap_add_liquidity, cowl_swap_deposit_cspr, cowl_swap_remove_liquidity,
+    cowl_swap_token_to_cspr, cowl_swap_withdraw_cspr, setup, TestContext,
+};
+
+#[test]
+fn should_mint_shares_in_proportion_to_contributions() {
+    let mut state = LiquidityState::default();
+    let mut first = LiquidityPosition::default();
+    let mut second = LiquidityPosition::default();
+
+    // First provider: geometric mean of both amounts
+    let shares = state
+        .add(&mut first, U512::from(400), U512::from(100))
+        .unwrap();
+    assert_eq!(shares, U512::from(200));
+
+    // Second provider: the smaller proportion, the excess tokens go to all holders
+    let shares = state
+        .add(&mut second, U512::from(200), U512::from(80))
+        .unwrap();
+    assert_eq!(shares, U512::from(100));
+    assert_eq!(state.total_shares, U512::from(300));
+    assert_eq!(state.cspr_liquidity, U512::from(600));
+    assert_eq!(state.token_liquidity, U512::from(180));
+
+    let error = state
+        .add(&mut second, U512::zero(), U512::from(80))
+        .unwrap_err();
+    assert!(matches!(error, SwapError::InvalidAmount));
+
+    let withdrawal = state.remove(&mut second, U512::from(100)).unwrap();
+    assert_eq!(withdrawal.cspr_amount, U512::from(200));
+    assert_eq!(withdrawal.token_amount, U512::from(60));
+    assert!(second.shares.is_zero());
+
+    let error = state.remove(&mut second, U512::one()).unwrap_err();
+    assert!(matches!(error, SwapError::InsufficientShares));
+}
+
+#[test]
+fn should_price_withdrawals_on_current_liquidity() {
+    let mut state = LiquidityState::default();
+    let mut position = LiquidityPosition::default();
+
+    state
+        .add(&mut position, U512::from(1_000), U512::from(1_000))
+        .unwrap();
+
+    // Providers hold the whole token reserve and sell 300 tokens for 500 CSPR
+    state
+        .sell_tokens(
+            U512::from(500),
+            U512::from(300),
+            U512::zero(),
+            U512::from(1_000),
+        )
+        .unwrap();
+    assert_eq!(state.cspr_liquidity, U512::from(1_500));
+    assert_eq!(state.token_liquidity, U512::from(700));
+
+    let withdrawal = state.remove(&mut position, position.shares).unwrap();
+    assert_eq!(withdrawal.cspr_amount, U512::from(1_500));
+    assert_eq!(withdrawal.token_amount, U512::from(700));
+    assert_eq!(state, LiquidityState::default());
+}
+
+#[test]
+fn should_share_tax_with_holders_at_the_time() {
+    let mut state = LiquidityState::default();
+    let mut first = LiquidityPosition::default();
+    let mut second = LiquidityPosition::default();
+
+    state
+        .add(&mut first, U512::from(1_000), U512::from(1_000))
+        .unwrap();
+
+    // Providers hold half of the CSPR reserve: they pay half of the CSPR bought, take in half of
+    // the tokens sold and earn half of the tax
+    state
+        .buy_tokens(
+            U512::from(200),
+            U512::from(100),
+            U512::from(100),
+            U512::from(2_000),
+        )
+        .unwrap();
+    assert_eq!(state.cspr_liquidity, U512::from(900));
+    assert_eq!(state.token_liquidity, U512::from(1_100));
+    assert_eq!(state.cspr_fees_owed, U512::from(50));
+    assert_eq!(
+        first.pending_fees(&state).unwrap(),
+        (U512::from(50), U512::zero())
+    );
+
+    // Tax accrued before joining is not earned by later providers
+    let shares = state
+        .add(&mut second, U512::from(900), U512::from(1_100))
+        .unwrap();
+    assert_eq!(shares, first.shares);
+    assert_eq!(
+        second.pending_fees(&state).unwrap(),
+        (U512::zero(), U512::zero())
+    );
+
+    state
+        .sell_tokens(
+            U512::zero(),
+            U512::zero(),
+            U512::from(40),
+            U512::from(4_400),
+        )
+        .unwrap();
+    assert_eq!(
+        first.pending_fees(&state).unwrap(),
+        (U512::from(50), U512::from(10))
+    );
+    assert_eq!(
+        second.pending_fees(&state).unwrap(),
+        (U512::zero(), U512::from(10))
+    );
+
+    let withdrawal = state.remove(&mut first, first.shares).unwrap();
+    assert_eq!(withdrawal.cspr_amount, U512::from(900));
+    assert_eq!(withdrawal.token_amount, U512::from(1_090));
+    assert_eq!(withdrawal.cspr_fees, U512::from(50));
+    assert_eq!(withdrawal.token_fees, U512::from(10));
+    assert!(state.cspr_fees_owed.is_zero());
+    assert_eq!(state.token_fees_owed, U512::from(10));
+}
+
+#[test]
+fn should_type_liquidity_as_nested_tuples() {
+    let state = LiquidityState {
+        total_shares: U512::from(1),
+        cspr_liquidity: U512::from(2),
+        token_liquidity: U512::from(3),
+        cspr_fee_per_share: U512::from(4),
+        token_fee_per_share: U512::from(5),
+        cspr_fees_owed: U512::from(6),
+        token_fees_owed: U512::from(7),
+    };
+    let decoded = CLValue::from_t(state)
+        .expect("should encode state")
+        .into_t::<((U512, U512, U512), (U512, U512), (U512, U512))>()
+        .expect("should decode as tuples");
+    assert_eq!(
+        decoded,
+        (
+            (U512::from(1), U512::from(2), U512::from(3)),
+            (U512::from(4), U512::from(5)),
+            (U512::from(6), U512::from(7)),
+        )
+    );
+
+    let position = LiquidityPosition {
+        shares: U512::from(1),
+        cspr_fee_debt: U512::from(2),
+        token_fee_debt: U512::from(3),
+    };
+    let decoded = CLValue::from_t(position)
+        .expect("should encode position")
+        .into_t::<(U512, U512, U512)>()
+        .expect("should decode as tuple");
+    assert_eq!(decoded, (U512::from(1), U512::from(2), U512::from(3)));
+}
+
+#[test]
+fn should_add_and_remove_liquidity_with_tax() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let pool = Key::from(cowl_cep18_token_package_hash);
+
+    let admin_cspr_amount = U512::from(100_000_000_000u64);
+    cowl_swap_deposit_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        admin_cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(50_000_000_000u64);
+    let token_amount = U256::from(100_000_000_000u64);
+    cowl_swap_add_liquidity(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        token_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let mut expected_state = LiquidityState::default();
+    let mut expected_position = LiquidityPosition::default();
+    let expected_shares = expected_state
+        .add(
+            &mut expected_position,
+            cspr_amount,
+            U512::from(100_000_000_000u64),
+        )
+        .unwrap();
+
+    let shares_key = *builder
+        .get_account(liquidity)
+        .unwrap()
+        .named_keys()
+        .get(ARG_SHARES)
+        .expect("shares uref should exist");
+    let shares = builder
+        .query(None, shares_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U512>()
+        .expect("should be U512.");
+    assert_eq!(shares, expected_shares);
+
+    let event: AddLiquidity = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.provider, Key::from(liquidity));
+    assert_eq!(event.cspr_amount, cspr_amount);
+    assert_eq!(event.token_amount, token_amount);
+    assert_eq!(event.shares, shares);
+
+    // Another account cannot remove the provider's shares
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    cowl_swap_remove_liquidity(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        shares,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientShares as u16,
+        "should not allow to remove shares not held",
+    );
+
+    cowl_swap_token_to_cspr(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from(10_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    let swap_event: CowlToCspr = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    expected_state
+        .buy_tokens(
+            U512::from(swap_event.cowl_amount.as_u128()),
+            swap_event.cspr_amount,
+            swap_event.tax_amount,
+            admin_cspr_amount + cspr_amount,
+        )
+        .unwrap();
+
+    cowl_swap_remove_liquidity(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &liquidity,
+        &pool,
+        shares,
+    )
+    .expect_success()
+    .commit();
+
+    let withdrawal = expected_state
+        .remove(&mut expected_position, shares)
+        .unwrap();
+    assert!(!withdrawal.cspr_fees.is_zero());
+    // Providers hold a third of the CSPR reserve, so paid a third of the CSPR bought for tokens
+    assert!(withdrawal.cspr_amount < cspr_amount);
+    assert!(withdrawal.token_amount > U512::from(100_000_000_000u64));
+
+    let event: RemoveLiquidity = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(event.provider, Key::from(liquidity));
+    assert_eq!(event.shares, shares);
+    assert_eq!(event.cspr_fees, withdrawal.cspr_fees);
+    assert_eq!(
+        event.cspr_amount,
+        withdrawal.cspr_amount + withdrawal.cspr_fees
+    );
+    assert_eq!(
+        event.token_amount,
+        U256::from(withdrawal.token_amount.as_u128())
+    );
+    assert!(event.token_fees.is_zero());
+}
+
+#[test]
+fn should_not_withdraw_cspr_owned_by_providers() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    cowl_swap_deposit_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        U512::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_add_liquidity(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U512::from(50_000_000_000u64),
+        U256::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_withdraw_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        U512::from(120_000_000_000u64),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientLiquidity as u16,
+        "should not allow admins to withdraw provider liquidity",
+    );
+
+    cowl_swap_withdraw_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        U512::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+}
@@ -0,0 +1,171 @@
+use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR};
+use casper_types::{ContractHash, Key};
+use cowl_swap::{
+    constants::{
+        ARG_POOL_TOKENS, ARG_REFERRAL_BONUS, ARG_STORAGE_VERSION, ARG_SWAP_COUNT,
+        DICT_BONDING_CURVES, DICT_CLAIM_START_TIMES, DICT_CONTRACT_NAMED_KEYS, DICT_CONTRIBUTIONS,
+        DICT_ORACLE_FEEDS, DICT_POOL_PRICING_MODES, DICT_POOL_SWAP_STATS, DICT_REFERRALS,
+        DICT_RESERVES, DICT_SALES, DICT_SWAP_POOLS, DICT_SWAP_STATS, DICT_VESTING_ALLOCATIONS,
+        DICT_VESTING_PHASES, DICT_VESTING_SCHEDULES,
+    },
+    events::{EventHeader, Migrated, Upgrade},
+    migrations::{INITIAL_STORAGE_VERSION, STORAGE_VERSION},
+};
+use vesting_tests::support::get_event;
+
+use crate::utility::{
+    constants::SWAP_CONTRACT_V1_0_0_WASM,
+    installer_request_builders::{
+        cowl_swap_upgrade, default_args, setup, setup_with_wasm_and_args, TestContext,
+    },
+};
+
+fn get_storage_version(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+) -> u32 {
+    builder
+        .query(
+            None,
+            cowl_swap_contract_hash.into(),
+            &[ARG_STORAGE_VERSION.to_string()],
+        )
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<u32>()
+        .expect("should be u32.")
+}
+
+fn assert_no_event(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+    index: u32,
+) {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(casper_event_standard::EVENTS_DICT)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, &index.to_string())
+        .expect_err("should not have dictionary value for this event index");
+}
+
+#[test]
+fn should_store_storage_version_on_install() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup();
+
+    assert_eq!(
+        get_storage_version(&builder, cowl_swap_contract_hash),
+        STORAGE_VERSION
+    );
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    assert_eq!(
+        get_storage_version(&builder, cowl_swap_contract_hash),
+        STORAGE_VERSION
+    );
+
+    // Nothing to migrate, only the Upgrade event is emitted
+    let _: Upgrade = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_no_event(&builder, cowl_swap_contract_hash, 1);
+}
+
+#[test]
+fn should_migrate_storage_when_upgrading_from_v1_0_0() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup_with_wasm_and_args(SWAP_CONTRACT_V1_0_0_WASM, default_args());
+
+    let named_keys = builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .clone();
+    assert!(!named_keys.contains_key(ARG_STORAGE_VERSION));
+    assert!(!named_keys.contains_key(DICT_RESERVES));
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    let upgraded_swap_contract_hash: ContractHash = builder
+        .get_contract_package(
+            builder
+                .get_contract(cowl_swap_contract_hash)
+                .expect("should have swap contract")
+                .contract_package_hash(),
+        )
+        .expect("should have swap package")
+        .current_contract_hash()
+        .expect("should have current version");
+
+    assert_eq!(
+        get_storage_version(&builder, upgraded_swap_contract_hash),
+        STORAGE_VERSION
+    );
+
+    let named_keys = builder
+        .get_contract(upgraded_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .clone();
+    assert!(named_keys.contains_key(DICT_RESERVES));
+    assert!(named_keys.contains_key(DICT_SWAP_STATS));
+    assert!(named_keys.contains_key(ARG_SWAP_COUNT));
+    assert!(named_keys.contains_key(DICT_SWAP_POOLS));
+    assert!(named_keys.contains_key(ARG_POOL_TOKENS));
+    assert!(named_keys.contains_key(DICT_POOL_PRICING_MODES));
+    assert!(named_keys.contains_key(DICT_ORACLE_FEEDS));
+    assert!(named_keys.contains_key(DICT_BONDING_CURVES));
+    assert!(named_keys.contains_key(ARG_REFERRAL_BONUS));
+    assert!(named_keys.contains_key(DICT_REFERRALS));
+    assert!(named_keys.contains_key(DICT_VESTING_SCHEDULES));
+    assert!(named_keys.contains_key(DICT_VESTING_ALLOCATIONS));
+    assert!(named_keys.contains_key(DICT_SALES));
+    assert!(named_keys.contains_key(DICT_CONTRIBUTIONS));
+    assert!(named_keys.contains_key(DICT_POOL_SWAP_STATS));
+    assert!(named_keys.contains_key(DICT_VESTING_PHASES));
+    assert!(named_keys.contains_key(DICT_CLAIM_START_TIMES));
+    assert!(named_keys.contains_key(DICT_CONTRACT_NAMED_KEYS));
+
+    let actual_event: Migrated = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    let expected_event = Migrated::new(
+        EventHeader::new(
+            1,
+            actual_event.header.block_time,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            None,
+        ),
+        INITIAL_STORAGE_VERSION,
+        STORAGE_VERSION,
+    );
+    assert_eq!(actual_event, expected_event, "Expected Migrated event.");
+
+    // A second upgrade finds storage up to date and runs no migration
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    let _: Upgrade = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    assert_no_event(&builder, cowl_swap_contract_hash, 3);
+}
@@ -2,8 +2,12 @@ use crate::utility::installer_request_builders::{
     cowl_swap_set_modalities, setup_with_args, TestContext,
 };
 use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
-use casper_types::{runtime_args, RuntimeArgs};
-use cowl_swap::{constants::ARG_EVENTS_MODE, enums::EventsMode, events::SetModalities};
+use casper_types::{runtime_args, Key, RuntimeArgs};
+use cowl_swap::{
+    constants::ARG_EVENTS_MODE,
+    enums::EventsMode,
+    events::{EventHeader, SetModalities},
+};
 use vesting_tests::support::get_event;
 
 #[test]
@@ -83,8 +87,13 @@ fn should_toggle_events_mode() {
     assert_eq!(events_mode, EventsMode::CES as u8);
 
     // Expect SetModalities event
-    let expected_event = SetModalities::new();
+    // The first SetModalities call ran without events and left sequence id 0 unused
     let actual_event: SetModalities = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    let expected_event = SetModalities::new(
+        EventHeader::new(0, actual_event.header.block_time, Key::from(owner), None),
+        EventsMode::NoEvents as u8,
+        EventsMode::CES as u8,
+    );
     assert_eq!(
         actual_event, expected_event,
         "Expected SetModalities event."
@@ -187,8 +196,12 @@ fn should_emit_event_on_set_modalities_with_events_mode_ces() {
     assert_eq!(events_mode, EventsMode::CES as u8);
 
     // Expect SetModalities event
-    let expected_event = SetModalities::new();
     let actual_event: SetModalities = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    let expected_event = SetModalities::new(
+        EventHeader::new(0, actual_event.header.block_time, Key::from(owner), None),
+        EventsMode::NoEvents as u8,
+        EventsMode::CES as u8,
+    );
     assert_eq!(
         actual_event, expected_event,
         "Expected SetModalities event."
@@ -0,0 +1,172 @@
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_types::{
+    bytesrepr::{Bytes, FromBytes},
+    runtime_args, ContractHash, Key, RuntimeArgs, U256,
+};
+use cowl_swap::{
+    constants::{ARG_EVENTS_MODE, ARG_NATIVE_EVENTS_COUNT, DICT_NATIVE_EVENTS},
+    enums::EventsMode,
+    events::{DepositCowl, EventHeader},
+};
+use vesting_tests::{constants::ACCOUNT_LIQUIDITY, support::get_event};
+
+use crate::utility::installer_request_builders::{
+    cowl_swap_deposit_cowl, setup_with_args, TestContext,
+};
+
+fn get_native_events_count(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+) -> u64 {
+    builder
+        .query(
+            None,
+            cowl_swap_contract_hash.into(),
+            &[ARG_NATIVE_EVENTS_COUNT.to_string()],
+        )
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<u64>()
+        .expect("should be u64.")
+}
+
+fn get_native_event<T: FromBytes>(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+    event_id: u64,
+) -> T {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(DICT_NATIVE_EVENTS)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    let bytes = builder
+        .query_dictionary_item(None, dictionary_seed_uref, &event_id.to_string())
+        .expect("should have native event")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<Bytes>()
+        .expect("should be bytes");
+
+    let (event, remainder) = T::from_bytes(&bytes).expect("should decode event");
+    assert!(remainder.is_empty());
+    event
+}
+
+#[test]
+fn should_record_native_events_sequentially() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ARG_EVENTS_MODE => EventsMode::Native as u8
+    });
+
+    let named_keys = builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .clone();
+    assert!(!named_keys.contains_key(casper_event_standard::EVENTS_DICT));
+    assert_eq!(
+        get_native_events_count(&builder, cowl_swap_contract_hash),
+        0
+    );
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    for _ in 0..2 {
+        cowl_swap_deposit_cowl(
+            &mut builder,
+            &liquidity,
+            &cowl_cep18_token_package_hash,
+            &cowl_swap_contract_package,
+            U256::one(),
+        )
+        .expect_success()
+        .commit();
+    }
+
+    assert_eq!(
+        get_native_events_count(&builder, cowl_swap_contract_hash),
+        2
+    );
+
+    for event_id in 0..2 {
+        let actual_event: DepositCowl =
+            get_native_event(&builder, cowl_swap_contract_hash, event_id);
+        let expected_event = DepositCowl::new(
+            EventHeader::new(
+                event_id,
+                actual_event.header.block_time,
+                Key::from(liquidity),
+                None,
+            ),
+            Key::from(cowl_cep18_token_package_hash),
+            Key::from(liquidity),
+            U256::one(),
+        );
+        assert_eq!(actual_event, expected_event, "Expected DepositCowl event.");
+    }
+}
+
+#[test]
+fn should_record_native_and_ces_events() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ARG_EVENTS_MODE => EventsMode::NativeAndCES as u8
+    });
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::one(),
+    )
+    .expect_success()
+    .commit();
+
+    let ces_event: DepositCowl = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    let expected_event = DepositCowl::new(
+        EventHeader::new(0, ces_event.header.block_time, Key::from(liquidity), None),
+        Key::from(cowl_cep18_token_package_hash),
+        Key::from(liquidity),
+        U256::one(),
+    );
+
+    assert_eq!(ces_event, expected_event, "Expected CES DepositCowl event.");
+
+    let native_event: DepositCowl = get_native_event(&builder, cowl_swap_contract_hash, 0);
+    assert_eq!(
+        native_event, expected_event,
+        "Expected native DepositCowl event."
+    );
+    assert_eq!(
+        get_native_events_count(&builder, cowl_swap_contract_hash),
+        1
+    );
+}
@@ -0,0 +1,377 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{CLValue, Key, U256, U512};
+use cowl_swap::{
+    constants::{ARG_BALANCE_COWL, TAX_RATE},
+    enums::PricingMode,
+    error::SwapError,
+    events::{OracleConfigUpdate, OraclePriceUpdate},
+    oracle::{OracleFeed, ORACLE_PRICE_PRECISION},
+    pricing::{price_cspr_to_token, price_cspr_to_token_at_oracle, price_token_to_cspr_at_oracle},
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_change_security, cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl,
+        cowl_swap_set_oracle_config, cowl_swap_set_pricing_mode, cowl_swap_update_price, setup,
+        SecurityLists, TestContext,
+    },
+    pools::test_pool,
+};
+
+fn configured_feed() -> OracleFeed {
+    let mut feed = OracleFeed::default();
+    feed.configure(60, 500, 100).unwrap();
+    feed
+}
+
+#[test]
+fn should_not_configure_invalid_oracle_bounds() {
+    let mut feed = OracleFeed::default();
+
+    let error = feed.configure(0, 500, 100).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidOracleConfig));
+
+    let error = feed.configure(60, 0, 100).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidOracleConfig));
+
+    let error = feed.configure(60, 500, 10_000).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidOracleConfig));
+
+    assert!(!feed.is_configured());
+}
+
+#[test]
+fn should_type_oracle_feed_as_nested_tuples() {
+    let mut feed = configured_feed();
+    feed.update_price(ORACLE_PRICE_PRECISION * 5, 100, 120)
+        .unwrap();
+
+    let decoded = CLValue::from_t(feed)
+        .expect("should encode feed")
+        .into_t::<((U512, u64, u64), (u32, u32))>()
+        .expect("should decode as tuples");
+    assert_eq!(
+        decoded,
+        (
+            (feed.price, feed.timestamp, feed.max_age),
+            (feed.max_deviation, feed.spread),
+        )
+    );
+}
+
+#[test]
+fn should_bound_oracle_price_updates() {
+    let price = ORACLE_PRICE_PRECISION * 5;
+
+    let error = OracleFeed::default()
+        .update_price(price, 1_000, 1_000)
+        .unwrap_err();
+    assert!(matches!(error, SwapError::MissingOracleConfig));
+
+    let mut feed = configured_feed();
+
+    let error = feed.update_price(U512::zero(), 1_000, 1_000).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidOraclePrice));
+
+    let error = feed.update_price(price, 1_001, 1_000).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidOracleTimestamp));
+
+    let error = feed.update_price(price, 900, 1_000).unwrap_err();
+    assert!(matches!(error, SwapError::StaleOraclePrice));
+
+    feed.update_price(price, 1_000, 1_000).unwrap();
+    assert_eq!(feed.fresh_price(1_060), Some(price));
+    assert_eq!(feed.fresh_price(1_061), None);
+
+    // Prices must be newer than the last one
+    let error = feed.update_price(price, 1_000, 1_010).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidOracleTimestamp));
+
+    // 6% away from a fresh price is above the 5% bound
+    let error = feed
+        .update_price(price * 106 / 100, 1_010, 1_010)
+        .unwrap_err();
+    assert!(matches!(error, SwapError::OracleDeviationExceeded));
+
+    feed.update_price(price * 105 / 100, 1_010, 1_010).unwrap();
+    assert_eq!(feed.price, price * 105 / 100);
+
+    // Once the last price is stale any move is accepted
+    feed.update_price(price * 2, 2_000, 2_000).unwrap();
+    assert_eq!(feed.fresh_price(2_000), Some(price * 2));
+}
+
+#[test]
+fn should_price_at_oracle_price_with_spread() {
+    let pool = test_pool(Key::Hash([1u8; 32]));
+    let price = ORACLE_PRICE_PRECISION * 5;
+
+    // 10 CSPR at 5 tokens per CSPR, less a 1% spread and untaxed
+    let quote =
+        price_cspr_to_token_at_oracle(&pool, U512::from(10_000_000_000u64), price, 100).unwrap();
+    assert_eq!(quote.output_amount, U512::from(49_500_000_000u64));
+    assert!(quote.fee_amount.is_zero());
+
+    // 50 tokens back to 10 CSPR, less the spread and then the pool tax
+    let quote =
+        price_token_to_cspr_at_oracle(&pool, U512::from(50_000_000_000u64), price, 100).unwrap();
+    let spread_amount = U512::from(9_900_000_000u64);
+    assert_eq!(quote.fee_amount, spread_amount * TAX_RATE / 100);
+    assert_eq!(quote.output_amount, spread_amount - quote.fee_amount);
+
+    // Without a fresh price the oracle mode is priced with the tiers
+    assert_eq!(
+        price_cspr_to_token(
+            &pool,
+            PricingMode::Oracle,
+            U512::from(10_000_000_000u64),
+            U512::zero(),
+            U512::zero(),
+        )
+        .unwrap(),
+        price_cspr_to_token(
+            &pool,
+            PricingMode::Tiers,
+            U512::from(10_000_000_000u64),
+            U512::zero(),
+            U512::zero(),
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn should_update_price_by_oracle_updater() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let price = ORACLE_PRICE_PRECISION * 5;
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    cowl_swap_update_price(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        price,
+        0,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::MissingOracleConfig as u16,
+        "should not allow a price before the oracle is configured",
+    );
+
+    cowl_swap_set_oracle_config(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        3600,
+        500,
+        100,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to configure the oracle for non admin account",
+    );
+
+    cowl_swap_set_oracle_config(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        3600,
+        500,
+        100,
+    )
+    .expect_success()
+    .commit();
+
+    let event: OracleConfigUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.max_age, 3600);
+    assert_eq!(event.max_deviation, 500);
+    assert_eq!(event.spread, 100);
+
+    cowl_swap_update_price(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        price,
+        0,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow a price from an account without the oracle role",
+    );
+
+    cowl_swap_change_security(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        SecurityLists {
+            admin_list: None,
+            none_list: None,
+            oracle_list: Some(vec![Key::from(account_user_1)]),
+        },
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_update_price(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        price,
+        0,
+    )
+    .expect_success()
+    .commit();
+
+    let event: OraclePriceUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.updater, Key::from(account_user_1));
+    assert_eq!(event.price, price);
+    assert_eq!(event.timestamp, 0);
+
+    cowl_swap_update_price(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        price,
+        0,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidOracleTimestamp as u16,
+        "should not allow a price that is not newer than the last one",
+    );
+}
+
+#[test]
+fn should_cspr_to_cowl_at_oracle_price() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let price = ORACLE_PRICE_PRECISION * 5;
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100_000_000_000u64);
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        PricingMode::Oracle as u8,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_oracle_config(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        3600,
+        500,
+        100,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_update_price(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        price,
+        0,
+    )
+    .expect_success()
+    .commit();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let quote =
+        price_cspr_to_token_at_oracle(&test_pool(Key::Hash([1u8; 32])), cspr_amount, price, 100)
+            .unwrap();
+
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    let actual_balance_cowl = builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.");
+
+    assert_eq!(
+        actual_balance_cowl,
+        cowl_pool_amount - U256::from_dec_str(&quote.output_amount.to_string()).unwrap()
+    );
+}
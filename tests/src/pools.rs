@@ -0,0 +1,443 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+};
+use casper_types::{runtime_args, CLValue, ContractHash, Key, RuntimeArgs, U512};
+use cowl_swap::{
+    constants::{
+        ARG_DURATION, ARG_PAUSED, ARG_POOL, ARG_POOL_TOKENS, ARG_TOKEN_DECIMALS,
+        DEFAULT_TOKEN_DECIMALS, DICT_SWAP_POOLS, ENTRY_POINT_ADD_POOL, RATE_TIERS, TAX_RATE,
+    },
+    error::SwapError,
+    events::PoolUpdate,
+    pools::{Pool, DEFAULT_POOL_ITEM_KEY},
+    rate::RateTier,
+};
+use vesting_tests::{
+    constants::ACCOUNT_USER_1,
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::installer_request_builders::{
+    cowl_swap_add_pool, cowl_swap_cspr_to_cowl, cowl_swap_cspr_to_pool, cowl_swap_update_pool,
+    setup, TestContext,
+};
+
+fn get_default_pool(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+) -> Pool {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(DICT_SWAP_POOLS)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, DEFAULT_POOL_ITEM_KEY)
+        .expect("should have default pool")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<Pool>()
+        .expect("should be Pool.")
+}
+
+fn get_pool_tokens(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+) -> Vec<Key> {
+    let pool_tokens_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_POOL_TOKENS)
+        .expect("must have key");
+
+    builder
+        .query(None, pool_tokens_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<Vec<Key>>()
+        .expect("should be Vec<Key>.")
+}
+
+fn test_pool(token_package: Key) -> Pool {
+    Pool {
+        token_package,
+        token_decimals: 6,
+        rate_tiers: Vec::from([
+            RateTier {
+                cspr_amount: U512::zero(),
+                rate: U512::from(10),
+            },
+            RateTier {
+                cspr_amount: U512::from(1_000_000_000_000u64),
+                rate: U512::from(12),
+            },
+        ]),
+        tax_rate: U512::from(5),
+        start_time: 0,
+        end_time: 3600,
+        paused: false,
+        reserve_prefix: String::new(),
+    }
+}
+
+#[test]
+fn should_create_default_pool_on_install() {
+    let (
+        builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let pool = get_default_pool(&builder, cowl_swap_contract_hash);
+    assert_eq!(pool.token_package, Key::from(cowl_cep18_token_package_hash));
+    assert_eq!(pool.token_decimals, DEFAULT_TOKEN_DECIMALS);
+    assert_eq!(pool.rate_tiers, RATE_TIERS.to_vec());
+    assert_eq!(pool.tax_rate, TAX_RATE);
+    assert_eq!(pool.start_time, 0);
+    assert_eq!(pool.end_time, 86400);
+    assert!(!pool.paused);
+    assert!(pool.is_default());
+
+    assert!(get_pool_tokens(&builder, cowl_swap_contract_hash).is_empty());
+}
+
+#[test]
+fn should_type_pool_as_nested_tuples() {
+    let mut pool = test_pool(Key::Hash([2u8; 32]));
+    pool.reserve_prefix = String::from("pool_1_");
+
+    let (
+        (token_package, token_decimals, rate_tiers),
+        (tax_rate, start_time, end_time),
+        (paused, reserve_prefix),
+    ) = CLValue::from_t(pool.clone())
+        .expect("should encode pool")
+        .into_t::<(
+            (Key, u8, Vec<(U512, U512)>),
+            (U512, u64, u64),
+            (bool, String),
+        )>()
+        .expect("should decode as tuples");
+    assert_eq!(token_package, pool.token_package);
+    assert_eq!(token_decimals, pool.token_decimals);
+    assert_eq!(
+        rate_tiers,
+        pool.rate_tiers
+            .iter()
+            .map(|tier| (tier.cspr_amount, tier.rate))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(tax_rate, pool.tax_rate);
+    assert_eq!(start_time, pool.start_time);
+    assert_eq!(end_time, pool.end_time);
+    assert_eq!(paused, pool.paused);
+    assert_eq!(reserve_prefix, pool.reserve_prefix);
+}
+
+#[test]
+fn should_add_pool_by_admin() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = test_pool(Key::Hash([2u8; 32]));
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to add a pool for non admin account",
+    );
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+    )
+    .expect_success()
+    .commit();
+
+    assert_eq!(
+        get_pool_tokens(&builder, cowl_swap_contract_hash),
+        vec![pool.token_package]
+    );
+
+    let event: PoolUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.pool, pool.token_package);
+    assert_eq!(event.token_decimals, pool.token_decimals);
+    assert_eq!(
+        event.rate_tiers,
+        pool.rate_tiers
+            .iter()
+            .map(|tier| (tier.cspr_amount, tier.rate))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(event.tax_rate, pool.tax_rate);
+    assert_eq!(event.start_time, pool.start_time);
+    assert_eq!(event.end_time, pool.end_time);
+    assert!(!event.paused);
+
+    // The default pool is untouched
+    assert_eq!(
+        get_default_pool(&builder, cowl_swap_contract_hash).rate_tiers,
+        RATE_TIERS.to_vec()
+    );
+}
+
+#[test]
+fn should_not_add_existing_pool() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let pool = test_pool(Key::Hash([2u8; 32]));
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::PoolAlreadyExists as u16,
+        "should not allow to add a pool twice",
+    );
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &test_pool(Key::from(cowl_cep18_token_package_hash)),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::PoolAlreadyExists as u16,
+        "should not allow to add a pool of the configured token",
+    );
+}
+
+#[test]
+fn should_not_add_pool_with_invalid_config() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup();
+
+    let mut pool = test_pool(Key::Hash([2u8; 32]));
+    pool.rate_tiers.reverse();
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidRateTiers as u16,
+        "should not allow tiers with decreasing thresholds",
+    );
+
+    let mut pool = test_pool(Key::Hash([2u8; 32]));
+    pool.tax_rate = U512::from(101);
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidTaxRate as u16,
+        "should not allow a tax above 100 percent",
+    );
+
+    let mut pool = test_pool(Key::Hash([2u8; 32]));
+    pool.end_time = pool.start_time;
+
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidTimeWindow as u16,
+        "should not allow an empty window",
+    );
+
+    let add_pool_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        cowl_swap_contract_hash,
+        ENTRY_POINT_ADD_POOL,
+        runtime_args! {
+            ARG_POOL => Key::Hash([2u8; 32]),
+            ARG_TOKEN_DECIMALS => DEFAULT_TOKEN_DECIMALS,
+            ARG_DURATION => 86400_u64,
+        },
+    )
+    .build();
+    builder.exec(add_pool_request).expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::MissingStartTime as u16,
+        "should not add a pool without a start time",
+    );
+}
+
+#[test]
+fn should_not_swap_in_paused_pool() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    cowl_swap_update_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            ARG_POOL => Key::from(cowl_cep18_token_package_hash),
+            ARG_PAUSED => 1u8,
+        },
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidPaused as u16,
+        "should not allow a pause flag that is not a bool",
+    );
+
+    cowl_swap_update_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            ARG_POOL => Key::from(cowl_cep18_token_package_hash),
+            ARG_PAUSED => true,
+        },
+    )
+    .expect_success()
+    .commit();
+
+    assert!(get_default_pool(&builder, cowl_swap_contract_hash).paused);
+
+    let event: PoolUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert!(event.paused);
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        U512::from(1_000_000_000_000u64),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::PoolPaused as u16,
+        "should not allow to swap in a paused pool",
+    );
+}
+
+#[test]
+fn should_not_swap_in_missing_pool() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    cowl_swap_cspr_to_pool(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        &Key::Hash([2u8; 32]),
+        U512::from(1_000_000_000_000u64),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::MissingPool as u16,
+        "should not allow to swap in a pool that was not added",
+    );
+}
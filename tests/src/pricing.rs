@@ -0,0 +1,256 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{Key, U256, U512};
+use cowl_swap::{
+    constants::{ARG_BALANCE_COWL, MIN_SWAP_AMOUNT, RATE_TIERS, TAX_RATE},
+    enums::PricingMode,
+    error::SwapError,
+    events::PricingModeUpdate,
+    pricing::{constant_product_amount, price_cspr_to_token, price_token_to_cspr},
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl, cowl_swap_deposit_cspr,
+        cowl_swap_set_pricing_mode, setup, TestContext,
+    },
+    pools::test_pool,
+};
+
+#[test]
+fn should_price_on_constant_product_curve() {
+    let pool = test_pool(Key::Hash([1u8; 32]));
+    let cspr_reserve = U512::from(50_000_000_000u64);
+    let token_reserve = U512::from(100_000_000_000u64);
+
+    assert_eq!(
+        constant_product_amount(U512::from(10), U512::from(90), U512::from(1_000)).unwrap(),
+        U512::from(100)
+    );
+
+    // 10 CSPR against 50 CSPR / 100 tokens: 100 * 10 / 60 tokens, minus the pool fee
+    let quote = price_cspr_to_token(
+        &pool,
+        PricingMode::ConstantProduct,
+        U512::from(10_000_000_000u64),
+        cspr_reserve,
+        token_reserve,
+    )
+    .unwrap();
+    let gross_amount = U512::from(16_666_666_666u64);
+    assert_eq!(quote.base_rate, U512::zero());
+    assert_eq!(quote.fee_amount, gross_amount * TAX_RATE / 100);
+    assert_eq!(quote.output_amount, gross_amount - quote.fee_amount);
+
+    // 100 tokens against 100 tokens / 50 CSPR: half of the CSPR, minus the pool fee
+    let quote = price_token_to_cspr(
+        &pool,
+        PricingMode::ConstantProduct,
+        U512::from(100_000_000_000u64),
+        cspr_reserve,
+        token_reserve,
+    )
+    .unwrap();
+    let gross_amount = U512::from(25_000_000_000u64);
+    assert_eq!(quote.fee_amount, gross_amount * TAX_RATE / 100);
+    assert_eq!(quote.output_amount, gross_amount - quote.fee_amount);
+}
+
+#[test]
+fn should_keep_tier_pricing_by_default() {
+    let pool = test_pool(Key::Hash([1u8; 32]));
+
+    let quote = price_cspr_to_token(
+        &pool,
+        PricingMode::Tiers,
+        U512::from(10_000_000_000u64),
+        U512::zero(),
+        U512::zero(),
+    )
+    .unwrap();
+    assert_eq!(quote.base_rate, RATE_TIERS[0].rate);
+    assert_eq!(
+        quote.output_amount,
+        U512::from(10_000_000_000u64) * RATE_TIERS[0].rate
+    );
+    assert!(quote.fee_amount.is_zero());
+}
+
+#[test]
+fn should_not_price_on_empty_curve() {
+    let pool = test_pool(Key::Hash([1u8; 32]));
+
+    let error = price_cspr_to_token(
+        &pool,
+        PricingMode::ConstantProduct,
+        MIN_SWAP_AMOUNT,
+        U512::zero(),
+        U512::from(100_000_000_000u64),
+    )
+    .unwrap_err();
+    assert!(matches!(error, SwapError::InsufficientLiquidity));
+
+    let error = price_cspr_to_token(
+        &pool,
+        PricingMode::ConstantProduct,
+        MIN_SWAP_AMOUNT - 1,
+        U512::from(50_000_000_000u64),
+        U512::from(100_000_000_000u64),
+    )
+    .unwrap_err();
+    assert!(matches!(error, SwapError::BelowMinimumSwap));
+}
+
+#[test]
+fn should_set_pricing_mode_by_admin() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        PricingMode::ConstantProduct as u8,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set the pricing mode for non admin account",
+    );
+
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        u8::MAX,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidPricingMode as u16,
+        "should not allow an unknown pricing mode",
+    );
+
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        PricingMode::ConstantProduct as u8,
+    )
+    .expect_success()
+    .commit();
+
+    let event: PricingModeUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.pricing_mode, PricingMode::ConstantProduct as u8);
+}
+
+#[test]
+fn should_cspr_to_cowl_on_constant_product_curve() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100_000_000_000u64);
+    let cspr_pool_amount = U512::from(50_000_000_000u64);
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_deposit_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        cspr_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &Key::from(cowl_cep18_token_package_hash),
+        PricingMode::ConstantProduct as u8,
+    )
+    .expect_success()
+    .commit();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    let cspr_amount = U512::from(10_000_000_000u64);
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let quote = price_cspr_to_token(
+        &test_pool(Key::Hash([1u8; 32])),
+        PricingMode::ConstantProduct,
+        cspr_amount,
+        cspr_pool_amount,
+        U512::from(100_000_000_000u64),
+    )
+    .unwrap();
+
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    let actual_balance_cowl = builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.");
+
+    assert_eq!(
+        actual_balance_cowl,
+        cowl_pool_amount - U256::from_dec_str(&quote.output_amount.to_string()).unwrap()
+    );
+}
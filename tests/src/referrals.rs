@@ -0,0 +1,391 @@
+use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR};
+use casper_types::{ContractHash, Key, U256, U512};
+use cowl_swap::{
+    constants::{ARG_BALANCE_COWL, DICT_REFERRALS, RATE_TIERS},
+    error::SwapError,
+    events::{CsprToCowl, ReferralBonusUpdate, ReferralReward, TokensVested},
+    referrals::{referral_reward, validate_referral_bonus},
+    vesting::VestingSchedule,
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1, ACCOUNT_USER_2},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_cspr_to_cowl, cowl_swap_cspr_to_cowl_with_referrer, cowl_swap_deposit_cowl,
+        cowl_swap_set_referral_bonus, cowl_swap_set_soft_cap, cowl_swap_set_vesting_schedule,
+        setup, TestContext,
+    },
+    keys::item_key,
+};
+
+fn get_referral_total(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+    referrer: Key,
+) -> U256 {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(DICT_REFERRALS)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, &item_key(&referrer))
+        .expect("should have referral total")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.")
+}
+
+#[test]
+fn should_compute_referral_reward_in_basis_points() {
+    assert_eq!(
+        referral_reward(U256::from(30_000_000_000u64), 500).unwrap(),
+        U256::from(1_500_000_000u64)
+    );
+    assert!(referral_reward(U256::from(30_000_000_000u64), 0)
+        .unwrap()
+        .is_zero());
+
+    assert!(validate_referral_bonus(10_000).is_ok());
+    let error = validate_referral_bonus(10_001).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidReferralBonus));
+}
+
+#[test]
+fn should_pay_referral_reward_on_cspr_to_cowl() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    let account_user_2 = *test_accounts.get(&ACCOUNT_USER_2).unwrap();
+
+    cowl_swap_set_referral_bonus(&mut builder, &cowl_swap_contract_hash, &account_user_1, 500)
+        .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set the referral bonus for non admin account",
+    );
+
+    cowl_swap_set_referral_bonus(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        10_001,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidReferralBonus as u16,
+        "should not allow a bonus above 100 percent",
+    );
+
+    cowl_swap_set_referral_bonus(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        500,
+    )
+    .expect_success()
+    .commit();
+
+    let event: ReferralBonusUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.referral_bonus, 500);
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100_000_000_000u64);
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+
+    cowl_swap_cspr_to_cowl_with_referrer(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        &Key::from(account_user_1),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::SelfReferral as u16,
+        "should not allow to refer oneself",
+    );
+
+    let referrer = Key::from(account_user_2);
+    cowl_swap_cspr_to_cowl_with_referrer(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        &referrer,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+    let reward_amount = referral_reward(cowl_amount, 500).unwrap();
+
+    let swap_event: CsprToCowl = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    assert_eq!(swap_event.cowl_amount, cowl_amount);
+
+    let event: ReferralReward = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(event.pool, Key::from(cowl_cep18_token_package_hash));
+    assert_eq!(event.referrer, referrer);
+    assert_eq!(event.buyer, Key::from(account_user_1));
+    assert_eq!(event.token_amount, cowl_amount);
+    assert_eq!(event.reward_amount, reward_amount);
+
+    assert_eq!(
+        get_referral_total(&builder, cowl_swap_contract_hash, referrer),
+        reward_amount
+    );
+
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    let actual_balance_cowl = builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.");
+
+    assert_eq!(
+        actual_balance_cowl,
+        cowl_pool_amount - cowl_amount - reward_amount
+    );
+}
+
+#[test]
+fn should_vest_referral_reward_with_the_purchase() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    let referrer = Key::from(*test_accounts.get(&ACCOUNT_USER_2).unwrap());
+
+    cowl_swap_set_referral_bonus(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        500,
+    )
+    .expect_success()
+    .commit();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100_000_000_000u64);
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(VestingSchedule {
+            start_time: 0,
+            cliff_duration: 100,
+            vesting_duration: 1_000,
+        }),
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_cspr_to_cowl_with_referrer(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        &referrer,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+    let reward_amount = referral_reward(cowl_amount, 500).unwrap();
+
+    let event: ReferralReward = get_event(&builder, &cowl_swap_contract_hash.into(), 5);
+    assert_eq!(event.referrer, referrer);
+    assert_eq!(event.reward_amount, reward_amount);
+
+    // The bonus vests on the referrer's allocation like the purchase does on the buyer's
+    let event: TokensVested = get_event(&builder, &cowl_swap_contract_hash.into(), 6);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.beneficiary, referrer);
+    assert_eq!(event.amount, reward_amount);
+    assert_eq!(event.total, reward_amount);
+
+    assert_eq!(
+        get_referral_total(&builder, cowl_swap_contract_hash, referrer),
+        reward_amount
+    );
+
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    let actual_balance_cowl = builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.");
+
+    assert_eq!(actual_balance_cowl, cowl_pool_amount);
+}
+
+#[test]
+fn should_refuse_referrals_while_the_sale_raises() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    let referrer = Key::from(*test_accounts.get(&ACCOUNT_USER_2).unwrap());
+
+    cowl_swap_set_referral_bonus(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        500,
+    )
+    .expect_success()
+    .commit();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_set_soft_cap(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        cspr_amount * 2,
+    )
+    .expect_success()
+    .commit();
+
+    // The bonus could not be paid out of a sale that may still be refunded
+    cowl_swap_cspr_to_cowl_with_referrer(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        &referrer,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::ReferralWhileRaising as u16,
+        "should not allow a referral while the sale raises",
+    );
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount * 2,
+    )
+    .expect_success()
+    .commit();
+
+    // Once the soft cap is met referrers earn their bonus again
+    cowl_swap_cspr_to_cowl_with_referrer(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        &referrer,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+    assert_eq!(
+        get_referral_total(&builder, cowl_swap_contract_hash, referrer),
+        referral_reward(cowl_amount, 500).unwrap()
+    );
+}
@@ -0,0 +1,432 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+};
+use casper_types::{
+    bytesrepr::FromBytes, runtime_args, system::mint, CLTyped, ContractHash, Key, RuntimeArgs,
+    U256, U512,
+};
+use cowl_swap::{
+    constants::{
+        ARG_MIN_RESERVE_COWL, ARG_MIN_RESERVE_CSPR, ARG_PURSE, ARG_WARNING_RESERVE_COWL,
+        ARG_WARNING_RESERVE_CSPR, DICT_RESERVES, RATE_TIERS,
+    },
+    error::SwapError,
+    events::{CowlReserveLow, EventHeader, Reconcile},
+    reserves::{
+        reserve_item_key, ReserveCounter, RESERVE_ASSET_COWL, RESERVE_ASSET_CSPR,
+        RESERVE_ASSET_PACKAGE_CSPR,
+    },
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    constants::MINIMUM_TRANSFER_AMOUNT,
+    installer_request_builders::{
+        cowl_cep18_token_transfer, cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl,
+        cowl_swap_deposit_cspr, cowl_swap_reconcile, cowl_swap_set_reserve_limits, setup,
+        setup_with_args, TestContext,
+    },
+};
+
+fn get_reserve<T: CLTyped + FromBytes>(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+    asset: &str,
+    counter: ReserveCounter,
+) -> T {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(DICT_RESERVES)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(
+            None,
+            dictionary_seed_uref,
+            &reserve_item_key(asset, counter),
+        )
+        .expect("should have reserve counter")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<T>()
+        .expect("should convert reserve counter")
+}
+
+#[test]
+fn should_account_deposits_in_reserves() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_amount = U256::from(1000);
+    let cspr_amount = U512::from_dec_str(MINIMUM_TRANSFER_AMOUNT).unwrap();
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_deposit_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_deposited: U256 = get_reserve(
+        &builder,
+        cowl_swap_contract_hash,
+        RESERVE_ASSET_COWL,
+        ReserveCounter::Deposited,
+    );
+    assert_eq!(cowl_deposited, cowl_amount);
+
+    let cspr_deposited: U512 = get_reserve(
+        &builder,
+        cowl_swap_contract_hash,
+        RESERVE_ASSET_CSPR,
+        ReserveCounter::Deposited,
+    );
+    assert_eq!(cspr_deposited, cspr_amount);
+}
+
+#[test]
+fn should_reconcile_and_sweep_stray_cowl() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let stray_amount = U256::from(42);
+
+    // Direct CEP-18 transfer bypassing deposit_cowl
+    cowl_cep18_token_transfer(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        Key::from(cowl_swap_contract_package),
+        stray_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_reconcile(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        true,
+    )
+    .expect_success()
+    .commit();
+
+    let actual_event: Reconcile = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    let expected_event = Reconcile::new(
+        EventHeader::new(
+            0,
+            actual_event.header.block_time,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            None,
+        ),
+        Key::from(cowl_cep18_token_package_hash),
+        U512::zero(),
+        U512::zero(),
+        U256::zero(),
+        stray_amount,
+        true,
+    );
+    assert_eq!(actual_event, expected_event, "Expected Reconcile event.");
+
+    let cowl_swept: U256 = get_reserve(
+        &builder,
+        cowl_swap_contract_hash,
+        RESERVE_ASSET_COWL,
+        ReserveCounter::Swept,
+    );
+    assert_eq!(cowl_swept, stray_amount);
+}
+
+#[test]
+fn should_count_swept_cspr_package_wide() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            ..
+        },
+    ) = setup();
+
+    let contract_purse = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_PURSE)
+        .expect("must have purse")
+        .as_uref()
+        .expect("must convert to purse");
+    let stray_amount = U512::from_dec_str(MINIMUM_TRANSFER_AMOUNT).unwrap();
+
+    // Direct transfer to the purse bypassing deposit_cspr
+    let transfer_request = ExecuteRequestBuilder::transfer(
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            mint::ARG_AMOUNT => stray_amount,
+            mint::ARG_TARGET => contract_purse,
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build();
+    builder.exec(transfer_request).expect_success().commit();
+
+    cowl_swap_reconcile(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        true,
+    )
+    .expect_success()
+    .commit();
+
+    let event: Reconcile = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    assert_eq!(event.cspr_actual - event.cspr_expected, stray_amount);
+
+    // The purse is shared, so the sweep is not charged to the pool reconciled
+    let cspr_swept: U512 = get_reserve(
+        &builder,
+        cowl_swap_contract_hash,
+        RESERVE_ASSET_PACKAGE_CSPR,
+        ReserveCounter::Swept,
+    );
+    assert_eq!(cspr_swept, stray_amount);
+}
+
+#[test]
+fn should_fail_reconcile_when_non_admin() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    cowl_swap_reconcile(&mut builder, &liquidity, &cowl_swap_contract_package, true)
+        .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "reconcile is only admin entrypoint",
+    );
+}
+
+#[test]
+fn should_fail_cspr_to_cowl_below_min_reserve() {
+    let cowl_pool_amount = U256::from_dec_str("100000000000").unwrap();
+    let cspr_transfer_amount = U512::from_dec_str("10000000000").unwrap();
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ARG_MIN_RESERVE_COWL => cowl_pool_amount,
+    });
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_transfer_amount,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+
+    assert_expected_error(
+        error,
+        SwapError::InsufficientLiquidity as u16,
+        "should not swap below the minimum COWL reserve",
+    );
+}
+
+#[test]
+fn should_emit_event_when_cowl_reserve_crosses_warning() {
+    let cowl_pool_amount = U256::from_dec_str("100000000000").unwrap();
+    let cowl_warning_amount = U256::from_dec_str("80000000000").unwrap();
+    let cspr_transfer_amount = U512::from_dec_str("10000000000").unwrap();
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ARG_WARNING_RESERVE_COWL => cowl_warning_amount,
+    });
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_transfer_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_transfer_amount * RATE_TIERS.first().unwrap().rate).to_string())
+            .unwrap();
+
+    // Expect CowlReserveLow event after DepositCowl and CsprToCowl
+    let actual_event: CowlReserveLow = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    let expected_event = CowlReserveLow::new(
+        EventHeader::new(
+            2,
+            actual_event.header.block_time,
+            Key::from(account_user_1),
+            None,
+        ),
+        Key::from(cowl_cep18_token_package_hash),
+        cowl_pool_amount - cowl_amount,
+        cowl_warning_amount,
+    );
+    assert_eq!(
+        actual_event, expected_event,
+        "Expected CowlReserveLow event."
+    );
+}
+
+#[test]
+fn should_fail_to_set_warning_reserve_below_min_reserve() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ARG_MIN_RESERVE_COWL => U256::from(100),
+        ARG_MIN_RESERVE_CSPR => U512::from(100),
+    });
+
+    cowl_swap_set_reserve_limits(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        runtime_args! {
+            ARG_WARNING_RESERVE_COWL => U256::from(99),
+        },
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+
+    assert_expected_error(
+        error,
+        SwapError::InvalidReserveWarning as u16,
+        "should not warn below the minimum COWL reserve",
+    );
+
+    cowl_swap_set_reserve_limits(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        runtime_args! {
+            ARG_MIN_RESERVE_CSPR => U512::from(200),
+            ARG_WARNING_RESERVE_CSPR => U512::from(150),
+        },
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+
+    assert_expected_error(
+        error,
+        SwapError::InvalidReserveWarning as u16,
+        "should not warn below the minimum CSPR reserve",
+    );
+
+    cowl_swap_set_reserve_limits(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        runtime_args! {
+            ARG_WARNING_RESERVE_COWL => U256::from(100),
+            ARG_WARNING_RESERVE_CSPR => U512::from(150),
+        },
+    )
+    .expect_success()
+    .commit();
+}
@@ -0,0 +1,285 @@
+use casper_engine_test_support::{ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
+use casper_types::{runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs};
+use cowl_swap::{
+    constants::{
+        ADMIN_LIST, ARG_CONTRACT_VERSION, ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_EVENTS_MODE,
+        ARG_ROLLBACK_VERSION, ENTRY_POINT_ROLLBACK,
+    },
+    enums::EventsMode,
+    error::SwapError,
+    events::{EventHeader, Rollback},
+    migrations::STORAGE_VERSION,
+};
+use vesting_tests::{
+    constants::ACCOUNT_USER_1,
+    support::{assert_expected_error, create_dummy_key_pair, get_event},
+};
+
+use crate::utility::{
+    constants::{SWAP_CONTRACT_KEY_NAME, SWAP_CONTRACT_V1_0_0_WASM, SWAP_CONTRACT_VERSION},
+    installer_request_builders::{
+        cowl_swap_rollback, cowl_swap_set_modalities, cowl_swap_upgrade,
+        cowl_swap_upgrade_with_args, default_args, setup, setup_with_args,
+        setup_with_wasm_and_args, TestContext,
+    },
+};
+
+#[test]
+fn should_rollback_to_previous_version() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            ..
+        },
+    ) = setup();
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    let upgraded_cowl_swap: ContractHash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SWAP_CONTRACT_KEY_NAME)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+    assert_ne!(upgraded_cowl_swap, cowl_swap_contract_hash);
+
+    cowl_swap_rollback(&mut builder, &DEFAULT_ACCOUNT_ADDR, 1)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+
+    let current_cowl_swap: ContractHash = account
+        .named_keys()
+        .get(SWAP_CONTRACT_KEY_NAME)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+    assert_eq!(current_cowl_swap, cowl_swap_contract_hash);
+
+    let cowl_swap_contract_version = builder
+        .query(
+            None,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            &[SWAP_CONTRACT_VERSION.to_string()],
+        )
+        .unwrap()
+        .as_cl_value()
+        .unwrap()
+        .to_owned()
+        .into_t::<u32>()
+        .unwrap();
+    assert_eq!(cowl_swap_contract_version, 1_u32);
+
+    let package = builder
+        .get_contract_package(cowl_swap_contract_package)
+        .expect("should have swap package");
+    assert!(package.is_contract_enabled(&cowl_swap_contract_hash));
+    assert!(!package.is_contract_enabled(&upgraded_cowl_swap));
+
+    // Upgrade is event 0, the header sequence is shared by both
+    let actual_event: Rollback = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    let expected_event = Rollback::new(
+        EventHeader::new(
+            1,
+            actual_event.header.block_time,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            None,
+        ),
+        Key::from(upgraded_cowl_swap),
+        2,
+        Key::from(cowl_swap_contract_hash),
+        1,
+        STORAGE_VERSION,
+    );
+    assert_eq!(actual_event, expected_event);
+}
+
+#[test]
+fn should_not_rollback_to_current_or_unknown_version() {
+    let (mut builder, _) = setup();
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    cowl_swap_rollback(&mut builder, &DEFAULT_ACCOUNT_ADDR, 2).expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InvalidRollbackVersion as u16,
+        "should not rollback to the current version",
+    );
+
+    cowl_swap_rollback(&mut builder, &DEFAULT_ACCOUNT_ADDR, 0).expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::UnknownContractVersion as u16,
+        "should not rollback to a version missing from the registry",
+    );
+}
+
+#[test]
+fn should_not_rollback_across_storage_migrations() {
+    let (mut builder, _) = setup_with_wasm_and_args(SWAP_CONTRACT_V1_0_0_WASM, default_args());
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    cowl_swap_rollback(&mut builder, &DEFAULT_ACCOUNT_ADDR, 1).expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::IncompatibleStorageVersion as u16,
+        "should not rollback to a version running on older storage",
+    );
+}
+
+#[test]
+fn should_not_allow_rollback_call_from_other_admin() {
+    let (_, public_key_account_user_1) = create_dummy_key_pair(ACCOUNT_USER_1);
+    let account_user_1 = public_key_account_user_1.to_account_hash();
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ADMIN_LIST => vec![Key::from(account_user_1)],
+    });
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    // A direct call can no longer pass the version it claims to roll back from
+    let rollback_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        account_user_1,
+        cowl_swap_contract_package,
+        None,
+        ENTRY_POINT_ROLLBACK,
+        runtime_args! {
+            ARG_ROLLBACK_VERSION => 1_u32,
+            ARG_CONTRACT_VERSION => 3_u32,
+        },
+    )
+    .build();
+    builder.exec(rollback_request).expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InvalidUpgradeCaller as u16,
+        "only the installer session should reach rollback",
+    );
+}
+
+#[test]
+fn should_keep_settings_changed_before_rollback() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup();
+
+    let token_package = ContractPackageHash::new([7u8; 32]);
+    cowl_swap_upgrade_with_args(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            ARG_COWL_CEP18_CONTRACT_PACKAGE => Key::from(token_package),
+        },
+    )
+    .expect_success()
+    .commit();
+
+    let upgraded_cowl_swap: ContractHash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SWAP_CONTRACT_KEY_NAME)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+
+    cowl_swap_set_modalities(
+        &mut builder,
+        &upgraded_cowl_swap,
+        &DEFAULT_ACCOUNT_ADDR,
+        Some(EventsMode::NoEvents),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_rollback(&mut builder, &DEFAULT_ACCOUNT_ADDR, 1)
+        .expect_success()
+        .commit();
+
+    // The earlier version reads the settings written by the later one
+    let query = |name: &str| {
+        builder
+            .query(None, cowl_swap_contract_hash.into(), &[name.to_string()])
+            .unwrap()
+            .as_cl_value()
+            .unwrap()
+            .to_owned()
+    };
+    assert_eq!(
+        query(ARG_EVENTS_MODE).into_t::<u8>().unwrap(),
+        EventsMode::NoEvents as u8
+    );
+    assert_eq!(
+        query(ARG_COWL_CEP18_CONTRACT_PACKAGE)
+            .into_t::<ContractPackageHash>()
+            .unwrap(),
+        token_package
+    );
+}
+
+#[test]
+fn should_not_rollback_past_keys_put_since() {
+    let (mut builder, _) = setup();
+
+    cowl_swap_upgrade(&mut builder, &DEFAULT_ACCOUNT_ADDR)
+        .expect_success()
+        .commit();
+
+    let upgraded_cowl_swap: ContractHash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SWAP_CONTRACT_KEY_NAME)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+
+    // Native events put their dictionary and counter in the upgraded version only
+    cowl_swap_set_modalities(
+        &mut builder,
+        &upgraded_cowl_swap,
+        &DEFAULT_ACCOUNT_ADDR,
+        Some(EventsMode::NativeAndCES),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_rollback(&mut builder, &DEFAULT_ACCOUNT_ADDR, 1).expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::IncompatibleNamedKeys as u16,
+        "should not rollback to a version missing keys put since",
+    );
+}
@@ -0,0 +1,364 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{CLTyped, CLValue, Key, U256, U512};
+use cowl_swap::{
+    constants::RATE_TIERS,
+    curve::{CurveStep, CURVE_RATE_PRECISION},
+    enums::{PricingMode, SaleState},
+    error::SwapError,
+    events::{SaleStateUpdate, TokensClaimed, TokensVested},
+    sale::Sale,
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_claim, cowl_swap_cspr_to_cowl, cowl_swap_cspr_to_cowl_at, cowl_swap_deposit_cowl,
+        cowl_swap_refund, cowl_swap_refund_at, cowl_swap_set_bonding_curve,
+        cowl_swap_set_pricing_mode, cowl_swap_set_soft_cap, cowl_swap_update_times,
+        cowl_swap_withdraw_cspr, setup, TestContext,
+    },
+    pools::get_default_curve,
+};
+
+#[test]
+fn should_succeed_sale_once_soft_cap_is_met() {
+    let mut sale = Sale::default();
+
+    let error = sale.set_soft_cap(U512::zero()).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidSoftCap));
+
+    assert!(sale.set_soft_cap(U512::from(100u64)).unwrap());
+    assert_eq!(sale.state, SaleState::Raising);
+
+    assert!(!sale.contribute(U512::from(60u64)).unwrap());
+    assert_eq!(sale.held(), U512::from(60u64));
+    assert!(!sale.settle(10, 10));
+
+    assert!(sale.contribute(U512::from(40u64)).unwrap());
+    assert_eq!(sale.state, SaleState::Succeeded);
+    assert!(sale.held().is_zero());
+
+    let error = sale.set_soft_cap(U512::from(200u64)).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidSaleState));
+}
+
+#[test]
+fn should_fail_sale_ended_below_soft_cap() {
+    let mut sale = Sale::default();
+    sale.set_soft_cap(U512::from(100u64)).unwrap();
+    sale.contribute(U512::from(30u64)).unwrap();
+
+    assert!(sale.settle(11, 10));
+    assert_eq!(sale.state, SaleState::Failed);
+    assert_eq!(sale.held(), U512::from(30u64));
+
+    let error = sale.contribute(U512::from(70u64)).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidSaleState));
+
+    sale.refunded = U512::from(30u64);
+    assert!(sale.held().is_zero());
+}
+
+#[test]
+fn should_type_sale_as_nested_tuples() {
+    let sale = Sale {
+        state: SaleState::Failed,
+        soft_cap: U512::from(1),
+        raised: U512::from(2),
+        refunded: U512::from(3),
+    };
+
+    let value = CLValue::from_t(sale).expect("should encode sale");
+    assert_eq!(value.cl_type(), &<((u8, U512), (U512, U512))>::cl_type());
+    assert_eq!(
+        value
+            .into_t::<((u8, U512), (U512, U512))>()
+            .expect("should decode as tuples"),
+        (
+            (SaleState::Failed as u8, U512::from(1)),
+            (U512::from(2), U512::from(3))
+        )
+    );
+}
+
+#[test]
+fn should_hold_raised_cspr_until_soft_cap_is_met() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    let soft_cap = cspr_amount * 2;
+
+    cowl_swap_set_soft_cap(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        soft_cap,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set the soft cap for non admin account",
+    );
+
+    cowl_swap_set_soft_cap(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        soft_cap,
+    )
+    .expect_success()
+    .commit();
+
+    let event: SaleStateUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.state, SaleState::Raising as u8);
+    assert_eq!(event.soft_cap, soft_cap);
+    assert!(event.raised.is_zero());
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+
+    // Tokens are credited even without a vesting schedule while the sale raises
+    let event: TokensVested = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(event.amount, cowl_amount);
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::SoftCapNotReached as u16,
+        "should not allow to claim before the soft cap is met",
+    );
+
+    cowl_swap_refund(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::RefundsNotOpen as u16,
+        "should not allow a refund while the sale is raising",
+    );
+
+    cowl_swap_withdraw_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::SoftCapNotReached as u16,
+        "should not allow to withdraw CSPR raised below the soft cap",
+    );
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let event: SaleStateUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 6);
+    assert_eq!(event.state, SaleState::Succeeded as u8);
+    assert_eq!(event.raised, soft_cap);
+
+    cowl_swap_withdraw_cspr(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokensClaimed = get_event(&builder, &cowl_swap_contract_hash.into(), 8);
+    assert_eq!(event.beneficiary, Key::from(account_user_1));
+    assert_eq!(event.amount, cowl_amount * 2);
+}
+
+#[test]
+fn should_refund_back_down_the_curve_and_keep_the_sale_failed() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from(100_000_000_000u64),
+    )
+    .expect_success()
+    .commit();
+
+    let steps = vec![CurveStep {
+        sold_until: U512::from(100_000_000_000u64),
+        rate: CURVE_RATE_PRECISION * 5,
+    }];
+    cowl_swap_set_bonding_curve(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        &steps,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_set_pricing_mode(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        PricingMode::BondingCurve as u8,
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_set_soft_cap(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        cspr_amount * 10,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    assert!(!get_default_curve(&builder, cowl_swap_contract_hash)
+        .sold
+        .is_zero());
+
+    // The default pool closes after a day, leaving the sale below its soft cap
+    let after_end_time = 86_401_000u64;
+    cowl_swap_refund_at(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        after_end_time,
+    )
+    .expect_success()
+    .commit();
+
+    // The refunded tokens are back on the curve
+    assert!(get_default_curve(&builder, cowl_swap_contract_hash)
+        .sold
+        .is_zero());
+
+    cowl_swap_cspr_to_cowl_at(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+        after_end_time,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::SwapExpired as u16,
+        "should not allow a purchase once the sale failed",
+    );
+
+    cowl_swap_update_times(&mut builder, &cowl_swap_contract_hash, 0, 172_800).expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidSaleState as u16,
+        "should not allow to reopen a failed sale",
+    );
+}
@@ -105,6 +105,7 @@ fn should_test_change_security() {
     let security_lists = SecurityLists {
         admin_list: Some(vec![Key::Account(account_user_2)]),
         none_list: None,
+        oracle_list: None,
     };
 
     let change_security = cowl_swap_change_security(
@@ -137,6 +138,7 @@ fn should_test_change_security() {
     let security_lists = SecurityLists {
         admin_list: None,
         none_list: Some(vec![Key::Account(account_user_2)]),
+        oracle_list: None,
     };
 
     let change_security = cowl_swap_change_security(
@@ -0,0 +1,154 @@
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_types::{CLTyped, CLValue, ContractHash, Key, U256, U512};
+use cowl_swap::{
+    constants::{ARG_SWAP_COUNT, DICT_POOL_SWAP_STATS, DICT_SWAP_STATS, RATE_TIERS},
+    history::SwapStats,
+};
+use vesting_tests::constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl, setup, TestContext,
+    },
+    keys::item_key,
+};
+
+fn get_swap_stats(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+    dictionary: &str,
+    account: Key,
+) -> SwapStats {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(dictionary)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, &item_key(&account))
+        .expect("should have swap stats")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<SwapStats>()
+        .expect("should convert swap stats")
+}
+
+#[test]
+fn should_type_swap_stats_as_nested_tuples() {
+    let stats = SwapStats {
+        cspr_in: U512::from(1),
+        cspr_out: U512::from(2),
+        cowl_in: U256::from(3),
+        cowl_out: U256::from(4),
+        swap_count: 5,
+        last_swap_time: 6,
+    };
+
+    let value = CLValue::from_t(stats).expect("should encode swap stats");
+    assert_eq!(
+        value.cl_type(),
+        &<((U512, U512), (U256, U256), (u64, u64))>::cl_type()
+    );
+    assert_eq!(
+        value
+            .into_t::<((U512, U512), (U256, U256), (u64, u64))>()
+            .expect("should decode as tuples"),
+        (
+            (U512::from(1), U512::from(2)),
+            (U256::from(3), U256::from(4)),
+            (5, 6)
+        )
+    );
+}
+
+#[test]
+fn should_record_swap_stats_on_cspr_to_cowl() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            cowl_swap_contract_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+
+    let cowl_pool_amount = U256::from_dec_str("100000000000").unwrap();
+    let cspr_transfer_amount = U512::from_dec_str("10000000000").unwrap();
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_transfer_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let swap_contract = builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract");
+
+    let swap_count_key = swap_contract
+        .named_keys()
+        .get(ARG_SWAP_COUNT)
+        .expect("swap count uref should exist");
+
+    let swap_count = builder
+        .query(None, *swap_count_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<u64>()
+        .expect("should be u64.");
+
+    assert_eq!(swap_count, 1);
+
+    let stats = get_swap_stats(
+        &builder,
+        cowl_swap_contract_hash,
+        DICT_SWAP_STATS,
+        Key::from(account_user_1),
+    );
+
+    let expected_cowl = cspr_transfer_amount * RATE_TIERS.first().unwrap().rate;
+
+    assert_eq!(stats.cspr_in, cspr_transfer_amount);
+    assert_eq!(stats.cspr_out, U512::zero());
+    assert_eq!(stats.cowl_in, U256::zero());
+    assert_eq!(
+        stats.cowl_out,
+        U256::from_dec_str(&expected_cowl.to_string()).unwrap()
+    );
+    assert_eq!(stats.swap_count, 1);
+
+    // The default pool keeps the same totals under the account key
+    let pool_stats = get_swap_stats(
+        &builder,
+        cowl_swap_contract_hash,
+        DICT_POOL_SWAP_STATS,
+        Key::from(account_user_1),
+    );
+    assert_eq!(pool_stats, stats);
+}
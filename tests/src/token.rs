@@ -0,0 +1,162 @@
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_types::{account::AccountHash, CLType, ContractPackageHash, Key, U256, U512};
+use cowl_swap::constants::{
+    ARG_AMOUNT, ARG_BALANCE_COWL, ARG_TOKEN_DECIMALS, ARG_TOKEN_SYMBOL, DEFAULT_TOKEN_DECIMALS,
+    DEFAULT_TOKEN_SYMBOL, ENTRY_POINT_TOKEN_SYMBOL, ENTRY_POINT_TOKEN_TO_CSPR, RATE_TIERS,
+};
+use vesting_tests::constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1};
+
+use crate::utility::installer_request_builders::{
+    cowl_cep18_token_balance_cowl, cowl_swap_cspr_to_token, cowl_swap_deposit_cowl,
+    cowl_swap_token_to_cspr, default_args, setup, setup_with_args, TestContext,
+};
+
+fn get_user_token_balance(
+    builder: &mut InMemoryWasmTestBuilder,
+    account: AccountHash,
+    token_package: &ContractPackageHash,
+) -> U256 {
+    cowl_cep18_token_balance_cowl(builder, &account, token_package, &Key::from(account))
+        .expect_success()
+        .commit();
+
+    let balance_key = *builder
+        .get_account(account)
+        .unwrap()
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.")
+}
+
+#[test]
+fn should_store_default_token_metadata() {
+    let (
+        builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            ..
+        },
+    ) = setup();
+
+    let swap_contract = builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract");
+
+    let symbol = builder
+        .query(
+            None,
+            *swap_contract.named_keys().get(ARG_TOKEN_SYMBOL).unwrap(),
+            &[],
+        )
+        .unwrap()
+        .as_cl_value()
+        .unwrap()
+        .clone()
+        .into_t::<String>()
+        .unwrap();
+    let decimals = builder
+        .query(
+            None,
+            *swap_contract.named_keys().get(ARG_TOKEN_DECIMALS).unwrap(),
+            &[],
+        )
+        .unwrap()
+        .as_cl_value()
+        .unwrap()
+        .clone()
+        .into_t::<u8>()
+        .unwrap();
+
+    assert_eq!(symbol, DEFAULT_TOKEN_SYMBOL);
+    assert_eq!(decimals, DEFAULT_TOKEN_DECIMALS);
+
+    let token_symbol = swap_contract
+        .entry_points()
+        .get_entry_point(ENTRY_POINT_TOKEN_SYMBOL)
+        .expect("should expose the token symbol");
+    assert_eq!(token_symbol.ret(), &CLType::String);
+
+    let token_to_cspr = swap_contract
+        .entry_points()
+        .get_entry_point(ENTRY_POINT_TOKEN_TO_CSPR)
+        .expect("should have token_to_cspr");
+    let amount = token_to_cspr
+        .args()
+        .iter()
+        .find(|parameter| parameter.name() == ARG_AMOUNT)
+        .expect("should take an amount");
+    assert_eq!(amount.cl_type(), &CLType::U256);
+}
+
+#[test]
+fn should_swap_token_with_configured_decimals() {
+    let mut install_args = default_args();
+    let _ = install_args.insert(ARG_TOKEN_SYMBOL, "TKN".to_string());
+    let _ = install_args.insert(ARG_TOKEN_DECIMALS, 6_u8);
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup_with_args(install_args);
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        U256::from_dec_str("100000000000").unwrap(),
+    )
+    .expect_success()
+    .commit();
+
+    let cspr_transfer_amount = U512::from_dec_str("10000000000").unwrap();
+
+    cowl_swap_cspr_to_token(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_transfer_amount,
+    )
+    .expect_success()
+    .commit();
+
+    // 9 CSPR decimals down to 6 token decimals.
+    let expected_token_amount = cspr_transfer_amount * RATE_TIERS.first().unwrap().rate / 1000;
+    let token_balance =
+        get_user_token_balance(&mut builder, account_user_1, &cowl_cep18_token_package_hash);
+    assert_eq!(
+        token_balance,
+        U256::from_dec_str(&expected_token_amount.to_string()).unwrap()
+    );
+
+    cowl_swap_token_to_cspr(
+        &mut builder,
+        &account_user_1,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        token_balance,
+    )
+    .expect_success()
+    .commit();
+
+    let token_balance =
+        get_user_token_balance(&mut builder, account_user_1, &cowl_cep18_token_package_hash);
+    assert_eq!(token_balance, U256::zero());
+}
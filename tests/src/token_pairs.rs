@@ -0,0 +1,265 @@
+use casper_engine_test_support::DEFAULT_ACCOUNT_ADDR;
+use casper_types::{CLValue, Key, U256, U512};
+use cowl_swap::{
+    constants::{PAIR_RATE_PRECISION, TAX_RATE},
+    error::SwapError,
+    events::TokenPairUpdate,
+    pairs::TokenPair,
+};
+use vesting_tests::{
+    constants::ACCOUNT_USER_1,
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::{
+    installer_request_builders::{
+        cowl_swap_add_pool, cowl_swap_set_token_pair, cowl_swap_token_to_token, setup, TestContext,
+    },
+    pools::test_pool,
+};
+
+fn test_pair(input_package: Key, output_package: Key) -> TokenPair {
+    TokenPair {
+        input_package,
+        output_package,
+        input_decimals: 6,
+        output_decimals: 9,
+        rate: PAIR_RATE_PRECISION * 2,
+        min_input_amount: U512::from(10_000_000u64),
+        enabled: true,
+    }
+}
+
+#[test]
+fn should_quote_token_pair_at_flat_rate_with_tax() {
+    let pair = test_pair(Key::Hash([1u8; 32]), Key::Hash([2u8; 32]));
+
+    // 100 input tokens: 2 output tokens each, minus tax
+    let quote = pair.quote(U512::from(100_000_000u64)).unwrap();
+    let gross_amount = U512::from(200_000_000_000u64);
+    let tax_amount = gross_amount * TAX_RATE / 100;
+    assert_eq!(quote.tax_amount, tax_amount);
+    assert_eq!(quote.output_amount, gross_amount - tax_amount);
+
+    // Larger swaps keep the same rate, pairs have no tiers
+    let quote = pair.quote(U512::from(100_000_000_000u64)).unwrap();
+    let gross_amount = U512::from(200_000_000_000_000u64);
+    let tax_amount = gross_amount * TAX_RATE / 100;
+    assert_eq!(quote.output_amount, gross_amount - tax_amount);
+
+    // The minimum is the pair's own, in input token units
+    assert!(pair.quote(pair.min_input_amount).is_ok());
+    assert!(matches!(
+        pair.quote(pair.min_input_amount - 1),
+        Err(SwapError::BelowMinimumSwap)
+    ));
+}
+
+#[test]
+fn should_type_token_pair_as_tuple() {
+    let pair = test_pair(Key::Hash([1u8; 32]), Key::Hash([2u8; 32]));
+
+    let (
+        (input_package, output_package, input_decimals),
+        (output_decimals, rate, min_input_amount),
+        enabled,
+    ) = CLValue::from_t(pair)
+        .unwrap()
+        .into_t::<((Key, Key, u8), (u8, U512, U512), bool)>()
+        .unwrap();
+    assert_eq!(input_package, pair.input_package);
+    assert_eq!(output_package, pair.output_package);
+    assert_eq!(input_decimals, pair.input_decimals);
+    assert_eq!(output_decimals, pair.output_decimals);
+    assert_eq!(rate, pair.rate);
+    assert_eq!(min_input_amount, pair.min_input_amount);
+    assert_eq!(enabled, pair.enabled);
+}
+
+#[test]
+fn should_set_token_pair_by_admin_only() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pair = test_pair(
+        Key::from(cowl_cep18_token_package_hash),
+        Key::Hash([2u8; 32]),
+    );
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &test_pool(pair.output_package),
+    )
+    .expect_success()
+    .commit();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    cowl_swap_set_token_pair(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pair,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set a token pair for non admin account",
+    );
+
+    cowl_swap_set_token_pair(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pair,
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokenPairUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(event.input_package, pair.input_package);
+    assert_eq!(event.output_package, pair.output_package);
+    assert_eq!(event.rate, pair.rate);
+    assert_eq!(event.min_input_amount, pair.min_input_amount);
+    assert!(event.enabled);
+}
+
+#[test]
+fn should_not_set_token_pair_without_output_pool() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    cowl_swap_set_token_pair(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &test_pair(
+            Key::from(cowl_cep18_token_package_hash),
+            Key::Hash([2u8; 32]),
+        ),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::MissingPool as u16,
+        "should not allow a pair whose output token has no pool to account for the tax",
+    );
+}
+
+#[test]
+fn should_not_set_token_pair_of_same_token() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    let token_package = Key::from(cowl_cep18_token_package_hash);
+
+    cowl_swap_set_token_pair(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &test_pair(token_package, token_package),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidTokenPair as u16,
+        "should not allow a pair with the same input and output token",
+    );
+}
+
+#[test]
+fn should_not_swap_missing_or_disabled_pair() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+    let input_package = Key::from(cowl_cep18_token_package_hash);
+    let output_package = Key::Hash([2u8; 32]);
+    cowl_swap_add_pool(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &test_pool(output_package),
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_token_to_token(
+        &mut builder,
+        &account_user_1,
+        &input_package,
+        &output_package,
+        &cowl_swap_contract_package,
+        U256::from(1_000_000_000u64),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::MissingTokenPair as u16,
+        "should not swap a pair that is not configured",
+    );
+
+    let mut pair = test_pair(input_package, output_package);
+    pair.enabled = false;
+    cowl_swap_set_token_pair(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pair,
+    )
+    .expect_success()
+    .commit();
+
+    cowl_swap_token_to_token(
+        &mut builder,
+        &account_user_1,
+        &input_package,
+        &output_package,
+        &cowl_swap_contract_package,
+        U256::from(1_000_000_000u64),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::TokenPairDisabled as u16,
+        "should not swap a disabled pair",
+    );
+}
@@ -2,15 +2,54 @@ use crate::utility::{
     constants::{
         SWAP_CONTRACT_KEY_NAME, SWAP_CONTRACT_VERSION, SWAP_CONTRACT_WASM, SWAP_TEST_NAME,
     },
-    installer_request_builders::{setup, TestContext},
+    installer_request_builders::{
+        cowl_swap_set_modalities, cowl_swap_upgrade_with_args, setup, setup_with_args, TestContext,
+    },
+};
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+};
+use casper_types::{
+    account::AccountHash, runtime_args, ContractHash, ContractPackageHash, Key, RuntimeArgs,
 };
-use casper_engine_test_support::{ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
-use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs};
 use cowl_swap::{
-    constants::{ARG_CONTRACT_HASH, ARG_NAME, ARG_UPGRADE_FLAG},
-    events::Upgrade,
+    constants::{
+        ADMIN_LIST, ARG_CONTRACT_HASH, ARG_CONTRACT_VERSION, ARG_DURATION, ARG_END_TIME,
+        ARG_EVENTS_MODE, ARG_NAME, ARG_PREVIOUS_CONTRACT_HASH, ARG_PREVIOUS_CONTRACT_VERSION,
+        ARG_START_TIME, ARG_UPGRADE_FLAG, ENTRY_POINT_UPGRADE,
+    },
+    enums::EventsMode,
+    error::SwapError,
+    events::{ChangeSecurity, EventHeader, SetModalities, UpdateTimes, Upgrade},
+    security::SecurityBadge,
+};
+use vesting_tests::{
+    constants::ACCOUNT_USER_1,
+    support::{assert_expected_error, create_dummy_key_pair, get_event},
 };
-use vesting_tests::support::get_event;
+
+fn call_upgrade_entry_point<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender: &AccountHash,
+    cowl_swap_contract_package: ContractPackageHash,
+    contract_hash: ContractHash,
+    previous_contract_hash: ContractHash,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let upgrade_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *sender,
+        cowl_swap_contract_package,
+        None,
+        ENTRY_POINT_UPGRADE,
+        runtime_args! {
+            ARG_CONTRACT_HASH => Key::from(contract_hash),
+            ARG_CONTRACT_VERSION => 2_u32,
+            ARG_PREVIOUS_CONTRACT_HASH => Key::from(previous_contract_hash),
+            ARG_PREVIOUS_CONTRACT_VERSION => 1_u32,
+        },
+    )
+    .build();
+    builder.exec(upgrade_request)
+}
 
 #[test]
 fn should_upgrade_and_update_account_contract_contexts() {
@@ -133,8 +172,228 @@ fn should_emit_event_on_upgrade_with_events_mode_ces() {
         .expect_success()
         .commit();
 
+    let upgraded_cowl_swap: ContractHash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SWAP_CONTRACT_KEY_NAME)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+
     // Expect Upgrade event
-    let expected_event = Upgrade::new();
     let actual_event: Upgrade = get_event(&builder, &cowl_swap_contract_hash.into(), 0);
+    let expected_event = Upgrade::new(
+        EventHeader::new(
+            0,
+            actual_event.header.block_time,
+            Key::from(*DEFAULT_ACCOUNT_ADDR),
+            None,
+        ),
+        Key::from(cowl_swap_contract_hash),
+        1,
+        Key::from(upgraded_cowl_swap),
+        2,
+    );
     assert_eq!(actual_event, expected_event, "Expected Upgrade event.");
 }
+
+#[test]
+fn should_not_allow_direct_upgrade_call_with_arbitrary_contract_hash() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_contract_hash,
+            ..
+        },
+    ) = setup();
+
+    // Points the swap to a hash that is not a version of its package
+    call_upgrade_entry_point(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        cowl_swap_contract_package,
+        cowl_cep18_token_contract_hash,
+        cowl_swap_contract_hash,
+    )
+    .expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InvalidContractHash as u16,
+        "should not upgrade to a hash that is not the executing version",
+    );
+
+    // Replays the upgrade on the version already active
+    call_upgrade_entry_point(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        cowl_swap_contract_package,
+        cowl_swap_contract_hash,
+        cowl_swap_contract_hash,
+    )
+    .expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InvalidContractHash as u16,
+        "should not upgrade a version onto itself",
+    );
+
+    let contract_hash: ContractHash = builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have contract")
+        .named_keys()
+        .get(ARG_CONTRACT_HASH)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+    assert_eq!(contract_hash, cowl_swap_contract_hash);
+}
+
+#[test]
+fn should_not_allow_upgrade_call_from_other_admin() {
+    let (_, public_key_account_user_1) = create_dummy_key_pair(ACCOUNT_USER_1);
+    let account_user_1 = public_key_account_user_1.to_account_hash();
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            ..
+        },
+    ) = setup_with_args(runtime_args! {
+        ADMIN_LIST => vec![Key::from(account_user_1)],
+    });
+
+    call_upgrade_entry_point(
+        &mut builder,
+        &account_user_1,
+        cowl_swap_contract_package,
+        cowl_swap_contract_hash,
+        cowl_swap_contract_hash,
+    )
+    .expect_failure();
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InvalidUpgradeCaller as u16,
+        "should only accept upgrades from the installer session",
+    );
+}
+
+#[test]
+fn should_apply_configuration_passed_with_upgrade() {
+    let (_, public_key_account_user_1) = create_dummy_key_pair(ACCOUNT_USER_1);
+    let account_user_1 = public_key_account_user_1.to_account_hash();
+
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_cep18_token_package_hash,
+            ..
+        },
+    ) = setup();
+
+    cowl_swap_upgrade_with_args(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            ARG_START_TIME => 100_u64,
+            ARG_DURATION => 1000_u64,
+            ADMIN_LIST => vec![Key::from(account_user_1)],
+            ARG_EVENTS_MODE => EventsMode::NativeAndCES as u8,
+        },
+    )
+    .expect_success()
+    .commit();
+
+    let upgraded_cowl_swap: ContractHash = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SWAP_CONTRACT_KEY_NAME)
+        .unwrap()
+        .into_hash()
+        .unwrap()
+        .into();
+
+    let get_u64 = |name: &str| {
+        builder
+            .query(None, upgraded_cowl_swap.into(), &[name.to_string()])
+            .unwrap()
+            .as_cl_value()
+            .unwrap()
+            .to_owned()
+            .into_t::<u64>()
+            .unwrap()
+    };
+    assert_eq!(get_u64(ARG_START_TIME), 100);
+    assert_eq!(get_u64(ARG_END_TIME), 1100);
+
+    // Settings are applied after the Upgrade event, events mode last
+    let caller = Key::from(*DEFAULT_ACCOUNT_ADDR);
+    let actual_event: UpdateTimes = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(
+        actual_event,
+        UpdateTimes::new(
+            EventHeader::new(1, actual_event.header.block_time, caller, None),
+            Key::from(cowl_cep18_token_package_hash),
+            100,
+            1100,
+        )
+    );
+
+    let actual_event: ChangeSecurity = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    assert_eq!(
+        actual_event,
+        ChangeSecurity::new(
+            EventHeader::new(2, actual_event.header.block_time, caller, None),
+            caller,
+            [(Key::from(account_user_1), SecurityBadge::Admin)].into(),
+        )
+    );
+
+    let actual_event: SetModalities = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(
+        actual_event,
+        SetModalities::new(
+            EventHeader::new(3, actual_event.header.block_time, caller, None),
+            EventsMode::CES as u8,
+            EventsMode::NativeAndCES as u8,
+        )
+    );
+
+    // The admin added by the upgrade can use admin entry points
+    cowl_swap_set_modalities(
+        &mut builder,
+        &upgraded_cowl_swap,
+        &account_user_1,
+        Some(EventsMode::CES),
+    )
+    .expect_success()
+    .commit();
+}
+
+#[test]
+fn should_reject_upgrade_with_incomplete_time_window() {
+    let (mut builder, _) = setup();
+
+    cowl_swap_upgrade_with_args(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            ARG_START_TIME => 100_u64,
+        },
+    )
+    .expect_failure();
+
+    assert_expected_error(
+        builder.get_error().expect("must have error"),
+        SwapError::InvalidTimeWindow as u16,
+        "should require both start time and duration",
+    );
+}
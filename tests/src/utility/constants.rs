@@ -1,12 +1,19 @@
 pub const SWAP_CONTRACT_WASM: &str = "cowl_swap.wasm";
+pub const SWAP_CONTRACT_V1_0_0_WASM: &str = "cowl_swap_v1_0_0.wasm";
 pub const SWAP_DEPOSIT_CSPR_SESSION_WASM: &str = "deposit_cspr_session.wasm";
 pub const SWAP_DEPOSIT_COWL_SESSION_WASM: &str = "deposit_cowl_session.wasm";
 pub const SWAP_CSPR_TO_COWL_SESSION_WASM: &str = "cspr_to_cowl_session.wasm";
 pub const SWAP_COWL_TO_CSPR_SESSION_WASM: &str = "cowl_to_cspr_session.wasm";
+pub const SWAP_CSPR_TO_TOKEN_SESSION_WASM: &str = "cspr_to_token_session.wasm";
+pub const SWAP_TOKEN_TO_CSPR_SESSION_WASM: &str = "token_to_cspr_session.wasm";
+pub const SWAP_TOKEN_TO_TOKEN_SESSION_WASM: &str = "token_to_token_session.wasm";
+pub const SWAP_ADD_LIQUIDITY_SESSION_WASM: &str = "add_liquidity_session.wasm";
 pub const SWAP_BALANCE_COWL_SESSION_WASM: &str = "balance_cowl_session.wasm";
 pub const SWAP_TEST_NAME: &str = "test";
 pub const SWAP_CONTRACT_KEY_NAME: &str = "swap_contract_hash_test";
 pub const SWAP_CONTRACT_PACKAGE_HASH_KEY_NAME: &str = "swap_contract_package_test";
 pub const SWAP_CONTRACT_VERSION: &str = "swap_contract_version_test";
+pub const SWAP_FACTORY_TEST_NAME: &str = "factory";
+pub const SWAP_FACTORY_CONTRACT_KEY_NAME: &str = "swap_factory_contract_hash_factory";
 
 pub const MINIMUM_TRANSFER_AMOUNT: &str = "2500000000"; // 2.5 CSPR
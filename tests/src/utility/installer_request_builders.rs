@@ -1,4 +1,6 @@
-use crate::utility::constants::{SWAP_CONTRACT_WASM, SWAP_TEST_NAME};
+use crate::utility::constants::{
+    SWAP_CONTRACT_WASM, SWAP_FACTORY_CONTRACT_KEY_NAME, SWAP_FACTORY_TEST_NAME, SWAP_TEST_NAME,
+};
 use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
 };
@@ -8,13 +10,31 @@ use casper_types::{
 };
 use cowl_swap::{
     constants::{
-        ADMIN_LIST, ARG_ADDRESS, ARG_AMOUNT, ARG_COWL_CEP18_CONTRACT_PACKAGE,
-        ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_DURATION, ARG_EVENTS_MODE, ARG_NAME, ARG_START_TIME,
+        ADMIN_LIST, ARG_ADDRESS, ARG_AMOUNT, ARG_CLAIM_START_TIME, ARG_CLIFF_DURATION,
+        ARG_COWL_CEP18_CONTRACT_PACKAGE, ARG_COWL_SWAP_CONTRACT_PACKAGE, ARG_CURVE_STEPS,
+        ARG_DURATION, ARG_ENABLED, ARG_EVENTS_MODE, ARG_FACTORY, ARG_FACTORY_PACKAGE,
+        ARG_INPUT_DECIMALS, ARG_INPUT_TOKEN_PACKAGE, ARG_MAX_AGE, ARG_MAX_DEVIATION,
+        ARG_MIN_INPUT_AMOUNT, ARG_NAME, ARG_OUTPUT_DECIMALS, ARG_OUTPUT_TOKEN_PACKAGE,
+        ARG_PAIR_RATE, ARG_PHASE, ARG_PHASE_END_TIME, ARG_POOL, ARG_PRICE, ARG_PRICING_MODE,
+        ARG_RATE_TIERS, ARG_RECIPIENT, ARG_REFERRAL_BONUS, ARG_REFERRER, ARG_ROLLBACK_VERSION,
+        ARG_SHARES, ARG_SOFT_CAP, ARG_SPREAD, ARG_START_TIME, ARG_SWEEP, ARG_TAX_RATE,
+        ARG_TIMESTAMP, ARG_TOKEN_AMOUNT, ARG_TOKEN_CONTRACT_PACKAGE, ARG_TOKEN_DECIMALS,
+        ARG_UPGRADE_FLAG, ARG_VESTING_DURATION, ARG_VESTING_START_TIME, ENTRY_POINT_ADD_POOL,
         ENTRY_POINT_BALANCE_COWL, ENTRY_POINT_BALANCE_CSPR, ENTRY_POINT_CHANGE_SECURITY,
-        ENTRY_POINT_SET_MODALITIES, ENTRY_POINT_UPDATE_TIMES, ENTRY_POINT_WITHDRAW_COWL,
-        ENTRY_POINT_WITHDRAW_CSPR, NONE_LIST,
+        ENTRY_POINT_CLAIM, ENTRY_POINT_CREATE_POOL, ENTRY_POINT_RECONCILE, ENTRY_POINT_REFUND,
+        ENTRY_POINT_REMOVE_LIQUIDITY, ENTRY_POINT_SET_BONDING_CURVE,
+        ENTRY_POINT_SET_CLAIM_START_TIME, ENTRY_POINT_SET_MODALITIES,
+        ENTRY_POINT_SET_ORACLE_CONFIG, ENTRY_POINT_SET_PRICING_MODE,
+        ENTRY_POINT_SET_REFERRAL_BONUS, ENTRY_POINT_SET_RESERVE_LIMITS, ENTRY_POINT_SET_SOFT_CAP,
+        ENTRY_POINT_SET_TOKEN_PAIR, ENTRY_POINT_SET_VESTING_SCHEDULE, ENTRY_POINT_TRANSFER,
+        ENTRY_POINT_UPDATE_POOL, ENTRY_POINT_UPDATE_PRICE, ENTRY_POINT_UPDATE_TIMES,
+        ENTRY_POINT_WITHDRAW_COWL, ENTRY_POINT_WITHDRAW_CSPR, NONE_LIST, ORACLE_LIST,
     },
+    curve::CurveStep,
     enums::EventsMode,
+    pairs::TokenPair,
+    pools::Pool,
+    vesting::{VestingPhase, VestingSchedule},
 };
 use std::collections::HashMap;
 #[cfg(test)]
@@ -22,9 +42,11 @@ use vesting_tests::setup as setup_vesting;
 use vesting_tests::TestContextVesting;
 
 use super::constants::{
-    SWAP_BALANCE_COWL_SESSION_WASM, SWAP_CONTRACT_KEY_NAME, SWAP_CONTRACT_PACKAGE_HASH_KEY_NAME,
-    SWAP_COWL_TO_CSPR_SESSION_WASM, SWAP_CSPR_TO_COWL_SESSION_WASM, SWAP_DEPOSIT_COWL_SESSION_WASM,
-    SWAP_DEPOSIT_CSPR_SESSION_WASM,
+    SWAP_ADD_LIQUIDITY_SESSION_WASM, SWAP_BALANCE_COWL_SESSION_WASM, SWAP_CONTRACT_KEY_NAME,
+    SWAP_CONTRACT_PACKAGE_HASH_KEY_NAME, SWAP_COWL_TO_CSPR_SESSION_WASM,
+    SWAP_CSPR_TO_COWL_SESSION_WASM, SWAP_CSPR_TO_TOKEN_SESSION_WASM,
+    SWAP_DEPOSIT_COWL_SESSION_WASM, SWAP_DEPOSIT_CSPR_SESSION_WASM,
+    SWAP_TOKEN_TO_CSPR_SESSION_WASM, SWAP_TOKEN_TO_TOKEN_SESSION_WASM,
 };
 
 #[derive(Clone)]
@@ -54,7 +76,15 @@ pub fn setup() -> (InMemoryWasmTestBuilder, TestContext) {
     setup_with_args(default_args())
 }
 
-pub fn setup_with_args(mut install_args: RuntimeArgs) -> (InMemoryWasmTestBuilder, TestContext) {
+pub fn setup_with_args(install_args: RuntimeArgs) -> (InMemoryWasmTestBuilder, TestContext) {
+    setup_with_wasm_and_args(SWAP_CONTRACT_WASM, install_args)
+}
+
+/// Installs the given swap contract wasm, e.g. a previous release to test upgrades from.
+pub fn setup_with_wasm_and_args(
+    swap_contract_wasm: &str,
+    mut install_args: RuntimeArgs,
+) -> (InMemoryWasmTestBuilder, TestContext) {
     let (
         mut builder,
         TestContextVesting {
@@ -75,7 +105,7 @@ pub fn setup_with_args(mut install_args: RuntimeArgs) -> (InMemoryWasmTestBuilde
     // Install SWAP contract with token
     let install_request_contract = ExecuteRequestBuilder::standard(
         *DEFAULT_ACCOUNT_ADDR,
-        SWAP_CONTRACT_WASM,
+        swap_contract_wasm,
         merge_args(install_args),
     )
     .build();
@@ -138,6 +168,7 @@ pub fn cowl_swap_set_modalities<'a>(
 pub struct SecurityLists {
     pub admin_list: Option<Vec<Key>>,
     pub none_list: Option<Vec<Key>>,
+    pub oracle_list: Option<Vec<Key>>,
 }
 
 pub fn cowl_swap_change_security<'a>(
@@ -149,6 +180,7 @@ pub fn cowl_swap_change_security<'a>(
     let SecurityLists {
         admin_list,
         none_list,
+        oracle_list,
     } = security_lists;
 
     let change_security_request = ExecuteRequestBuilder::contract_call_by_hash(
@@ -158,6 +190,7 @@ pub fn cowl_swap_change_security<'a>(
         runtime_args! {
             ADMIN_LIST => admin_list.unwrap_or_default(),
             NONE_LIST => none_list.unwrap_or_default(),
+            ORACLE_LIST => oracle_list.unwrap_or_default(),
         },
     )
     .build();
@@ -318,6 +351,22 @@ pub fn cowl_swap_cspr_to_cowl<'a>(
     sender_account: &'a AccountHash,
     cowl_swap_contract_package: &'a ContractPackageHash,
     amount: U512,
+) -> &'a mut InMemoryWasmTestBuilder {
+    cowl_swap_cspr_to_cowl_at(
+        builder,
+        sender_account,
+        cowl_swap_contract_package,
+        amount,
+        0,
+    )
+}
+
+pub fn cowl_swap_cspr_to_cowl_at<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    amount: U512,
+    block_time: u64,
 ) -> &'a mut InMemoryWasmTestBuilder {
     let cspr_to_cowl_request = ExecuteRequestBuilder::standard(
         *sender_account,
@@ -328,11 +377,51 @@ pub fn cowl_swap_cspr_to_cowl<'a>(
 
         },
     )
+    .with_block_time(block_time)
+    .build();
+
+    builder.exec(cspr_to_cowl_request)
+}
+
+pub fn cowl_swap_cspr_to_cowl_with_referrer<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    amount: U512,
+    referrer: &'a Key,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let cspr_to_cowl_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_CSPR_TO_COWL_SESSION_WASM,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_COWL_SWAP_CONTRACT_PACKAGE => Key::from(*cowl_swap_contract_package),
+            ARG_REFERRER => *referrer,
+        },
+    )
     .build();
 
     builder.exec(cspr_to_cowl_request)
 }
 
+pub fn cowl_swap_set_referral_bonus<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    referral_bonus: u32,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let set_referral_bonus_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_REFERRAL_BONUS,
+        runtime_args! {
+            ARG_REFERRAL_BONUS => referral_bonus,
+        },
+    )
+    .build();
+    builder.exec(set_referral_bonus_request)
+}
+
 pub fn cowl_swap_cowl_to_cspr<'a>(
     builder: &'a mut InMemoryWasmTestBuilder,
     sender_account: &'a AccountHash,
@@ -354,6 +443,636 @@ pub fn cowl_swap_cowl_to_cspr<'a>(
     builder.exec(cowl_to_cspr_request)
 }
 
+pub fn cowl_swap_cspr_to_token<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    amount: U512,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let cspr_to_token_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_CSPR_TO_TOKEN_SESSION_WASM,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_COWL_SWAP_CONTRACT_PACKAGE => Key::from(*cowl_swap_contract_package)
+        },
+    )
+    .build();
+
+    builder.exec(cspr_to_token_request)
+}
+
+pub fn cowl_swap_token_to_cspr<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    token_contract_package: &'a ContractPackageHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    amount: U256,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let token_to_cspr_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_TOKEN_TO_CSPR_SESSION_WASM,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_TOKEN_CONTRACT_PACKAGE => Key::from(*token_contract_package),
+            ARG_COWL_SWAP_CONTRACT_PACKAGE => Key::from(*cowl_swap_contract_package)
+        },
+    )
+    .build();
+    builder.exec(token_to_cspr_request)
+}
+
+pub fn cowl_swap_set_token_pair<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pair: &TokenPair,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let set_token_pair_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_TOKEN_PAIR,
+        runtime_args! {
+            ARG_INPUT_TOKEN_PACKAGE => pair.input_package,
+            ARG_OUTPUT_TOKEN_PACKAGE => pair.output_package,
+            ARG_INPUT_DECIMALS => pair.input_decimals,
+            ARG_OUTPUT_DECIMALS => pair.output_decimals,
+            ARG_PAIR_RATE => pair.rate,
+            ARG_MIN_INPUT_AMOUNT => pair.min_input_amount,
+            ARG_ENABLED => pair.enabled,
+        },
+    )
+    .build();
+    builder.exec(set_token_pair_request)
+}
+
+pub fn cowl_swap_token_to_token<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    input_token_package: &'a Key,
+    output_token_package: &'a Key,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    amount: U256,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let token_to_token_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_TOKEN_TO_TOKEN_SESSION_WASM,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_INPUT_TOKEN_PACKAGE => *input_token_package,
+            ARG_OUTPUT_TOKEN_PACKAGE => *output_token_package,
+            ARG_COWL_SWAP_CONTRACT_PACKAGE => Key::from(*cowl_swap_contract_package)
+        },
+    )
+    .build();
+    builder.exec(token_to_token_request)
+}
+
+pub fn cowl_swap_add_pool<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &Pool,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let rate_tiers: Vec<(U512, U512)> = pool
+        .rate_tiers
+        .iter()
+        .map(|tier| (tier.cspr_amount, tier.rate))
+        .collect();
+    let add_pool_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_ADD_POOL,
+        runtime_args! {
+            ARG_POOL => pool.token_package,
+            ARG_TOKEN_DECIMALS => pool.token_decimals,
+            ARG_RATE_TIERS => rate_tiers,
+            ARG_TAX_RATE => pool.tax_rate,
+            ARG_START_TIME => pool.start_time,
+            ARG_DURATION => pool.end_time - pool.start_time,
+        },
+    )
+    .build();
+    builder.exec(add_pool_request)
+}
+
+/// Calls `update_pool` with the given arguments, `pool` selecting the pool to update.
+pub fn cowl_swap_update_pool<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    args: RuntimeArgs,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let update_pool_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_UPDATE_POOL,
+        args,
+    )
+    .build();
+    builder.exec(update_pool_request)
+}
+
+pub fn cowl_swap_set_pricing_mode<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    pricing_mode: u8,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let set_pricing_mode_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_PRICING_MODE,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_PRICING_MODE => pricing_mode,
+        },
+    )
+    .build();
+    builder.exec(set_pricing_mode_request)
+}
+
+pub fn cowl_swap_set_bonding_curve<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    steps: &[CurveStep],
+) -> &'a mut InMemoryWasmTestBuilder {
+    let curve_steps: Vec<(U512, U512)> = steps
+        .iter()
+        .map(|step| (step.sold_until, step.rate))
+        .collect();
+    let set_bonding_curve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_BONDING_CURVE,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_CURVE_STEPS => curve_steps,
+        },
+    )
+    .build();
+    builder.exec(set_bonding_curve_request)
+}
+
+pub fn cowl_swap_set_vesting_schedule<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    schedule: Option<VestingSchedule>,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let args = match schedule {
+        Some(schedule) => runtime_args! {
+            ARG_POOL => *pool,
+            ARG_VESTING_START_TIME => schedule.start_time,
+            ARG_CLIFF_DURATION => schedule.cliff_duration,
+            ARG_VESTING_DURATION => schedule.vesting_duration,
+        },
+        None => runtime_args! {
+            ARG_POOL => *pool,
+            ARG_ENABLED => false,
+        },
+    };
+    let set_vesting_schedule_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_VESTING_SCHEDULE,
+        args,
+    )
+    .build();
+    builder.exec(set_vesting_schedule_request)
+}
+
+pub fn cowl_swap_claim<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    amount: Option<U256>,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let mut args = runtime_args! {
+        ARG_POOL => *pool,
+    };
+    if let Some(amount) = amount {
+        let _ = args.insert(ARG_AMOUNT, amount);
+    }
+    let claim_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_CLAIM,
+        args,
+    )
+    .build();
+    builder.exec(claim_request)
+}
+
+pub fn cowl_swap_set_vesting_phase<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    phase: VestingPhase,
+    enabled: bool,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let args = if enabled {
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_PHASE => phase.start_time,
+            ARG_PHASE_END_TIME => phase.end_time,
+            ARG_VESTING_START_TIME => phase.schedule.start_time,
+            ARG_CLIFF_DURATION => phase.schedule.cliff_duration,
+            ARG_VESTING_DURATION => phase.schedule.vesting_duration,
+        }
+    } else {
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_PHASE => phase.start_time,
+            ARG_ENABLED => false,
+        }
+    };
+    let set_vesting_phase_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_VESTING_SCHEDULE,
+        args,
+    )
+    .build();
+    builder.exec(set_vesting_phase_request)
+}
+
+pub fn cowl_swap_claim_phase<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    phase: u64,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let claim_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_CLAIM,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_PHASE => phase,
+        },
+    )
+    .build();
+    builder.exec(claim_request)
+}
+
+pub fn cowl_swap_set_claim_start_time<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    claim_start_time: Option<u64>,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let mut args = runtime_args! {
+        ARG_POOL => *pool,
+    };
+    if let Some(claim_start_time) = claim_start_time {
+        let _ = args.insert(ARG_CLAIM_START_TIME, claim_start_time);
+    }
+    let set_claim_start_time_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_CLAIM_START_TIME,
+        args,
+    )
+    .build();
+    builder.exec(set_claim_start_time_request)
+}
+
+pub fn cowl_swap_set_soft_cap<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    soft_cap: U512,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let set_soft_cap_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_SOFT_CAP,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_SOFT_CAP => soft_cap,
+        },
+    )
+    .build();
+    builder.exec(set_soft_cap_request)
+}
+
+pub fn cowl_swap_refund<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+) -> &'a mut InMemoryWasmTestBuilder {
+    cowl_swap_refund_at(builder, cowl_swap, sender_account, pool, 0)
+}
+
+pub fn cowl_swap_refund_at<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    block_time: u64,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let refund_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_REFUND,
+        runtime_args! {
+            ARG_POOL => *pool,
+        },
+    )
+    .with_block_time(block_time)
+    .build();
+    builder.exec(refund_request)
+}
+
+pub fn cowl_swap_set_oracle_config<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    max_age: u64,
+    max_deviation: u32,
+    spread: u32,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let set_oracle_config_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_SET_ORACLE_CONFIG,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_MAX_AGE => max_age,
+            ARG_MAX_DEVIATION => max_deviation,
+            ARG_SPREAD => spread,
+        },
+    )
+    .build();
+    builder.exec(set_oracle_config_request)
+}
+
+pub fn cowl_swap_update_price<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    price: U512,
+    timestamp: u64,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let update_price_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_UPDATE_PRICE,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_PRICE => price,
+            ARG_TIMESTAMP => timestamp,
+        },
+    )
+    .build();
+    builder.exec(update_price_request)
+}
+
+/// Swaps CSPR in the pool of `pool`, which may not be the configured token.
+pub fn cowl_swap_cspr_to_pool<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    pool: &'a Key,
+    amount: U512,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let cspr_to_token_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_CSPR_TO_TOKEN_SESSION_WASM,
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_POOL => *pool,
+            ARG_COWL_SWAP_CONTRACT_PACKAGE => Key::from(*cowl_swap_contract_package)
+        },
+    )
+    .build();
+    builder.exec(cspr_to_token_request)
+}
+
+pub fn cowl_swap_add_liquidity<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    token_contract_package: &'a ContractPackageHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    cspr_amount: U512,
+    token_amount: U256,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let add_liquidity_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_ADD_LIQUIDITY_SESSION_WASM,
+        runtime_args! {
+            ARG_AMOUNT => cspr_amount,
+            ARG_TOKEN_AMOUNT => token_amount,
+            ARG_TOKEN_CONTRACT_PACKAGE => Key::from(*token_contract_package),
+            ARG_COWL_SWAP_CONTRACT_PACKAGE => Key::from(*cowl_swap_contract_package)
+        },
+    )
+    .build();
+    builder.exec(add_liquidity_request)
+}
+
+pub fn cowl_swap_remove_liquidity<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    cowl_swap: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    pool: &'a Key,
+    shares: U512,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let remove_liquidity_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *cowl_swap,
+        ENTRY_POINT_REMOVE_LIQUIDITY,
+        runtime_args! {
+            ARG_POOL => *pool,
+            ARG_SHARES => shares,
+        },
+    )
+    .build();
+    builder.exec(remove_liquidity_request)
+}
+
+/// Upgrades the swap installed under the test name with the current contract wasm.
+pub fn cowl_swap_upgrade<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+) -> &'a mut InMemoryWasmTestBuilder {
+    cowl_swap_upgrade_with_args(builder, sender_account, RuntimeArgs::new())
+}
+
+/// Upgrades the swap with additional install-time arguments applied by the new version.
+pub fn cowl_swap_upgrade_with_args<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    mut upgrade_args: RuntimeArgs,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let _ = upgrade_args.insert(ARG_UPGRADE_FLAG, true);
+    let _ = upgrade_args.insert(ARG_NAME, SWAP_TEST_NAME);
+
+    let upgrade_request =
+        ExecuteRequestBuilder::standard(*sender_account, SWAP_CONTRACT_WASM, upgrade_args).build();
+    builder.exec(upgrade_request)
+}
+
+/// Installs a factory from the swap wasm, named [`SWAP_FACTORY_TEST_NAME`].
+pub fn cowl_swap_factory_install(builder: &mut InMemoryWasmTestBuilder) -> ContractHash {
+    let install_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        SWAP_CONTRACT_WASM,
+        runtime_args! {
+            ARG_NAME => SWAP_FACTORY_TEST_NAME,
+            ARG_FACTORY => true,
+            ARG_EVENTS_MODE => EventsMode::CES as u8,
+        },
+    )
+    .build();
+    builder.exec(install_request).expect_success().commit();
+
+    builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(SWAP_FACTORY_CONTRACT_KEY_NAME)
+        .and_then(|key| key.into_hash())
+        .map(ContractHash::new)
+        .expect("should have factory contract hash")
+}
+
+/// Creates a pool through the factory from an installer session, which keeps the pool access key.
+pub fn cowl_swap_factory_create_pool_session<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    factory: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    name: &str,
+    token_package: &'a ContractPackageHash,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let factory_package = builder
+        .get_contract(*factory)
+        .expect("should have factory contract")
+        .contract_package_hash();
+    let create_pool_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_CONTRACT_WASM,
+        runtime_args! {
+            ARG_NAME => name,
+            ARG_FACTORY_PACKAGE => Key::from(factory_package),
+            ARG_COWL_CEP18_CONTRACT_PACKAGE => Key::from(*token_package),
+            ARG_START_TIME => 0_u64,
+            ARG_DURATION => 86400_u64,
+        },
+    )
+    .build();
+    builder.exec(create_pool_request)
+}
+
+pub fn cowl_swap_factory_create_pool<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    factory: &'a ContractHash,
+    sender_account: &'a AccountHash,
+    name: &str,
+    token_package: &'a ContractPackageHash,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let create_pool_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *sender_account,
+        *factory,
+        ENTRY_POINT_CREATE_POOL,
+        runtime_args! {
+            ARG_NAME => name,
+            ARG_COWL_CEP18_CONTRACT_PACKAGE => Key::from(*token_package),
+            ARG_START_TIME => 0_u64,
+            ARG_DURATION => 86400_u64,
+        },
+    )
+    .build();
+    builder.exec(create_pool_request)
+}
+
+pub fn cowl_swap_rollback<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    rollback_version: u32,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let rollback_request = ExecuteRequestBuilder::standard(
+        *sender_account,
+        SWAP_CONTRACT_WASM,
+        runtime_args! {
+            ARG_NAME => SWAP_TEST_NAME,
+            ARG_ROLLBACK_VERSION => rollback_version,
+        },
+    )
+    .build();
+    builder.exec(rollback_request)
+}
+
+pub fn cowl_swap_set_reserve_limits<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    reserve_limits: RuntimeArgs,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let set_reserve_limits_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *sender_account,
+        *cowl_swap_contract_package,
+        None,
+        ENTRY_POINT_SET_RESERVE_LIMITS,
+        reserve_limits,
+    )
+    .build();
+    builder.exec(set_reserve_limits_request)
+}
+
+pub fn cowl_swap_reconcile<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_swap_contract_package: &'a ContractPackageHash,
+    sweep: bool,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let reconcile_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *sender_account,
+        *cowl_swap_contract_package,
+        None,
+        ENTRY_POINT_RECONCILE,
+        runtime_args! {
+            ARG_SWEEP => sweep,
+        },
+    )
+    .build();
+    builder.exec(reconcile_request)
+}
+
+pub fn cowl_cep18_token_transfer<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender_account: &'a AccountHash,
+    cowl_cep18_token_package_hash: &'a ContractPackageHash,
+    recipient: Key,
+    amount: U256,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let transfer_request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        *sender_account,
+        *cowl_cep18_token_package_hash,
+        None,
+        ENTRY_POINT_TRANSFER,
+        runtime_args! {
+            ARG_RECIPIENT => recipient,
+            ARG_AMOUNT => amount,
+        },
+    )
+    .build();
+    builder.exec(transfer_request)
+}
+
 fn merge_args(install_args: RuntimeArgs) -> RuntimeArgs {
     let mut merged_args = install_args;
 
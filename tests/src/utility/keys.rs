@@ -0,0 +1,10 @@
+use casper_types::{bytesrepr::ToBytes, Key};
+
+/// Dictionary item key of `key`, its serialized bytes in hex as the contract keys them.
+pub fn item_key(key: &Key) -> String {
+    key.to_bytes()
+        .expect("should serialize key")
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
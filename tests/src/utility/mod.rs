@@ -1,2 +1,4 @@
 pub mod constants;
 pub mod installer_request_builders;
+pub mod keys;
+pub mod pools;
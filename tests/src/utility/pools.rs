@@ -0,0 +1,45 @@
+use casper_engine_test_support::InMemoryWasmTestBuilder;
+use casper_types::{ContractHash, Key};
+use cowl_swap::{
+    constants::{DEFAULT_TOKEN_DECIMALS, DICT_BONDING_CURVES, RATE_TIERS, TAX_RATE},
+    curve::BondingCurve,
+    pools::{Pool, DEFAULT_POOL_ITEM_KEY},
+};
+
+/// Pool of `token_package` with the built-in tiers, tax and decimals, open for a day.
+pub fn test_pool(token_package: Key) -> Pool {
+    Pool {
+        token_package,
+        token_decimals: DEFAULT_TOKEN_DECIMALS,
+        rate_tiers: RATE_TIERS.to_vec(),
+        tax_rate: TAX_RATE,
+        start_time: 0,
+        end_time: 86400,
+        paused: false,
+        reserve_prefix: String::new(),
+    }
+}
+
+/// Bonding curve of the default pool.
+pub fn get_default_curve(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+) -> BondingCurve {
+    let dictionary_seed_uref = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(DICT_BONDING_CURVES)
+        .expect("must have key")
+        .as_uref()
+        .expect("must convert to dictionary seed uref");
+
+    builder
+        .query_dictionary_item(None, dictionary_seed_uref, DEFAULT_POOL_ITEM_KEY)
+        .expect("should have default curve")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<BondingCurve>()
+        .expect("should be BondingCurve.")
+}
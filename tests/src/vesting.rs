@@ -0,0 +1,532 @@
+use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR};
+use casper_types::{CLValue, ContractHash, Key, U256, U512};
+use cowl_swap::{
+    constants::{ARG_BALANCE_COWL, RATE_TIERS},
+    error::SwapError,
+    events::{CsprToCowl, TokensClaimed, TokensVested, VestingScheduleUpdate},
+    vesting::{
+        set_vesting_phase, vesting_phase_at, VestingAllocation, VestingPhase, VestingSchedule,
+    },
+};
+use vesting_tests::{
+    constants::{ACCOUNT_LIQUIDITY, ACCOUNT_USER_1},
+    support::{assert_expected_error, get_event},
+};
+
+use crate::utility::installer_request_builders::{
+    cowl_swap_claim, cowl_swap_claim_phase, cowl_swap_cspr_to_cowl, cowl_swap_deposit_cowl,
+    cowl_swap_set_vesting_phase, cowl_swap_set_vesting_schedule, cowl_swap_withdraw_cowl, setup,
+    TestContext,
+};
+
+fn get_balance_cowl(
+    builder: &InMemoryWasmTestBuilder,
+    cowl_swap_contract_hash: ContractHash,
+) -> U256 {
+    let balance_key = *builder
+        .get_contract(cowl_swap_contract_hash)
+        .expect("should have swap contract")
+        .named_keys()
+        .get(ARG_BALANCE_COWL)
+        .expect("balance uref should exist");
+
+    builder
+        .query(None, balance_key, &[])
+        .expect("should be stored value.")
+        .as_cl_value()
+        .expect("should be cl value.")
+        .clone()
+        .into_t::<U256>()
+        .expect("should be U256.")
+}
+
+#[test]
+fn should_release_vested_tokens_after_cliff_linearly() {
+    let schedule = VestingSchedule {
+        start_time: 1_000,
+        cliff_duration: 100,
+        vesting_duration: 1_000,
+    };
+    let total = U256::from(1_000_000u64);
+
+    assert!(schedule.vested_amount(total, 0).unwrap().is_zero());
+    assert!(schedule.vested_amount(total, 1_099).unwrap().is_zero());
+    assert_eq!(
+        schedule.vested_amount(total, 1_100).unwrap(),
+        U256::from(100_000u64)
+    );
+    assert_eq!(
+        schedule.vested_amount(total, 1_500).unwrap(),
+        U256::from(500_000u64)
+    );
+    assert_eq!(schedule.vested_amount(total, 2_000).unwrap(), total);
+    assert_eq!(schedule.vested_amount(total, 5_000).unwrap(), total);
+
+    let allocation = VestingAllocation {
+        total,
+        claimed: U256::from(100_000u64),
+    };
+    assert_eq!(
+        allocation.releasable(Some(&schedule), 1_500).unwrap(),
+        U256::from(400_000u64)
+    );
+    assert_eq!(
+        allocation.releasable(None, 0).unwrap(),
+        U256::from(900_000u64)
+    );
+
+    // A zero duration releases everything at the start
+    let schedule = VestingSchedule {
+        start_time: 1_000,
+        cliff_duration: 0,
+        vesting_duration: 0,
+    };
+    assert!(schedule.validate().is_ok());
+    assert!(schedule.vested_amount(total, 999).unwrap().is_zero());
+    assert_eq!(schedule.vested_amount(total, 1_000).unwrap(), total);
+}
+
+#[test]
+fn should_not_validate_cliff_after_vesting_end() {
+    let error = VestingSchedule {
+        start_time: 0,
+        cliff_duration: 1_001,
+        vesting_duration: 1_000,
+    }
+    .validate()
+    .unwrap_err();
+    assert!(matches!(error, SwapError::InvalidVestingSchedule));
+
+    let error = VestingSchedule {
+        start_time: u64::MAX,
+        cliff_duration: 0,
+        vesting_duration: 1,
+    }
+    .validate()
+    .unwrap_err();
+    assert!(matches!(error, SwapError::InvalidVestingSchedule));
+}
+
+#[test]
+fn should_not_overflow_cliff_end() {
+    let error = VestingSchedule {
+        start_time: u64::MAX,
+        cliff_duration: 1,
+        vesting_duration: 1,
+    }
+    .vested_amount(U256::one(), u64::MAX)
+    .unwrap_err();
+    assert!(matches!(error, SwapError::Overflow));
+}
+
+#[test]
+fn should_keep_vesting_phases_ordered_and_apart() {
+    let schedule = VestingSchedule::default();
+    let phase = |start_time, end_time| VestingPhase {
+        start_time,
+        end_time,
+        schedule,
+    };
+
+    let mut phases = Vec::new();
+    set_vesting_phase(&mut phases, phase(2_000, 3_000)).unwrap();
+    set_vesting_phase(&mut phases, phase(0, 1_000)).unwrap();
+    assert_eq!(phases, vec![phase(0, 1_000), phase(2_000, 3_000)]);
+
+    let error = set_vesting_phase(&mut phases, phase(500, 2_500)).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidVestingSchedule));
+    let error = set_vesting_phase(&mut phases, phase(1_000, 1_000)).unwrap_err();
+    assert!(matches!(error, SwapError::InvalidVestingSchedule));
+
+    // The start identifies the phase, so setting it again moves its end
+    set_vesting_phase(&mut phases, phase(0, 2_000)).unwrap();
+    assert_eq!(phases, vec![phase(0, 2_000), phase(2_000, 3_000)]);
+
+    assert_eq!(vesting_phase_at(&phases, 1_999), Some(&phase(0, 2_000)));
+    assert_eq!(vesting_phase_at(&phases, 2_000), Some(&phase(2_000, 3_000)));
+    assert_eq!(vesting_phase_at(&phases, 3_000), None);
+}
+
+#[test]
+fn should_type_vesting_as_tuples() {
+    let phase = VestingPhase {
+        start_time: 1,
+        end_time: 2,
+        schedule: VestingSchedule {
+            start_time: 3,
+            cliff_duration: 4,
+            vesting_duration: 5,
+        },
+    };
+    let decoded = CLValue::from_t(phase)
+        .expect("should encode phase")
+        .into_t::<((u64, u64), (u64, u64, u64))>()
+        .expect("should decode as tuples");
+    assert_eq!(decoded, ((1, 2), (3, 4, 5)));
+
+    let allocation = VestingAllocation {
+        total: U256::from(10u64),
+        claimed: U256::from(4u64),
+    };
+    let decoded = CLValue::from_t(allocation)
+        .expect("should encode allocation")
+        .into_t::<(U256, U256)>()
+        .expect("should decode as a tuple");
+    assert_eq!(decoded, (allocation.total, allocation.claimed));
+}
+
+#[test]
+fn should_vest_cspr_to_cowl_until_claimed() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100_000_000_000u64);
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let schedule = VestingSchedule {
+        start_time: 0,
+        cliff_duration: 100,
+        vesting_duration: 1_000,
+    };
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        Some(schedule),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientRights as u16,
+        "should not allow to set a vesting schedule for non admin account",
+    );
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(VestingSchedule {
+            cliff_duration: 1_001,
+            ..schedule
+        }),
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidVestingSchedule as u16,
+        "should not allow a cliff after the end of the vesting",
+    );
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        Some(schedule),
+    )
+    .expect_success()
+    .commit();
+
+    let event: VestingScheduleUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(event.pool, pool);
+    assert!(event.enabled);
+    assert_eq!(event.cliff_duration, 100);
+    assert_eq!(event.vesting_duration, 1_000);
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+
+    let event: CsprToCowl = get_event(&builder, &cowl_swap_contract_hash.into(), 2);
+    assert_eq!(event.cowl_amount, cowl_amount);
+
+    let event: TokensVested = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.beneficiary, Key::from(account_user_1));
+    assert_eq!(event.amount, cowl_amount);
+    assert_eq!(event.total, cowl_amount);
+
+    // The bought tokens stay in the contract
+    assert_eq!(
+        get_balance_cowl(&builder, cowl_swap_contract_hash),
+        cowl_pool_amount
+    );
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::NothingToClaim as u16,
+        "should not release anything before the cliff",
+    );
+
+    cowl_swap_withdraw_cowl(
+        &mut builder,
+        &DEFAULT_ACCOUNT_ADDR,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InsufficientLiquidity as u16,
+        "should not allow to withdraw tokens locked for buyers",
+    );
+
+    cowl_swap_set_vesting_schedule(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        None,
+    )
+    .expect_success()
+    .commit();
+
+    let event: VestingScheduleUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 4);
+    assert!(!event.enabled);
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokensClaimed = get_event(&builder, &cowl_swap_contract_hash.into(), 5);
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.beneficiary, Key::from(account_user_1));
+    assert_eq!(event.amount, cowl_amount);
+
+    assert_eq!(
+        get_balance_cowl(&builder, cowl_swap_contract_hash),
+        cowl_pool_amount - cowl_amount
+    );
+
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::NothingToClaim as u16,
+        "should not release the same tokens twice",
+    );
+}
+
+#[test]
+fn should_vest_purchases_with_their_phase_schedule() {
+    let (
+        mut builder,
+        TestContext {
+            cowl_swap_contract_hash,
+            cowl_swap_contract_package,
+            cowl_cep18_token_package_hash,
+            ref test_accounts,
+            ..
+        },
+    ) = setup();
+
+    let pool = Key::from(cowl_cep18_token_package_hash);
+    let account_user_1 = *test_accounts.get(&ACCOUNT_USER_1).unwrap();
+
+    let liquidity = *test_accounts.get(&ACCOUNT_LIQUIDITY).unwrap();
+    let cowl_pool_amount = U256::from(100_000_000_000u64);
+    cowl_swap_deposit_cowl(
+        &mut builder,
+        &liquidity,
+        &cowl_cep18_token_package_hash,
+        &cowl_swap_contract_package,
+        cowl_pool_amount,
+    )
+    .expect_success()
+    .commit();
+
+    // Purchases of the first phase are claimable right away
+    let phase = VestingPhase {
+        start_time: 0,
+        end_time: 1_000,
+        schedule: VestingSchedule::default(),
+    };
+    cowl_swap_set_vesting_phase(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        phase,
+        true,
+    )
+    .expect_success()
+    .commit();
+
+    let event: VestingScheduleUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 1);
+    assert_eq!(event.phase, Some(0));
+    assert!(event.enabled);
+
+    cowl_swap_set_vesting_phase(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        VestingPhase {
+            start_time: 500,
+            end_time: 2_000,
+            ..phase
+        },
+        true,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::InvalidVestingSchedule as u16,
+        "should not allow overlapping phases",
+    );
+
+    let cspr_amount = U512::from(10_000_000_000u64);
+    cowl_swap_cspr_to_cowl(
+        &mut builder,
+        &account_user_1,
+        &cowl_swap_contract_package,
+        cspr_amount,
+    )
+    .expect_success()
+    .commit();
+
+    let cowl_amount =
+        U256::from_dec_str(&(cspr_amount * RATE_TIERS.first().unwrap().rate).to_string()).unwrap();
+
+    let event: TokensVested = get_event(&builder, &cowl_swap_contract_hash.into(), 3);
+    assert_eq!(event.phase, Some(0));
+    assert_eq!(event.amount, cowl_amount);
+
+    assert_eq!(
+        get_balance_cowl(&builder, cowl_swap_contract_hash),
+        cowl_pool_amount
+    );
+
+    // The purchase is not on the pool-wide allocation
+    cowl_swap_claim(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        None,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::NothingToClaim as u16,
+        "should claim phase purchases with their phase",
+    );
+
+    cowl_swap_claim_phase(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &account_user_1,
+        &pool,
+        phase.start_time,
+    )
+    .expect_success()
+    .commit();
+
+    let event: TokensClaimed = get_event(&builder, &cowl_swap_contract_hash.into(), 4);
+    assert_eq!(event.phase, Some(0));
+    assert_eq!(event.beneficiary, Key::from(account_user_1));
+    assert_eq!(event.amount, cowl_amount);
+
+    assert_eq!(
+        get_balance_cowl(&builder, cowl_swap_contract_hash),
+        cowl_pool_amount - cowl_amount
+    );
+
+    cowl_swap_set_vesting_phase(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        phase,
+        false,
+    )
+    .expect_success()
+    .commit();
+
+    let event: VestingScheduleUpdate = get_event(&builder, &cowl_swap_contract_hash.into(), 5);
+    assert_eq!(event.phase, Some(0));
+    assert!(!event.enabled);
+
+    cowl_swap_set_vesting_phase(
+        &mut builder,
+        &cowl_swap_contract_hash,
+        &DEFAULT_ACCOUNT_ADDR,
+        &pool,
+        phase,
+        false,
+    )
+    .expect_failure();
+
+    let error = builder.get_error().expect("must have error");
+    assert_expected_error(
+        error,
+        SwapError::MissingVestingSchedule as u16,
+        "should not remove a phase twice",
+    );
+}